@@ -59,6 +59,8 @@ async function activateMarketEntry(market: Awaited<ReturnType<typeof marketServi
     try {
       await anchorClient.activateMarket({
         marketPubkey: pubkey,
+        asset,
+        timeframe,
         strikePrice: currentPrice,
       });
       logger.info(`✅ Activated on-chain market ${asset}-${timeframe} with strike $${currentPrice.toLocaleString()}`);
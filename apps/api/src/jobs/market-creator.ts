@@ -185,7 +185,7 @@ async function verifyOnChainMarket(pubkey: string): Promise<boolean> {
 
 /**
  * Derive the market PDA from seeds
- * Must match on-chain: seeds = [b"market", asset.as_bytes(), timeframe.as_bytes(), expiry_ts.to_le_bytes()]
+ * Must match on-chain: seeds = [b"market", asset.as_bytes(), timeframe.as_bytes(), expiry_ts.to_le_bytes(), [strike_index]]
  * Note: asset and timeframe use raw bytes, NOT padded
  */
 export function deriveMarketPda(
@@ -203,6 +203,7 @@ export function deriveMarketPda(
       Buffer.from(asset),      // Raw bytes, no padding
       Buffer.from(timeframe),  // Raw bytes, no padding
       expiryBuffer,
+      Buffer.from([0]),        // strike_index - one strike per expiry
     ],
     PROGRAM_ID
   );
@@ -237,7 +237,7 @@ async function resolveMarket(
     
     onChainResolvePending = anchorClient.resolveMarket({
       marketPubkey: market.pubkey,
-      outcome,
+      asset: market.asset,
       finalPrice,
     }).then(sig => {
       logger.info(`✅ Market resolved on-chain: ${sig}`);
@@ -169,8 +169,32 @@ export function getGlobalStatePda(): PublicKey {
   return pda;
 }
 
-export function getMarketPda(asset: string, timeframe: string, expiryTs: number): PublicKey {
-  // Seeds must match on-chain: [b"market", asset.as_bytes(), timeframe.as_bytes(), expiry_ts.to_le_bytes()]
+export function getResolverConfigPda(): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('resolver_config')],
+    PROGRAM_ID
+  );
+  return pda;
+}
+
+export function getAssetConfigPda(asset: string): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('asset_config'), Buffer.from(asset)],
+    PROGRAM_ID
+  );
+  return pda;
+}
+
+export function getTimeframeConfigPda(timeframe: string): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('timeframe_config'), Buffer.from(timeframe)],
+    PROGRAM_ID
+  );
+  return pda;
+}
+
+export function getMarketPda(asset: string, timeframe: string, expiryTs: number, strikeIndex = 0): PublicKey {
+  // Seeds must match on-chain: [b"market", asset.as_bytes(), timeframe.as_bytes(), expiry_ts.to_le_bytes(), [strike_index]]
   // Note: asset and timeframe are NOT padded - use raw string bytes
   const expiryBuffer = Buffer.alloc(8);
   expiryBuffer.writeBigInt64LE(BigInt(expiryTs), 0);
@@ -181,12 +205,32 @@ export function getMarketPda(asset: string, timeframe: string, expiryTs: number)
       Buffer.from(asset),      // Raw bytes, no padding
       Buffer.from(timeframe),  // Raw bytes, no padding
       expiryBuffer,
+      Buffer.from([strikeIndex]),
     ],
     PROGRAM_ID
   );
   return pda;
 }
 
+export function getMarketGroupPda(asset: string, timeframe: string, expiryTs: number): PublicKey {
+  const expiryBuffer = Buffer.alloc(8);
+  expiryBuffer.writeBigInt64LE(BigInt(expiryTs), 0);
+
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('market_group'), Buffer.from(asset), Buffer.from(timeframe), expiryBuffer],
+    PROGRAM_ID
+  );
+  return pda;
+}
+
+export function getMarketSeriesPda(asset: string, timeframe: string): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('market_series'), Buffer.from(asset), Buffer.from(timeframe)],
+    PROGRAM_ID
+  );
+  return pda;
+}
+
 export function getMarketVaultPda(marketPubkey: PublicKey): PublicKey {
   const [pda] = PublicKey.findProgramAddressSync(
     [Buffer.from('vault'), marketPubkey.toBuffer()],
@@ -544,6 +588,8 @@ export class AnchorClient {
       programId: PROGRAM_ID,
       keys: [
         { pubkey: market, isSigner: false, isWritable: true },
+        { pubkey: getGlobalStatePda(), isSigner: false, isWritable: false },
+        { pubkey: getResolverConfigPda(), isSigner: false, isWritable: false },
         { pubkey: vault, isSigner: false, isWritable: true },
         { pubkey: position, isSigner: false, isWritable: true },
         { pubkey: userUsdc, isSigner: false, isWritable: true },
@@ -926,16 +972,16 @@ export class AnchorClient {
 
   /**
    * Resolve a market on-chain after expiry
-   * The relayer determines the outcome from real price feeds (Binance/Coinbase)
-   * and passes it to the on-chain instruction.
+   * The relayer reports the final price from real price feeds (Binance/Coinbase).
+   * The on-chain program derives the outcome from final price vs strike (NO wins ties).
    * 
    * @param params.marketPubkey - The market PDA address
-   * @param params.outcome - 'YES' or 'NO' determined by relayer
+   * @param params.asset - The market's asset (for the asset config PDA)
    * @param params.finalPrice - Final price at resolution (will be stored on-chain)
    */
   async resolveMarket(params: {
     marketPubkey: string;
-    outcome: 'YES' | 'NO';
+    asset: string;
     finalPrice: number;
   }): Promise<string> {
     if (!this.isReady()) {
@@ -947,12 +993,11 @@ export class AnchorClient {
     // Build instruction data: discriminator + ResolveMarketArgs
     const discriminator = computeDiscriminator('resolve_market');
     
-    // ResolveMarketArgs: outcome (u8) + final_price (u64)
-    const argsBuffer = Buffer.alloc(9);
-    argsBuffer.writeUInt8(params.outcome === 'YES' ? 0 : 1, 0);  // outcome: 0=Yes, 1=No
+    // ResolveMarketArgs: final_price (u64)
+    const argsBuffer = Buffer.alloc(8);
     // Final price with 8 decimals (matching on-chain strike price format)
     const finalPriceU64 = BigInt(Math.floor(params.finalPrice * 100_000_000));
-    argsBuffer.writeBigUInt64LE(finalPriceU64, 1);
+    argsBuffer.writeBigUInt64LE(finalPriceU64, 0);
     
     const data = Buffer.concat([discriminator, argsBuffer]);
 
@@ -960,13 +1005,19 @@ export class AnchorClient {
       programId: PROGRAM_ID,
       keys: [
         { pubkey: market, isSigner: false, isWritable: true },
+        { pubkey: getGlobalStatePda(), isSigner: false, isWritable: false },
+        { pubkey: getAssetConfigPda(params.asset), isSigner: false, isWritable: false },
+        // resolver_config is always passed (may be uninitialized) so a configured quorum is enforced
+        { pubkey: getResolverConfigPda(), isSigner: false, isWritable: false },
+        // price_samples is optional - only needed for TWAP/median markets, pass None
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
         { pubkey: this.relayerKeypair!.publicKey, isSigner: true, isWritable: false },
       ],
       data,
     });
 
-    const signature = await this.submitTransaction([instruction], [], `Resolve Market ${params.marketPubkey.slice(0, 8)} (${params.outcome})`);
-    logger.info(`Market resolved on-chain: ${signature} (outcome=${params.outcome}, price=${params.finalPrice})`);
+    const signature = await this.submitTransaction([instruction], [], `Resolve Market ${params.marketPubkey.slice(0, 8)} (price=${params.finalPrice})`);
+    logger.info(`Market resolved on-chain: ${signature} (price=${params.finalPrice})`);
 
     return signature;
   }
@@ -979,13 +1030,15 @@ export class AnchorClient {
     timeframe: string;
     strikePrice: number;
     expiryTs: number;
+    strikeIndex?: number;  // Position in the expiry's strike ladder (default 0)
   }): Promise<TransactionInstruction> {
     if (!this.relayerKeypair) {
       throw new Error('Relayer not initialized');
     }
 
     const globalState = getGlobalStatePda();
-    const market = getMarketPda(params.asset, params.timeframe, params.expiryTs);
+    const strikeIndex = params.strikeIndex ?? 0;
+    const market = getMarketPda(params.asset, params.timeframe, params.expiryTs, strikeIndex);
     const vault = await getAssociatedTokenAddress(USDC_MINT, market, true);
 
     const discriminator = computeDiscriminator('initialize_market');
@@ -1013,13 +1066,19 @@ export class AnchorClient {
       timeframeBytes,
       strikePriceBuffer,
       expiryTsBuffer,
+      Buffer.from([strikeIndex]),
     ]);
 
     return new TransactionInstruction({
       programId: PROGRAM_ID,
       keys: [
         { pubkey: globalState, isSigner: false, isWritable: true },
+        { pubkey: getAssetConfigPda(params.asset), isSigner: false, isWritable: false },
+        { pubkey: getTimeframeConfigPda(params.timeframe), isSigner: false, isWritable: false },
         { pubkey: market, isSigner: false, isWritable: true },
+        { pubkey: getMarketGroupPda(params.asset, params.timeframe, params.expiryTs), isSigner: false, isWritable: true },
+        // market_series is always passed (may be uninitialized) so series slots can't be created open
+        { pubkey: getMarketSeriesPda(params.asset, params.timeframe), isSigner: false, isWritable: false },
         { pubkey: vault, isSigner: false, isWritable: true },
         { pubkey: USDC_MINT, isSigner: false, isWritable: false },
         { pubkey: this.relayerKeypair.publicKey, isSigner: true, isWritable: true },
@@ -1041,8 +1100,9 @@ export class AnchorClient {
     timeframe: string;
     strikePrice: number;
     expiryTs: number;  // Unix timestamp in seconds
+    strikeIndex?: number;
   }): Promise<string> {
-    const market = getMarketPda(params.asset, params.timeframe, params.expiryTs);
+    const market = getMarketPda(params.asset, params.timeframe, params.expiryTs, params.strikeIndex ?? 0);
     const instruction = await this.buildInitializeMarketInstruction(params);
     const status = params.strikePrice > 0 ? 'OPEN' : 'PENDING';
     const signature = await this.submitTransaction([instruction], [], `Init Market ${params.asset}-${params.timeframe} (${status}) (${market.toBase58().slice(0, 8)})`);
@@ -1058,6 +1118,8 @@ export class AnchorClient {
    */
   async buildActivateMarketInstruction(params: {
     marketPubkey: string;
+    asset: string;
+    timeframe: string;
     strikePrice: number;  // Strike price in dollars (e.g., 95432.50)
   }): Promise<TransactionInstruction> {
     if (!this.relayerKeypair) {
@@ -1078,6 +1140,9 @@ export class AnchorClient {
       programId: PROGRAM_ID,
       keys: [
         { pubkey: market, isSigner: false, isWritable: true },
+        { pubkey: getAssetConfigPda(params.asset), isSigner: false, isWritable: false },
+        // market_series is always passed (may be uninitialized) so series strikes can't be bypassed
+        { pubkey: getMarketSeriesPda(params.asset, params.timeframe), isSigner: false, isWritable: false },
         { pubkey: this.relayerKeypair.publicKey, isSigner: true, isWritable: true },
       ],
       data,
@@ -1090,6 +1155,8 @@ export class AnchorClient {
    */
   async activateMarket(params: {
     marketPubkey: string;
+    asset: string;
+    timeframe: string;
     strikePrice: number;  // Strike price in dollars (e.g., 95432.50)
   }): Promise<string> {
     if (!this.isReady()) {
@@ -119,6 +119,7 @@ function deriveMarketPda(
       Buffer.from(asset),      // Raw bytes
       Buffer.from(timeframe),  // Raw bytes
       expiryBuffer,
+      Buffer.from([0]),        // strike_index - one strike per expiry
     ],
     PROGRAM_ID
   );
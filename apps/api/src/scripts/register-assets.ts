@@ -0,0 +1,86 @@
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+} from '@solana/web3.js';
+import * as anchor from '@coral-xyz/anchor';
+import bs58 from 'bs58';
+import { config } from '../config.js';
+import fs from 'fs';
+import path from 'path';
+import { fileURLToPath } from 'url';
+
+const __filename = fileURLToPath(import.meta.url);
+const __dirname = path.dirname(__filename);
+
+// Pyth price feed IDs (same on every cluster)
+const ASSETS = [
+  { asset: 'BTC', feedId: 'e62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43', strikeDecimals: 8 },
+  { asset: 'ETH', feedId: 'ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace', strikeDecimals: 8 },
+  { asset: 'SOL', feedId: 'ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d', strikeDecimals: 8 },
+];
+
+const TIMEFRAMES = ['5m', '15m', '1h', '4h', '24h'];
+
+// Set to require oracle activation (disables the keeper's authority-chosen strikes)
+const ORACLE_ACTIVATION = false;
+
+// Run register-timeframes first - markets need both the asset and the timeframe registered
+async function registerAssets() {
+  console.log('🔄 Registering On-Chain Assets...\n');
+
+  if (!config.relayerPrivateKey) {
+    console.error('❌ RELAYER_PRIVATE_KEY not set in .env');
+    process.exit(1);
+  }
+
+  // Load IDL
+  const idlPath = path.resolve(__dirname, '../../../../packages/contracts/target/idl/degen_terminal.json');
+  const idl = JSON.parse(fs.readFileSync(idlPath, 'utf8'));
+
+  // Setup Connection & Provider
+  const connection = new Connection(config.solanaRpcUrl, 'confirmed');
+  const relayerKeypair = Keypair.fromSecretKey(bs58.decode(config.relayerPrivateKey));
+  const wallet = new anchor.Wallet(relayerKeypair);
+  const provider = new anchor.AnchorProvider(connection, wallet, { commitment: 'confirmed' });
+  
+  const programId = new PublicKey(config.programId);
+  const program = new anchor.Program(idl, programId, provider);
+
+  const [globalStatePda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('global')],
+    program.programId
+  );
+
+  console.log('Program ID:', program.programId.toBase58());
+  console.log('Admin (Relayer):', relayerKeypair.publicKey.toBase58());
+
+  for (const { asset, feedId, strikeDecimals } of ASSETS) {
+    const [assetConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('asset_config'), Buffer.from(asset)],
+      program.programId
+    );
+
+    try {
+      const tx = await program.methods
+        .setAssetConfig(asset, Array.from(Buffer.from(feedId, 'hex')), strikeDecimals, true, ORACLE_ACTIVATION, TIMEFRAMES)
+        .accounts({
+          globalState: globalStatePda,
+          assetConfig: assetConfigPda,
+          admin: relayerKeypair.publicKey,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+
+      console.log(`✅ ${asset} registered (${assetConfigPda.toBase58()}): ${tx}`);
+    } catch (err: any) {
+      console.error(`❌ Failed to register ${asset}:`, err.message);
+      if (err.logs) {
+        console.error('\nProgram logs:', err.logs);
+      }
+    }
+  }
+}
+
+registerAssets().catch(console.error);
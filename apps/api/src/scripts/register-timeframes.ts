@@ -0,0 +1,101 @@
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+} from '@solana/web3.js';
+import * as anchor from '@coral-xyz/anchor';
+import bs58 from 'bs58';
+import { config } from '../config.js';
+import fs from 'fs';
+import path from 'path';
+import { fileURLToPath } from 'url';
+
+const __filename = fileURLToPath(import.meta.url);
+const __dirname = path.dirname(__filename);
+
+// Close buffer matches the MM bot (stops quoting 30s before expiry); lead time
+// covers the market-creator look-ahead (current + next interval). Trading params
+// default to $0.01 ticks and the protocol size limits; 24h markets get finer
+// ticks and larger caps.
+const DAILY_TRADING_PARAMS = {
+  tickSize: new anchor.BN(1_000),
+  minPrice: new anchor.BN(1_000),
+  maxPrice: new anchor.BN(999_000),
+  minOrderSize: new anchor.BN(1_000),
+  maxOrderSize: new anchor.BN(1_000_000_000_000),
+  maxPositionSize: new anchor.BN(5_000_000_000_000),
+};
+const TIMEFRAMES = [
+  { timeframe: '5m', duration: 5 * 60, closeBuffer: 30, tradingParams: null },
+  { timeframe: '15m', duration: 15 * 60, closeBuffer: 30, tradingParams: null },
+  { timeframe: '1h', duration: 60 * 60, closeBuffer: 30, tradingParams: null },
+  { timeframe: '4h', duration: 4 * 60 * 60, closeBuffer: 30, tradingParams: null },
+  { timeframe: '24h', duration: 24 * 60 * 60, closeBuffer: 30, tradingParams: DAILY_TRADING_PARAMS },
+];
+const MIN_LEAD_TIME = 60;
+
+async function registerTimeframes() {
+  console.log('🔄 Registering On-Chain Timeframes...\n');
+
+  if (!config.relayerPrivateKey) {
+    console.error('❌ RELAYER_PRIVATE_KEY not set in .env');
+    process.exit(1);
+  }
+
+  // Load IDL
+  const idlPath = path.resolve(__dirname, '../../../../packages/contracts/target/idl/degen_terminal.json');
+  const idl = JSON.parse(fs.readFileSync(idlPath, 'utf8'));
+
+  // Setup Connection & Provider
+  const connection = new Connection(config.solanaRpcUrl, 'confirmed');
+  const relayerKeypair = Keypair.fromSecretKey(bs58.decode(config.relayerPrivateKey));
+  const wallet = new anchor.Wallet(relayerKeypair);
+  const provider = new anchor.AnchorProvider(connection, wallet, { commitment: 'confirmed' });
+  
+  const programId = new PublicKey(config.programId);
+  const program = new anchor.Program(idl, programId, provider);
+
+  const [globalStatePda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('global')],
+    program.programId
+  );
+
+  console.log('Program ID:', program.programId.toBase58());
+  console.log('Admin (Relayer):', relayerKeypair.publicKey.toBase58());
+
+  for (const { timeframe, duration, closeBuffer, tradingParams } of TIMEFRAMES) {
+    const [timeframeConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('timeframe_config'), Buffer.from(timeframe)],
+      program.programId
+    );
+
+    try {
+      const tx = await program.methods
+        .setTimeframeConfig(
+          timeframe,
+          new anchor.BN(duration),
+          new anchor.BN(closeBuffer),
+          new anchor.BN(MIN_LEAD_TIME),
+          new anchor.BN(3 * duration),
+          tradingParams
+        )
+        .accounts({
+          globalState: globalStatePda,
+          timeframeConfig: timeframeConfigPda,
+          admin: relayerKeypair.publicKey,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+
+      console.log(`✅ ${timeframe} registered (${timeframeConfigPda.toBase58()}): ${tx}`);
+    } catch (err: any) {
+      console.error(`❌ Failed to register ${timeframe}:`, err.message);
+      if (err.logs) {
+        console.error('\nProgram logs:', err.logs);
+      }
+    }
+  }
+}
+
+registerTimeframes().catch(console.error);
@@ -57,7 +57,8 @@ export function getGlobalStatePda(): [PublicKey, number] {
 export function getMarketPda(
   asset: string,
   timeframe: string,
-  expiryTimestamp: number
+  expiryTimestamp: number,
+  strikeIndex = 0
 ): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
     [
@@ -65,6 +66,7 @@ export function getMarketPda(
       Buffer.from(asset),
       Buffer.from(timeframe),
       Buffer.from(expiryTimestamp.toString()),
+      Buffer.from([strikeIndex]),
     ],
     PROGRAM_ID
   );
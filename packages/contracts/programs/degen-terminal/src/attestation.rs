@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::get_instruction_relative;
+use crate::errors::DegenError;
+
+// ============================================================================
+// ED25519 PRICE ATTESTATIONS
+// ============================================================================
+
+/// Ed25519 precompile instruction layout (see `solana_sdk::ed25519_instruction`)
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+const PUBKEY_SIZE: usize = 32;
+
+/// What an attested price is used for (prevents replaying a strike as a final price)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AttestationKind {
+    Strike = 0,     // Price at market start (activation)
+    Final = 1,      // Price at expiry (resolution)
+}
+
+/// Message signed by a price publisher (borsh-serialized)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct PriceAttestation {
+    /// Market the price is for
+    pub market: Pubkey,
+    /// Strike or final price
+    pub kind: AttestationKind,
+    /// Price (8 decimals)
+    pub price: u64,
+    /// When the price was observed (unix timestamp)
+    pub publish_time: i64,
+}
+
+impl PriceAttestation {
+    pub const SIZE: usize = 32 +    // market
+        1 +                         // kind
+        8 +                         // price
+        8;                          // publish_time
+}
+
+/// Verify that the instruction immediately before the current one is an Ed25519
+/// precompile instruction with a single signature over `attestation`, and return
+/// the signer's public key.
+///
+/// The precompile fails the whole transaction if the signature is invalid, so
+/// if it is present we only need to check which key signed which message.
+/// Offsets must point into the precompile instruction itself (index `u16::MAX`).
+pub fn verify_price_attestation(
+    instructions_sysvar: &AccountInfo,
+    attestation: &PriceAttestation,
+) -> Result<Pubkey> {
+    let ix = get_instruction_relative(-1, instructions_sysvar)
+        .map_err(|_| error!(DegenError::MissingSignatureVerification))?;
+    require_keys_eq!(ix.program_id, ed25519_program::ID, DegenError::MissingSignatureVerification);
+
+    let data = &ix.data;
+    require!(data.len() >= SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE, DegenError::InvalidSignature);
+    require!(data[0] == 1, DegenError::InvalidSignature);
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let offsets = SIGNATURE_OFFSETS_START;
+    let signature_ix_index = read_u16(offsets + 2);
+    let pubkey_offset = read_u16(offsets + 4) as usize;
+    let pubkey_ix_index = read_u16(offsets + 6);
+    let message_offset = read_u16(offsets + 8) as usize;
+    let message_size = read_u16(offsets + 10) as usize;
+    let message_ix_index = read_u16(offsets + 12);
+
+    // Signature, key and message must all live in the precompile instruction
+    require!(
+        signature_ix_index == u16::MAX && pubkey_ix_index == u16::MAX && message_ix_index == u16::MAX,
+        DegenError::InvalidSignature
+    );
+
+    let pubkey_bytes = data
+        .get(pubkey_offset..pubkey_offset + PUBKEY_SIZE)
+        .ok_or(DegenError::InvalidSignature)?;
+    let message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(DegenError::InvalidSignature)?;
+
+    let expected = attestation.try_to_vec().map_err(|_| error!(DegenError::InvalidSignature))?;
+    require!(message == expected.as_slice(), DegenError::InvalidSignature);
+
+    let signer = Pubkey::try_from(pubkey_bytes).map_err(|_| error!(DegenError::InvalidSignature))?;
+    Ok(signer)
+}
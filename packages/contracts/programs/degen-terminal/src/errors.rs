@@ -25,7 +25,7 @@ pub enum DegenError {
     #[msg("Market is not pending (cannot activate)")]
     MarketNotPending,
     
-    #[msg("Market is closing soon (within 30 seconds)")]
+    #[msg("Market is closing soon (within the close buffer)")]
     MarketClosing,
     
     #[msg("Market has already expired")]
@@ -95,6 +95,9 @@ pub enum DegenError {
     #[msg("Missing seller USDC receive account (required for closing trades)")]
     MissingSellerAccount,
     
+    #[msg("Merge terms do not match the seller's ask order")]
+    MergeOrderMismatch,
+    
     // =========================================================================
     // Position Errors (6050-6069)
     // =========================================================================
@@ -134,7 +137,7 @@ pub enum DegenError {
     #[msg("Invalid oracle account")]
     InvalidOracle,
     
-    #[msg("Oracle price is stale (older than 60 seconds)")]
+    #[msg("Oracle price is stale or not published at the required time")]
     StaleOraclePrice,
     
     #[msg("Oracle price is invalid or unavailable")]
@@ -168,4 +171,240 @@ pub enum DegenError {
     
     #[msg("Signature does not match the expected signer")]
     SignerMismatch,
+    
+    // =========================================================================
+    // Resolver Errors (6120-6139)
+    // =========================================================================
+    
+    #[msg("Too many resolvers")]
+    TooManyResolvers,
+    
+    #[msg("Invalid resolver (default or duplicate key)")]
+    InvalidResolver,
+    
+    #[msg("Invalid quorum threshold - must be between 1 and the number of resolvers")]
+    InvalidThreshold,
+    
+    #[msg("Market requires a quorum of resolver votes - use submit_resolution")]
+    QuorumRequired,
+    
+    #[msg("Resolver has already voted on this market")]
+    AlreadyVoted,
+    
+    #[msg("Market resolution is disputed - admin must resolve")]
+    MarketDisputed,
+    
+    // =========================================================================
+    // Dispute Errors (6140-6159)
+    // =========================================================================
+    
+    #[msg("Optimistic resolution is not enabled (no bond or dispute window configured)")]
+    OptimisticResolutionDisabled,
+    
+    #[msg("Invalid dispute configuration")]
+    InvalidDisputeConfig,
+    
+    #[msg("Market has no pending outcome proposal")]
+    MarketNotProposed,
+    
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+    
+    #[msg("Dispute window is still open")]
+    DisputeWindowOpen,
+    
+    #[msg("Proposal has already been challenged")]
+    ProposalAlreadyChallenged,
+    
+    #[msg("Proposal has not been challenged")]
+    ProposalNotChallenged,
+    
+    #[msg("Proposer cannot challenge their own proposal")]
+    SelfChallenge,
+    
+    #[msg("The proposer or challenger cannot settle their own dispute")]
+    DisputeParticipant,
+    
+    // =========================================================================
+    // Attestation Errors (6160-6169)
+    // =========================================================================
+    
+    #[msg("Too many price publishers")]
+    TooManyPricePublishers,
+    
+    #[msg("Invalid price publisher (default or duplicate key)")]
+    InvalidPricePublisher,
+    
+    // =========================================================================
+    // Activation Errors (6170-6179)
+    // =========================================================================
+    
+    #[msg("Market trading window has not started yet")]
+    MarketNotStarted,
+    
+    #[msg("Market strike must be set from the oracle")]
+    OracleActivationRequired,
+    
+    // =========================================================================
+    // Sampling Errors (6180-6189)
+    // =========================================================================
+    
+    #[msg("Invalid resolution method or parameters for this market")]
+    InvalidResolutionMethod,
+    
+    #[msg("Not enough price samples recorded to resolve")]
+    InsufficientPriceSamples,
+    
+    #[msg("Price sample is outside the resolution window")]
+    SampleOutsideWindow,
+    
+    #[msg("A price sample is already recorded for this time slot")]
+    DuplicatePriceSample,
+    
+    // =========================================================================
+    // Aggregation Errors (6190-6199)
+    // =========================================================================
+    
+    #[msg("Invalid oracle source configuration")]
+    InvalidOracleConfig,
+    
+    #[msg("Not enough valid, agreeing oracle sources")]
+    InsufficientOracleSources,
+    
+    #[msg("A configured oracle source account was not provided")]
+    MissingOracleSource,
+    
+    // =========================================================================
+    // Asset Errors (6200-6209)
+    // =========================================================================
+    
+    #[msg("Asset is disabled")]
+    AssetDisabled,
+    
+    #[msg("Timeframe is not enabled for this asset")]
+    TimeframeNotAllowed,
+    
+    #[msg("Invalid asset configuration")]
+    InvalidAssetConfig,
+    
+    // =========================================================================
+    // Timeframe Errors (6210-6219)
+    // =========================================================================
+    
+    #[msg("Invalid timeframe configuration")]
+    InvalidTimeframeConfig,
+    
+    #[msg("Expiry is not on the timeframe grid")]
+    ExpiryNotAligned,
+    
+    #[msg("Expiry is outside the timeframe's lead time bounds")]
+    InvalidLeadTime,
+    
+    // =========================================================================
+    // Market Group Errors (6220-6229)
+    // =========================================================================
+    
+    #[msg("Strike index must be the next free slot in the market group")]
+    InvalidStrikeIndex,
+    
+    #[msg("Market group is full")]
+    MarketGroupFull,
+    
+    // =========================================================================
+    // Categorical Errors (6230-6239)
+    // =========================================================================
+    
+    #[msg("Outcome is not valid for this market")]
+    InvalidOutcome,
+    
+    #[msg("Invalid bucket bounds - must be non-zero and strictly ascending")]
+    InvalidBucketBounds,
+    
+    // =========================================================================
+    // Scalar Errors (6240-6249)
+    // =========================================================================
+    
+    #[msg("Invalid scalar bounds - upper bound must be above the lower bound")]
+    InvalidScalarBounds,
+    
+    // =========================================================================
+    // Barrier Errors (6250-6259)
+    // =========================================================================
+    
+    #[msg("Invalid barrier - price must be non-zero")]
+    InvalidBarrier,
+    
+    #[msg("Oracle price does not touch the barrier")]
+    BarrierNotTouched,
+    
+    #[msg("Barrier market cannot resolve NO until the touch grace period after expiry")]
+    BarrierTouchPending,
+    
+    // =========================================================================
+    // Series Errors (6260-6269)
+    // =========================================================================
+    
+    #[msg("Market is part of a rolling series - its strike is set by roll_series")]
+    SeriesStrikeRequired,
+    
+    #[msg("Market does not continue the series")]
+    InvalidSeriesMarket,
+    
+    #[msg("Market series is disabled")]
+    SeriesDisabled,
+    
+    // =========================================================================
+    // Schedule Errors (6270-6279)
+    // =========================================================================
+    
+    #[msg("Invalid market schedule configuration")]
+    InvalidScheduleConfig,
+    
+    #[msg("Market schedule is disabled")]
+    ScheduleDisabled,
+    
+    #[msg("Next scheduled market is not due yet")]
+    ScheduleNotDue,
+    
+    #[msg("Market schedule cannot cover the rent and tip")]
+    ScheduleUnderfunded,
+    
+    // =========================================================================
+    // Order Type Errors (6280-6289)
+    // =========================================================================
+    
+    #[msg("Fill-or-kill order must be filled in full")]
+    FillOrKillNotFilled,
+    
+    // =========================================================================
+    // Order Book Errors (6290-6299)
+    // =========================================================================
+    
+    #[msg("Order book is full")]
+    OrderBookFull,
+    
+    #[msg("Only active, unexpired limit orders can rest on the order book")]
+    OrderNotRestable,
+    
+    #[msg("Order rests on the order book - pass the market's order book")]
+    OrderOnBook,
+    
+    #[msg("Order is not the best order on its side of the book")]
+    NotTopOfBook,
+    
+    #[msg("Best bid and ask do not cross")]
+    BookNotCrossed,
+    
+    #[msg("Order escrow is below the minimum for resting on the book")]
+    RestingOrderTooSmall,
+    
+    // =========================================================================
+    // Batch Match Errors (6300-6309)
+    // =========================================================================
+    
+    #[msg("Invalid batch match accounts - expected an (order, position, USDC) triple per maker")]
+    InvalidBatchAccounts,
+    
+    #[msg("Order escrow does not cover the fill and taker fee")]
+    InsufficientEscrow,
 }
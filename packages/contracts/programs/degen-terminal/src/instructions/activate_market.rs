@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::{Market, MarketStatus};
+use crate::state::{AssetConfig, Market, MarketSeries, MarketStatus};
 use crate::errors::DegenError;
 
 #[derive(Accounts)]
@@ -11,6 +11,22 @@ pub struct ActivateMarket<'info> {
     )]
     pub market: Account<'info, Market>,
     
+    /// Registered asset (must be enabled)
+    #[account(
+        seeds = [AssetConfig::SEED, market.asset_bytes()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+    
+    /// Rolling series for the market's asset and timeframe - always passed so a series
+    /// strike cannot be bypassed. May be uninitialized if no series has been set up.
+    /// CHECK: PDA address is enforced by seeds; contents are loaded with `MarketSeries::load_optional`
+    #[account(
+        seeds = [MarketSeries::SEED, market.asset_bytes(), market.timeframe_bytes()],
+        bump
+    )]
+    pub market_series: UncheckedAccount<'info>,
+    
     #[account(mut)]
     pub authority: Signer<'info>,
 }
@@ -29,8 +45,20 @@ pub fn activate_market(
 ) -> Result<()> {
     let clock = Clock::get()?;
     let market = &mut ctx.accounts.market;
+    let asset_config = &ctx.accounts.asset_config;
+    
+    // New markets can't go live on a disabled asset
+    require!(asset_config.enabled, DegenError::AssetDisabled);
+    
+    // Oracle-activated markets take their strike from the oracle, not the authority
+    require!(!market.oracle_activation, DegenError::OracleActivationRequired);
+    
+    // Base markets of a rolling series take their strike from `roll_series`
+    let market_series = MarketSeries::load_optional(&ctx.accounts.market_series)?;
+    require!(!market_series.is_some_and(|series| series.chains(market)), DegenError::SeriesStrikeRequired);
     
-    // Validate strike price
+    // Validate strike price (at the asset's precision)
+    let strike_price = asset_config.normalize_strike(strike_price);
     require!(strike_price > 0, DegenError::InvalidMarketParams);
     
     // Ensure market hasn't expired yet
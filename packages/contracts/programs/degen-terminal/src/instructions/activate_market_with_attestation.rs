@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use crate::state::{AssetConfig, Market, MarketSeries, MarketStatus, ResolverConfig, ORACLE_PUBLISH_TOLERANCE};
+use crate::attestation::{verify_price_attestation, AttestationKind, PriceAttestation};
+use crate::oracle::published_at;
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct ActivateMarketWithAttestation<'info> {
+    #[account(
+        mut,
+        constraint = market.status == MarketStatus::Pending @ DegenError::MarketNotPending,
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Registered asset (must be enabled)
+    #[account(
+        seeds = [AssetConfig::SEED, market.asset_bytes()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    /// Rolling series for the market's asset and timeframe - always passed so a series
+    /// strike cannot be bypassed. May be uninitialized if no series has been set up.
+    /// CHECK: PDA address is enforced by seeds; contents are loaded with `MarketSeries::load_optional`
+    #[account(
+        seeds = [MarketSeries::SEED, market.asset_bytes(), market.timeframe_bytes()],
+        bump
+    )]
+    pub market_series: UncheckedAccount<'info>,
+
+    /// Holds the whitelisted price publishers
+    #[account(
+        seeds = [ResolverConfig::SEED],
+        bump = resolver_config.bump
+    )]
+    pub resolver_config: Account<'info, ResolverConfig>,
+
+    /// Instructions sysvar (to read the preceding Ed25519 verification)
+    /// CHECK: Address is checked against the sysvar ID
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// Anyone can submit a signed attestation - the price is verified on-chain
+    pub authority: Signer<'info>,
+}
+
+/// Activate a pending market with a strike price signed by a whitelisted price publisher.
+///
+/// The transaction must include an Ed25519 precompile instruction, immediately
+/// before this one, over the borsh-encoded `PriceAttestation` for this market.
+/// The attested price must be fresh (published within `ORACLE_PUBLISH_TOLERANCE`).
+/// On oracle-activated markets it must also be the price at `start_at`, as with
+/// `activate_market_with_oracle`.
+pub fn activate_market_with_attestation(
+    ctx: Context<ActivateMarketWithAttestation>,
+    strike_price: u64,
+    publish_time: i64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let asset_config = &ctx.accounts.asset_config;
+    let clock = Clock::get()?;
+
+    require!(asset_config.enabled, DegenError::AssetDisabled);
+
+    // Base markets of a rolling series take their strike from `roll_series`
+    let market_series = MarketSeries::load_optional(&ctx.accounts.market_series)?;
+    require!(!market_series.is_some_and(|series| series.chains(market)), DegenError::SeriesStrikeRequired);
+
+    // Validate strike price (at the asset's precision)
+    require!(asset_config.normalize_strike(strike_price) > 0, DegenError::InvalidMarketParams);
+
+    // Ensure market hasn't expired yet
+    require!(clock.unix_timestamp < market.expiry_at, DegenError::MarketExpired);
+
+    // Strike must be a current price
+    require!(
+        publish_time <= clock.unix_timestamp &&
+        publish_time >= clock.unix_timestamp - ORACLE_PUBLISH_TOLERANCE,
+        DegenError::StaleOraclePrice
+    );
+
+    // Oracle-activated markets take the price at start, as with `activate_market_with_oracle`
+    if market.oracle_activation {
+        require!(clock.unix_timestamp >= market.start_at, DegenError::MarketNotStarted);
+        require!(
+            published_at(publish_time, market.start_at),
+            DegenError::StaleOraclePrice
+        );
+    }
+
+    // Verify the publisher's signature over this exact attestation
+    let attestation = PriceAttestation {
+        market: market.key(),
+        kind: AttestationKind::Strike,
+        price: strike_price,
+        publish_time,
+    };
+    let signer = verify_price_attestation(&ctx.accounts.instructions_sysvar, &attestation)?;
+    require!(ctx.accounts.resolver_config.is_price_publisher(&signer), DegenError::SignerMismatch);
+
+    market.strike_price = asset_config.normalize_strike(strike_price);
+    market.status = MarketStatus::Open;
+
+    msg!(
+        "Market #{} activated by attestation: {} {} strike={} publisher={}",
+        market.id,
+        market.asset_str(),
+        market.timeframe_str(),
+        market.strike_price,
+        signer
+    );
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use crate::state::{AssetConfig, Market, MarketSeries, MarketStatus};
+use crate::oracle::{read_pyth_price, published_at};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct ActivateMarketWithOracle<'info> {
+    #[account(
+        mut,
+        constraint = market.status == MarketStatus::Pending @ DegenError::MarketNotPending,
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Registered asset (oracle feed, must be enabled)
+    #[account(
+        seeds = [AssetConfig::SEED, market.asset_bytes()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    /// Rolling series for the market's asset and timeframe - always passed so a series
+    /// strike cannot be bypassed. May be uninitialized if no series has been set up.
+    /// CHECK: PDA address is enforced by seeds; contents are loaded with `MarketSeries::load_optional`
+    #[account(
+        seeds = [MarketSeries::SEED, market.asset_bytes(), market.timeframe_bytes()],
+        bump
+    )]
+    pub market_series: UncheckedAccount<'info>,
+
+    /// Pyth `PriceUpdateV2` account for the market's asset
+    /// CHECK: Owner, discriminator and feed ID are validated in `read_pyth_price`
+    pub price_update: UncheckedAccount<'info>,
+
+    /// Anyone can activate once the market has started - the strike is read on-chain
+    pub authority: Signer<'info>,
+}
+
+/// Activate a pending market with the strike read from the asset's Pyth feed.
+///
+/// Permissionless, so markets go live on time even if the keeper lags. The price
+/// update must be published at the market's start (within `ORACLE_PUBLISH_TOLERANCE`
+/// seconds after `start_at`), so the strike is the same no matter who activates or when.
+pub fn activate_market_with_oracle(ctx: Context<ActivateMarketWithOracle>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let asset_config = &ctx.accounts.asset_config;
+    let clock = Clock::get()?;
+
+    require!(asset_config.enabled, DegenError::AssetDisabled);
+
+    // Base markets of a rolling series take their strike from `roll_series`
+    let market_series = MarketSeries::load_optional(&ctx.accounts.market_series)?;
+    require!(!market_series.is_some_and(|series| series.chains(market)), DegenError::SeriesStrikeRequired);
+
+    // Ensure the trading window has started and not yet ended
+    require!(clock.unix_timestamp >= market.start_at, DegenError::MarketNotStarted);
+    require!(clock.unix_timestamp < market.expiry_at, DegenError::MarketExpired);
+
+    // Read the oracle price for this market's asset
+    let oracle_price = read_pyth_price(&ctx.accounts.price_update.to_account_info(), &asset_config.oracle_feed)?;
+
+    // Strike must be the price at start, not a later (cherry-picked) one
+    require!(
+        published_at(oracle_price.publish_time, market.start_at),
+        DegenError::StaleOraclePrice
+    );
+
+    market.strike_price = asset_config.normalize_strike(oracle_price.price);
+    market.status = MarketStatus::Open;
+
+    msg!(
+        "Market #{} activated by oracle: {} {} strike={} publish_time={}",
+        market.id,
+        market.asset_str(),
+        market.timeframe_str(),
+        market.strike_price,
+        oracle_price.publish_time
+    );
+
+    Ok(())
+}
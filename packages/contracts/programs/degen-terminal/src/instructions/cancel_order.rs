@@ -1,12 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::{Market, Order};
+use crate::state::{Market, Order, OrderBook};
 use crate::errors::DegenError;
 
 #[derive(Accounts)]
 pub struct CancelOrder<'info> {
     /// The market for this order
     #[account(
+        mut,
         constraint = market.key() == order.market @ DegenError::InvalidMarketParams
     )]
     pub market: Account<'info, Market>,
@@ -25,6 +26,14 @@ pub struct CancelOrder<'info> {
     )]
     pub user_usdc: Account<'info, TokenAccount>,
     
+    /// Market's order book (required if the order rests on it)
+    #[account(
+        mut,
+        seeds = [OrderBook::SEED, market.key().as_ref()],
+        bump = order_book.load()?.bump
+    )]
+    pub order_book: Option<AccountLoader<'info, OrderBook>>,
+    
     /// The order to cancel
     #[account(
         mut,
@@ -50,23 +59,15 @@ pub fn cancel_order(ctx: Context<CancelOrder>) -> Result<()> {
     let order = &ctx.accounts.order;
     let market = &ctx.accounts.market;
     
-    // Calculate refund amount based on remaining size
-    // locked_amount is for full order, refund proportional to remaining
-    let refund_amount = if order.filled_size == 0 {
-        // Order was never filled, return full locked amount
-        order.locked_amount
-    } else if order.filled_size >= order.size {
-        // Order is fully filled, no refund
-        0
-    } else {
-        // Partially filled - refund proportional to remaining
-        let remaining = order.size.saturating_sub(order.filled_size);
-        order.locked_amount
-            .checked_mul(remaining)
-            .unwrap_or(0)
-            .checked_div(order.size)
-            .unwrap_or(0)
-    };
+    // Take the order off the book first
+    if order.on_book {
+        let order_book = ctx.accounts.order_book.as_ref().ok_or(DegenError::OrderOnBook)?;
+        order_book.load_mut()?.remove(&order.key());
+    }
+    
+    // Refund the locked amount for the remaining size
+    // (locked_amount is for the full order, so a partial fill refunds proportionally)
+    let refund_amount = order.refundable_amount();
     
     // Transfer USDC from vault back to user if there's a refund
     if refund_amount > 0 {
@@ -76,6 +77,7 @@ pub fn cancel_order(ctx: Context<CancelOrder>) -> Result<()> {
             market.asset_bytes(),
             market.timeframe_bytes(),
             &market.expiry_at.to_le_bytes(),
+            &[market.strike_index],
             &[market.bump],
         ];
         let signer_seeds = &[&market_seeds[..]];
@@ -91,6 +93,7 @@ pub fn cancel_order(ctx: Context<CancelOrder>) -> Result<()> {
         
         msg!("Refunded {} USDC to user", refund_amount);
     }
+    ctx.accounts.market.release_escrow(refund_amount);
     
     msg!(
         "Order cancelled: order={} owner={} remaining_size={} refund={}",
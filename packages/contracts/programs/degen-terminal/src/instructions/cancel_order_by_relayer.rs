@@ -1,12 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::{Market, Order, TRADING_CLOSE_BUFFER};
+use crate::state::{Market, Order, OrderBook};
 use crate::errors::DegenError;
 
 #[derive(Accounts)]
 pub struct CancelOrderByRelayer<'info> {
     /// The market for this order
     #[account(
+        mut,
         constraint = market.key() == order.market @ DegenError::InvalidMarketParams
     )]
     pub market: Account<'info, Market>,
@@ -25,6 +26,14 @@ pub struct CancelOrderByRelayer<'info> {
     )]
     pub user_usdc: Account<'info, TokenAccount>,
     
+    /// Market's order book (required if the order rests on it)
+    #[account(
+        mut,
+        seeds = [OrderBook::SEED, market.key().as_ref()],
+        bump = order_book.load()?.bump
+    )]
+    pub order_book: Option<AccountLoader<'info, OrderBook>>,
+    
     /// The order to cancel (rent returned to owner)
     #[account(
         mut,
@@ -49,14 +58,16 @@ pub struct CancelOrderByRelayer<'info> {
     pub token_program: Program<'info, Token>,
 }
 
-/// Cancel an order after the market is no longer accepting trades.
+/// Cancel an order after the market is no longer accepting trades, or once the order expired.
 ///
 /// This is designed for rent + escrow recovery when the market has closed/expired
-/// and users have not manually cancelled their open orders.
+/// and users have not manually cancelled their open orders, and to refund immediate
+/// (Market/IOC/FOK) orders that were not matched in their placement slot.
 ///
 /// Safety:
 /// - Only the market `authority` (relayer) can call this
-/// - Only allowed once trading is closed (expiry_at - buffer)
+/// - Only allowed once trading is closed (expiry_at - buffer, or resolved early by a barrier touch)
+///   or the order has expired
 /// - Refunds remaining escrow from vault to the user's USDC ATA
 /// - Closes the Order account and returns rent to the owner
 pub fn cancel_order_by_relayer(ctx: Context<CancelOrderByRelayer>) -> Result<()> {
@@ -64,26 +75,22 @@ pub fn cancel_order_by_relayer(ctx: Context<CancelOrderByRelayer>) -> Result<()>
     let market = &ctx.accounts.market;
     let clock = Clock::get()?;
     
-    // Only allow forced cancellation once the market is closed to trading.
-    // (Within the last 30s buffer or after expiry.)
+    // Only allow forced cancellation once the market is closed to trading
+    // (within the timeframe's close buffer, after expiry, or once resolved early),
+    // or once the order itself has expired (including unfilled IOC/FOK/Market orders).
     require!(
-        clock.unix_timestamp >= market.expiry_at - TRADING_CLOSE_BUFFER,
+        !market.is_trading_open(clock.unix_timestamp) || order.is_expired(clock.unix_timestamp, clock.slot),
         DegenError::MarketNotOpen
     );
     
+    // Take the order off the book first
+    if order.on_book {
+        let order_book = ctx.accounts.order_book.as_ref().ok_or(DegenError::OrderOnBook)?;
+        order_book.load_mut()?.remove(&order.key());
+    }
+    
     // Calculate refund amount based on remaining size
-    let refund_amount = if order.filled_size == 0 {
-        order.locked_amount
-    } else if order.filled_size >= order.size {
-        0
-    } else {
-        let remaining = order.size.saturating_sub(order.filled_size);
-        order.locked_amount
-            .checked_mul(remaining)
-            .unwrap_or(0)
-            .checked_div(order.size)
-            .unwrap_or(0)
-    };
+    let refund_amount = order.refundable_amount();
     
     // Transfer USDC from vault back to user if there's a refund
     if refund_amount > 0 {
@@ -92,6 +99,7 @@ pub fn cancel_order_by_relayer(ctx: Context<CancelOrderByRelayer>) -> Result<()>
             market.asset_bytes(),
             market.timeframe_bytes(),
             &market.expiry_at.to_le_bytes(),
+            &[market.strike_index],
             &[market.bump],
         ];
         let signer_seeds = &[&market_seeds[..]];
@@ -107,6 +115,7 @@ pub fn cancel_order_by_relayer(ctx: Context<CancelOrderByRelayer>) -> Result<()>
         
         msg!("Refunded {} USDC to user (forced cancel)", refund_amount);
     }
+    ctx.accounts.market.release_escrow(refund_amount);
     
     msg!(
         "Order force-cancelled by relayer: order={} owner={} remaining_size={} refund={}",
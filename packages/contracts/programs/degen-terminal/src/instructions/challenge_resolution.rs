@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{Market, MarketStatus, ResolutionProposal};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct ChallengeResolution<'info> {
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [ResolutionProposal::SEED, market.key().as_ref()],
+        bump = proposal.bump,
+        has_one = market @ DegenError::InvalidMarketParams
+    )]
+    pub proposal: Account<'info, ResolutionProposal>,
+    
+    /// Bond escrow created with the proposal
+    #[account(
+        mut,
+        address = proposal.bond_vault @ DegenError::InvalidMarketParams
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+    
+    /// Challenger's USDC token account (pays the matching bond)
+    #[account(
+        mut,
+        constraint = challenger_usdc.owner == challenger.key() @ DegenError::Unauthorized,
+        constraint = challenger_usdc.mint == bond_vault.mint @ DegenError::InvalidMarketParams
+    )]
+    pub challenger_usdc: Account<'info, TokenAccount>,
+    
+    /// Anyone except the proposer can challenge
+    pub challenger: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Challenge a proposed outcome by posting a bond equal to the proposer's.
+/// Must be called before the dispute window ends. The market stays `Proposed`
+/// until the admin or a registered resolver calls `settle_dispute`.
+pub fn challenge_resolution(ctx: Context<ChallengeResolution>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let proposal = &mut ctx.accounts.proposal;
+    let challenger = ctx.accounts.challenger.key();
+    let clock = Clock::get()?;
+    
+    require!(market.status == MarketStatus::Proposed, DegenError::MarketNotProposed);
+    require!(!proposal.is_challenged(), DegenError::ProposalAlreadyChallenged);
+    require!(clock.unix_timestamp < proposal.challenge_deadline, DegenError::DisputeWindowClosed);
+    require!(challenger != proposal.proposer, DegenError::SelfChallenge);
+    
+    // Post the matching bond
+    require!(ctx.accounts.challenger_usdc.amount >= proposal.bond, DegenError::InsufficientBalance);
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.challenger_usdc.to_account_info(),
+            to: ctx.accounts.bond_vault.to_account_info(),
+            authority: ctx.accounts.challenger.to_account_info(),
+        },
+    );
+    token::transfer(cpi_ctx, proposal.bond)?;
+    
+    proposal.challenger = challenger;
+    proposal.challenged_at = clock.unix_timestamp;
+    
+    msg!(
+        "Market #{} proposal challenged by {} (proposed {:?}, final={})",
+        market.id, challenger, proposal.outcome, proposal.final_price
+    );
+    
+    Ok(())
+}
@@ -97,6 +97,7 @@ pub fn close_market(ctx: Context<CloseMarket>) -> Result<()> {
         market.asset_bytes(),
         market.timeframe_bytes(),
         expiry_bytes.as_ref(),
+        &[market.strike_index],
         &[bump]
     ];
     let signer_seeds = &[&seeds[..]];
@@ -0,0 +1,211 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Mint};
+use anchor_spl::associated_token::AssociatedToken;
+use crate::state::{GlobalState, AssetConfig, TimeframeConfig, Market, MarketGroup, MarketSchedule, MarketStatus, FeeSchedule, MarketOutcome, ResolutionMethod, MAX_GROUP_MARKETS};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+#[instruction(expiry_ts: i64)]
+pub struct CrankCreateMarket<'info> {
+    #[account(
+        mut,
+        seeds = [GlobalState::SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [MarketSchedule::SEED, market_schedule.asset_bytes(), market_schedule.timeframe_bytes()],
+        bump = market_schedule.bump
+    )]
+    pub market_schedule: Account<'info, MarketSchedule>,
+    
+    /// Registered asset (must be enabled for this timeframe)
+    #[account(
+        seeds = [AssetConfig::SEED, market_schedule.asset_bytes()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+    
+    /// Registered timeframe (duration, close buffer, lead time bounds)
+    #[account(
+        seeds = [TimeframeConfig::SEED, market_schedule.timeframe_bytes()],
+        bump = timeframe_config.bump
+    )]
+    pub timeframe_config: Account<'info, TimeframeConfig>,
+    
+    /// Strike ladder for the scheduled expiry (may already exist from `initialize_market`)
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = MarketGroup::SIZE,
+        seeds = [
+            MarketGroup::SEED,
+            market_schedule.asset_bytes(),
+            market_schedule.timeframe_bytes(),
+            &expiry_ts.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_group: Account<'info, MarketGroup>,
+    
+    /// Scheduled market, appended at the next strike index of the group
+    #[account(
+        init,
+        payer = cranker,
+        space = Market::SIZE,
+        seeds = [
+            Market::SEED,
+            market_schedule.asset_bytes(),
+            market_schedule.timeframe_bytes(),
+            &expiry_ts.to_le_bytes(),
+            &[market_group.num_markets]
+        ],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// The market's USDC vault (ATA owned by market PDA)
+    #[account(
+        init,
+        payer = cranker,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = market,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    
+    /// USDC mint (pinned by the schedule)
+    #[account(address = market_schedule.usdc_mint @ DegenError::InvalidMarketParams)]
+    pub usdc_mint: Account<'info, Mint>,
+    
+    /// Anyone can crank the schedule - paid back the rent plus the schedule's tip
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the next pending market of a schedule.
+/// 
+/// `expiry_ts` must be the schedule's due expiry (see `MarketSchedule::due_expiry`), so the
+/// caller cannot pick the slot, and it must be within the schedule's lead time. The market's
+/// authority is the schedule's market authority, which activates it as usual. The schedule
+/// pays the caller back the rent of the new accounts plus `tip_lamports`.
+/// 
+/// If the expiry's market group already exists (e.g. created by `initialize_market`), the
+/// scheduled market is appended at the group's next strike index instead of failing.
+pub fn crank_create_market(ctx: Context<CrankCreateMarket>, expiry_ts: i64) -> Result<()> {
+    let clock = Clock::get()?;
+    let market_schedule = &ctx.accounts.market_schedule;
+    let asset_config = &ctx.accounts.asset_config;
+    let timeframe_config = &ctx.accounts.timeframe_config;
+    
+    require!(market_schedule.enabled, DegenError::ScheduleDisabled);
+    require!(asset_config.enabled, DegenError::AssetDisabled);
+    require!(
+        asset_config.allows_timeframe(&String::from_utf8_lossy(market_schedule.timeframe_bytes())),
+        DegenError::TimeframeNotAllowed
+    );
+    
+    // Only the due slot can be created, once it is within the schedule's lead time
+    require!(
+        expiry_ts == market_schedule.due_expiry(clock.unix_timestamp, timeframe_config.min_lead_time),
+        DegenError::InvalidExpiry
+    );
+    require!(expiry_ts - clock.unix_timestamp <= market_schedule.lead_time, DegenError::ScheduleNotDue);
+    require!(timeframe_config.is_aligned(expiry_ts), DegenError::ExpiryNotAligned);
+    require!(timeframe_config.allows_lead_time(expiry_ts, clock.unix_timestamp), DegenError::InvalidLeadTime);
+    
+    let market_key = ctx.accounts.market.key();
+    
+    let market_group = &mut ctx.accounts.market_group;
+    let strike_index = market_group.num_markets;
+    require!((strike_index as usize) < MAX_GROUP_MARKETS, DegenError::MarketGroupFull);
+    
+    // The group is new unless a market was already created for this expiry
+    let group_created = strike_index == 0;
+    if group_created {
+        market_group.asset = market_schedule.asset;
+        market_group.timeframe = market_schedule.timeframe;
+        market_group.expiry_at = expiry_ts;
+        market_group.creator = market_schedule.market_authority;
+        market_group.bump = ctx.bumps.market_group;
+    }
+    market_group.markets[strike_index as usize] = market_key;
+    market_group.num_markets = strike_index + 1;
+    
+    // Update global state
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.total_markets += 1;
+    let market_id = global_state.total_markets;
+    
+    // Scheduled markets are always created PENDING - the strike is set at activation
+    let market = &mut ctx.accounts.market;
+    market.id = market_id;
+    market.authority = market_schedule.market_authority;
+    market.asset = market_schedule.asset;
+    market.timeframe = market_schedule.timeframe;
+    market.strike_price = 0;
+    market.final_price = 0;
+    market.created_at = clock.unix_timestamp;
+    market.start_at = expiry_ts - timeframe_config.duration;
+    market.expiry_at = expiry_ts;
+    market.resolved_at = 0;
+    market.settled_at = 0;
+    market.status = MarketStatus::Pending;
+    market.outcome = MarketOutcome::Pending;
+    market.total_volume = 0;
+    market.total_trades = 0;
+    market.total_positions = 0;
+    market.settled_positions = 0;
+    market.open_interest = 0;
+    market.total_cost_basis = 0;
+    market.refund_vault_balance = 0;
+    market.open_order_escrow = 0;
+    market.oracle_activation = asset_config.oracle_activation;
+    market.resolution_method = ResolutionMethod::Spot;
+    market.resolution_window = 0;
+    market.resolution_samples = 0;
+    market.close_buffer = timeframe_config.close_buffer;
+    market.strike_index = strike_index;
+    market.clear_market_type();
+    market.trading_params = timeframe_config.trading_params;
+    market.fee_schedule = FeeSchedule::default();
+    market.bump = ctx.bumps.market;
+    
+    // Pay back the rent of the new accounts plus the tip, keeping the schedule rent-exempt
+    let group_rent = if group_created { market_group.to_account_info().lamports() } else { 0 };
+    let rent_paid = market.to_account_info().lamports()
+        .checked_add(group_rent)
+        .and_then(|v| v.checked_add(ctx.accounts.vault.to_account_info().lamports()))
+        .ok_or(DegenError::MathOverflow)?;
+    let payout = rent_paid.checked_add(market_schedule.tip_lamports).ok_or(DegenError::MathOverflow)?;
+    
+    let schedule_info = ctx.accounts.market_schedule.to_account_info();
+    let rent_exempt = Rent::get()?.minimum_balance(MarketSchedule::SIZE);
+    let available = schedule_info.lamports().saturating_sub(rent_exempt);
+    require!(available >= payout, DegenError::ScheduleUnderfunded);
+    
+    **schedule_info.try_borrow_mut_lamports()? -= payout;
+    **ctx.accounts.cranker.to_account_info().try_borrow_mut_lamports()? += payout;
+    
+    let market_schedule = &mut ctx.accounts.market_schedule;
+    market_schedule.next_expiry = expiry_ts + market_schedule.cadence;
+    market_schedule.last_market = market_key;
+    market_schedule.num_created = market_schedule.num_created.checked_add(1).ok_or(DegenError::MathOverflow)?;
+    
+    msg!(
+        "Market #{} created by crank: {} {} expiry={} (paid {} lamports to {})",
+        market_id,
+        market.asset_str(),
+        market.timeframe_str(),
+        expiry_ts,
+        payout,
+        ctx.accounts.cranker.key()
+    );
+    
+    Ok(())
+}
@@ -0,0 +1,289 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{GlobalState, Market, MarketStatus, Order, OrderBook, OrderStatus, Outcome, Side, TradeType, UserPosition, opening_costs};
+use crate::instructions::MatchExecuted;
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct CrankMatch<'info> {
+    #[account(
+        seeds = [GlobalState::SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+    
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+    
+    #[account(
+        mut,
+        seeds = [OrderBook::SEED, market.key().as_ref()],
+        bump = order_book.load()?.bump
+    )]
+    pub order_book: AccountLoader<'info, OrderBook>,
+    
+    /// Market's USDC vault - holds the escrow of resting orders
+    #[account(
+        mut,
+        constraint = vault.owner == market.key() @ DegenError::InvalidMarketParams
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+    
+    /// Fee recipient's USDC account - validated against global state
+    #[account(
+        mut,
+        constraint = fee_recipient.owner == global_state.fee_recipient @ DegenError::Unauthorized
+    )]
+    pub fee_recipient: Box<Account<'info, TokenAccount>>,
+    
+    /// Best bid on the book (checked against the book in the handler).
+    /// On binary markets this is in YES terms, so it can be a NO ask.
+    #[account(
+        mut,
+        constraint = bid_order.market == market.key() @ DegenError::InvalidMarketParams
+    )]
+    pub bid_order: Box<Account<'info, Order>>,
+    
+    /// Best ask on the book (checked against the book in the handler).
+    /// On binary markets this is in YES terms, so it can be a NO bid.
+    #[account(
+        mut,
+        constraint = ask_order.market == market.key() @ DegenError::InvalidMarketParams
+    )]
+    pub ask_order: Box<Account<'info, Order>>,
+    
+    /// Bid owner's USDC account - receives escrow the fill does not use
+    #[account(
+        mut,
+        constraint = bid_owner_usdc.owner == bid_order.owner @ DegenError::Unauthorized
+    )]
+    pub bid_owner_usdc: Box<Account<'info, TokenAccount>>,
+    
+    /// Ask owner's USDC account - receives escrow the fill does not use
+    #[account(
+        mut,
+        constraint = ask_owner_usdc.owner == ask_order.owner @ DegenError::Unauthorized
+    )]
+    pub ask_owner_usdc: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = UserPosition::SIZE,
+        seeds = [UserPosition::SEED, market.key().as_ref(), bid_order.owner.as_ref()],
+        bump
+    )]
+    pub bid_position: Box<Account<'info, UserPosition>>,
+    
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = UserPosition::SIZE,
+        seeds = [UserPosition::SEED, market.key().as_ref(), ask_order.owner.as_ref()],
+        bump
+    )]
+    pub ask_position: Box<Account<'info, UserPosition>>,
+    
+    /// Anyone can crank the book - pays for new position accounts
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Fill the best bid against the best ask of an outcome on the market's order book.
+/// 
+/// Price-time priority: the bid and ask must be the top of the book, and the trade
+/// executes at the price of the one posted first (the maker). The later one is the
+/// taker and pays the taker fee. Both sides are escrowed, so this is permissionless.
+/// Escrow the fill does not use (the taker's price improvement) is refunded.
+/// 
+/// Binary books are kept in YES terms: a NO bid rests as a YES ask at $1 - price (and
+/// a NO ask as a YES bid), so YES and NO bids cross each other. Crank them with
+/// `Outcome::Yes` (`Outcome::No` is treated the same).
+/// 
+/// If either top order can no longer fill (expired, the fill would exceed its owner's
+/// position limit, or its escrow does not cover its leg and the taker fee) it is taken
+/// off the book instead, keeping its escrow until it is cancelled, so a stale order
+/// never blocks the book.
+pub fn crank_match(ctx: Context<CrankMatch>, outcome: Outcome) -> Result<()> {
+    let global_state = &ctx.accounts.global_state;
+    let market_info = ctx.accounts.market.to_account_info();
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+    
+    require!(!global_state.paused, DegenError::ProtocolPaused);
+    require!(market.status == MarketStatus::Open, DegenError::MarketNotOpen);
+    require!(market.is_trading_open(clock.unix_timestamp), DegenError::MarketClosing);
+    require!(market.accepts_outcome(outcome), DegenError::InvalidOutcome);
+    
+    // Binary books are kept in YES terms
+    let outcome = if outcome == Outcome::No { Outcome::Yes } else { outcome };
+    
+    let bid_order = &mut ctx.accounts.bid_order;
+    let ask_order = &mut ctx.accounts.ask_order;
+    let bid_position = &mut ctx.accounts.bid_position;
+    let ask_position = &mut ctx.accounts.ask_position;
+    
+    // Only the top of the book can be matched (prices are the book's, in YES terms)
+    let (bid_seq, bid_price, ask_seq, ask_price) = {
+        let order_book = ctx.accounts.order_book.load()?;
+        let best_bid = order_book.best(Side::Bid, outcome).ok_or(DegenError::BookNotCrossed)?;
+        let best_ask = order_book.best(Side::Ask, outcome).ok_or(DegenError::BookNotCrossed)?;
+        require!(
+            best_bid.order == bid_order.key() && best_ask.order == ask_order.key(),
+            DegenError::NotTopOfBook
+        );
+        (best_bid.seq, best_bid.price, best_ask.seq, best_ask.price)
+    };
+    
+    // The bid gets the YES leg (the bucket on categorical markets), the ask its complement
+    let match_size = bid_order.remaining_size().min(ask_order.remaining_size());
+    let max_position_size = market.trading_params.max_position_size;
+    
+    let bid_stale = bid_order.is_expired(clock.unix_timestamp, clock.slot)
+        || !bid_position.fits_opening_leg(outcome, true, market.num_buckets, match_size, max_position_size);
+    let ask_stale = ask_order.is_expired(clock.unix_timestamp, clock.slot)
+        || !ask_position.fits_opening_leg(outcome, false, market.num_buckets, match_size, max_position_size);
+    
+    if bid_stale || ask_stale {
+        let mut order_book = ctx.accounts.order_book.load_mut()?;
+        for (order, stale) in [(bid_order, bid_stale), (ask_order, ask_stale)] {
+            if stale {
+                order_book.remove(&order.key());
+                order.on_book = false;
+                msg!("Order taken off the book (cannot fill): {}", order.key());
+            }
+        }
+        return Ok(());
+    }
+    
+    require!(bid_price >= ask_price, DegenError::BookNotCrossed);
+    
+    // The order posted first is the maker and sets the price
+    let maker_is_bid = bid_seq < ask_seq;
+    let execution_price = if maker_is_bid { bid_price } else { ask_price };
+    let (yes_cost, no_cost) = opening_costs(outcome, execution_price, match_size).ok_or(DegenError::MathOverflow)?;
+    let (bid_cost, ask_cost) = (yes_cost, no_cost);
+    
+    // Taker fee on the taker's leg (market fee schedule, falling back to the global taker fee)
+    let taker_fee_bps = market.taker_fee_bps(global_state.taker_fee_bps, clock.unix_timestamp);
+    let taker_fee = if maker_is_bid { ask_cost } else { bid_cost }
+        .checked_mul(taker_fee_bps as u64).ok_or(DegenError::MathOverflow)?
+        .checked_div(10_000).ok_or(DegenError::DivisionByZero)?;
+    let (bid_cost, ask_cost) = if maker_is_bid {
+        (bid_cost, ask_cost.checked_add(taker_fee).ok_or(DegenError::MathOverflow)?)
+    } else {
+        (bid_cost.checked_add(taker_fee).ok_or(DegenError::MathOverflow)?, ask_cost)
+    };
+    
+    // Each escrow must cover its leg (and the taker fee). One that cannot is taken off the book
+    // instead, so the fee is never paid out of other users' collateral.
+    let bid_unfunded = bid_order.fill_escrow(match_size) < bid_cost;
+    let ask_unfunded = ask_order.fill_escrow(match_size) < ask_cost;
+    if bid_unfunded || ask_unfunded {
+        let mut order_book = ctx.accounts.order_book.load_mut()?;
+        for (order, unfunded) in [(bid_order, bid_unfunded), (ask_order, ask_unfunded)] {
+            if unfunded {
+                order_book.remove(&order.key());
+                order.on_book = false;
+                msg!("Order taken off the book (escrow does not cover the fill): {}", order.key());
+            }
+        }
+        return Ok(());
+    }
+    
+    // Escrow the fill does not use goes back to its owner (the taker's price improvement)
+    let bid_refund = bid_order.fill_surplus(match_size, bid_cost);
+    let ask_refund = ask_order.fill_surplus(match_size, ask_cost);
+    
+    // Update orders, taking filled ones off the book and releasing the escrow they spent
+    {
+        let mut order_book = ctx.accounts.order_book.load_mut()?;
+        for order in [&mut *bid_order, &mut *ask_order] {
+            let escrow_before = order.outstanding_escrow();
+            order.filled_size = order.filled_size.checked_add(match_size).ok_or(DegenError::MathOverflow)?;
+            if order.filled_size >= order.size {
+                order.status = OrderStatus::Filled;
+                order.on_book = false;
+                order_book.remove(&order.key());
+            } else {
+                order.status = OrderStatus::PartialFill;
+            }
+            market.release_escrow(escrow_before.saturating_sub(order.outstanding_escrow()));
+        }
+    }
+    
+    // Initialize positions if needed
+    if bid_position.owner == Pubkey::default() {
+        bid_position.owner = bid_order.owner;
+        bid_position.market = market.key();
+        bid_position.bump = ctx.bumps.bid_position;
+        market.total_positions += 1;
+    }
+    
+    if ask_position.owner == Pubkey::default() {
+        ask_position.owner = ask_order.owner;
+        ask_position.market = market.key();
+        ask_position.bump = ctx.bumps.ask_position;
+        market.total_positions += 1;
+    }
+    
+    // Opening trade: mint new shares from both escrows
+    bid_position.mint_opening_leg(outcome, true, market.num_buckets, match_size, bid_cost).ok_or(DegenError::MathOverflow)?;
+    ask_position.mint_opening_leg(outcome, false, market.num_buckets, match_size, ask_cost).ok_or(DegenError::MathOverflow)?;
+    market.record_opening_fill(match_size, yes_cost, no_cost, taker_fee).ok_or(DegenError::MathOverflow)?;
+    
+    let market_seeds = &[
+        Market::SEED,
+        market.asset_bytes(),
+        market.timeframe_bytes(),
+        &market.expiry_at.to_le_bytes(),
+        &[market.strike_index],
+        &[market.bump],
+    ];
+    let signer_seeds = &[&market_seeds[..]];
+    
+    // Transfer fees and refunds out of the vault
+    let payouts = [
+        (ctx.accounts.fee_recipient.to_account_info(), taker_fee),
+        (ctx.accounts.bid_owner_usdc.to_account_info(), bid_refund),
+        (ctx.accounts.ask_owner_usdc.to_account_info(), ask_refund),
+    ];
+    for (to, amount) in payouts {
+        if amount > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to,
+                authority: market_info.clone(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, amount)?;
+        }
+    }
+    
+    let (maker, taker) = if maker_is_bid { (bid_order.owner, ask_order.owner) } else { (ask_order.owner, bid_order.owner) };
+    
+    msg!(
+        "Book match: {} shares @ {} (bid={} ask={} yes={} no={} fee={} refunds={}/{})",
+        match_size, execution_price, bid_order.key(), ask_order.key(), yes_cost, no_cost, taker_fee, bid_refund, ask_refund
+    );
+    
+    emit!(MatchExecuted {
+        market: market.key(),
+        maker,
+        taker,
+        outcome,
+        price: execution_price,
+        size: match_size,
+        yes_cost,
+        no_cost,
+        taker_fee,
+        maker_has_escrow: true,
+        taker_has_escrow: true,
+        trade_type: TradeType::Opening,
+    });
+    
+    Ok(())
+}
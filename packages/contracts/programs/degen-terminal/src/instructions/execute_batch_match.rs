@@ -0,0 +1,401 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount};
+use anchor_lang::Discriminator;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{GlobalState, Market, UserPosition, Order, OrderStatus, OrderType, Side, Outcome, MarketStatus, TradeType, opening_costs, order_in_frame, MAX_BATCH_MAKERS};
+use crate::instructions::{MatchExecuted, PlaceOrderArgs};
+use crate::errors::DegenError;
+
+/// Accounts passed per maker in `remaining_accounts`: order, position, USDC account
+const MAKER_ACCOUNTS: usize = 3;
+
+#[derive(Accounts)]
+pub struct ExecuteBatchMatch<'info> {
+    #[account(
+        seeds = [GlobalState::SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+    
+    /// Market account - validated by Anchor's account discriminator check
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+    
+    /// Market's USDC vault - validated to be owned by market PDA
+    #[account(
+        mut,
+        constraint = vault.owner == market.key() @ DegenError::InvalidMarketParams
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+    
+    /// Fee recipient's USDC account - validated against global state
+    #[account(
+        mut,
+        constraint = fee_recipient.owner == global_state.fee_recipient @ DegenError::Unauthorized
+    )]
+    pub fee_recipient: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Taker wallet - trusted by relayer (user orders verified via place_order)
+    pub taker: AccountInfo<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = UserPosition::SIZE,
+        seeds = [UserPosition::SEED, market.key().as_ref(), taker.key().as_ref()],
+        bump
+    )]
+    pub taker_position: Box<Account<'info, UserPosition>>,
+    
+    /// Taker's USDC account - validated to be owned by taker
+    #[account(
+        mut,
+        constraint = taker_usdc.owner == taker.key() @ DegenError::Unauthorized
+    )]
+    pub taker_usdc: Box<Account<'info, TokenAccount>>,
+    
+    /// Taker's Order PDA (optional - only for user orders, not MM)
+    /// If provided, USDC is already locked in vault. Mutable to update filled_size.
+    #[account(mut)]
+    pub taker_order: Option<Account<'info, Order>>,
+    
+    /// Relayer that pays for account creation and submits the tx
+    /// Also used as delegate authority for MM token transfers
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Match one taker against several maker orders in sequence (Opening Trades)
+/// 
+/// `remaining_accounts` holds one `(maker order, maker position, maker USDC)` triple per
+/// entry of `match_sizes`, in fill order. Makers must be escrowed Order PDAs; their
+/// position PDAs are created (paid by the relayer) on their first trade. Each leg is
+/// checked like `execute_match` and executes at its maker's price. The taker is validated,
+/// charged and updated once for the total size.
+pub fn execute_batch_match<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteBatchMatch<'info>>,
+    taker_args: PlaceOrderArgs,
+    match_sizes: Vec<u64>,
+) -> Result<()> {
+    let global_state = &ctx.accounts.global_state;
+    let market_info = ctx.accounts.market.to_account_info();
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+    
+    require!(
+        !match_sizes.is_empty()
+            && match_sizes.len() <= MAX_BATCH_MAKERS
+            && ctx.remaining_accounts.len() == match_sizes.len() * MAKER_ACCOUNTS,
+        DegenError::InvalidBatchAccounts
+    );
+    let total_size = match_sizes.iter().try_fold(0u64, |total, &size| total.checked_add(size)).ok_or(DegenError::MathOverflow)?;
+    
+    // Taker order - prefer the Order PDA if available, otherwise use args
+    let taker_has_escrow = ctx.accounts.taker_order.is_some();
+    let (taker_side, outcome, taker_price, taker_size, taker_expired, taker_allows_fill) = if let Some(ref order) = ctx.accounts.taker_order {
+        require!(order.owner == ctx.accounts.taker.key(), DegenError::Unauthorized);
+        require!(order.market == market.key(), DegenError::InvalidMarketParams);
+        require!(order.is_active(), DegenError::OrderNotActive);
+        require!(!order.on_book, DegenError::OrderOnBook);
+        require!(total_size <= order.remaining_size(), DegenError::InvalidSize);
+        (order.side, order.outcome, order.price, order.size, order.is_expired(clock.unix_timestamp, clock.slot), order.allows_fill(total_size))
+    } else {
+        require!(total_size <= taker_args.size, DegenError::InvalidSize);
+        (
+            taker_args.side, taker_args.outcome, taker_args.price, taker_args.size,
+            taker_args.expiry_ts <= clock.unix_timestamp,
+            taker_args.order_type != OrderType::FOK || total_size == taker_args.size,
+        )
+    };
+    
+    // Taker validations (once for the whole batch)
+    let trading_params = market.trading_params;
+    require!(!global_state.paused, DegenError::ProtocolPaused);
+    require!(market.status == MarketStatus::Open, DegenError::MarketNotOpen);
+    require!(market.is_trading_open(clock.unix_timestamp), DegenError::MarketClosing);
+    require!(market.accepts_outcome(outcome), DegenError::InvalidOutcome);
+    require!(!taker_expired, DegenError::OrderExpired);
+    require!(taker_allows_fill, DegenError::FillOrKillNotFilled);
+    require!(trading_params.allows_price(taker_price), DegenError::InvalidPrice);
+    require!(trading_params.is_on_tick(taker_price), DegenError::InvalidTickSize);
+    require!(trading_params.allows_size(taker_size), DegenError::InvalidSize);
+    
+    // The taker gets the YES leg (the bucket on categorical markets) when buying it or selling NO
+    let taker_yes_leg = (taker_side == Side::Bid) == (outcome != Outcome::No);
+    require!(
+        ctx.accounts.taker_position.fits_opening_leg(outcome, taker_yes_leg, market.num_buckets, total_size, trading_params.max_position_size),
+        DegenError::PositionLimitExceeded
+    );
+    
+    let taker_fee_bps = market.taker_fee_bps(global_state.taker_fee_bps, clock.unix_timestamp);
+    let market_key = market.key();
+    // Seeds are copied out since the market stats are updated between transfers
+    let (asset_bytes, timeframe_bytes) = (market.asset_bytes().to_vec(), market.timeframe_bytes().to_vec());
+    let market_seeds = &[
+        Market::SEED,
+        &asset_bytes,
+        &timeframe_bytes,
+        &market.expiry_at.to_le_bytes(),
+        &[market.strike_index],
+        &[market.bump],
+    ];
+    let signer_seeds = &[&market_seeds[..]];
+    
+    let mut taker_cost = 0u64;
+    let mut taker_fee_total = 0u64;
+    
+    // Fill each maker in sequence
+    for (maker_accounts, &match_size) in ctx.remaining_accounts.chunks(MAKER_ACCOUNTS).zip(match_sizes.iter()) {
+        let mut maker_order = Account::<Order>::try_from(&maker_accounts[0])?;
+        let maker_usdc = Account::<TokenAccount>::try_from(&maker_accounts[2])?;
+        let maker = maker_order.owner;
+    
+        require!(maker_accounts[0].is_writable, DegenError::InvalidBatchAccounts);
+        require!(maker_order.market == market_key, DegenError::InvalidMarketParams);
+        require!(maker_order.is_active(), DegenError::OrderNotActive);
+        require!(!maker_order.on_book, DegenError::OrderOnBook);
+        require!(!maker_order.is_expired(clock.unix_timestamp, clock.slot), DegenError::OrderExpired);
+        require!(maker_order.allows_fill(match_size), DegenError::FillOrKillNotFilled);
+        require!(match_size <= maker_order.remaining_size(), DegenError::InvalidSize);
+        require!(maker_usdc.owner == maker, DegenError::Unauthorized);
+        require!(maker != ctx.accounts.taker.key(), DegenError::SelfTrade);
+        require!(trading_params.allows_size(match_size), DegenError::InvalidSize);
+    
+        // Makers are taken in terms of the taker's outcome, so complementary orders match too
+        // (a NO bid at q is a YES ask at $1 - q)
+        let (maker_side, execution_price) = order_in_frame(maker_order.side, maker_order.outcome, maker_order.price, outcome)
+            .ok_or(DegenError::OutcomeMismatch)?;
+        require!(maker_side != taker_side, DegenError::SameSide);
+    
+        // Orders must cross - the leg executes at the maker's price
+        if maker_side == Side::Bid {
+            require!(taker_price <= execution_price, DegenError::PriceMismatch);
+        } else {
+            require!(taker_price >= execution_price, DegenError::PriceMismatch);
+        }
+    
+        let (mut maker_position, created) = load_or_create_position(
+            &maker_accounts[1],
+            market_key,
+            maker,
+            &ctx.accounts.relayer,
+            &ctx.accounts.system_program,
+        )?;
+        require!(
+            maker_position.fits_opening_leg(outcome, !taker_yes_leg, market.num_buckets, match_size, trading_params.max_position_size),
+            DegenError::PositionLimitExceeded
+        );
+    
+        // Leg costs and the taker fee on the taker's leg
+        let (yes_cost, no_cost) = opening_costs(outcome, execution_price, match_size).ok_or(DegenError::MathOverflow)?;
+        let (taker_leg_cost, maker_cost) = if taker_yes_leg { (yes_cost, no_cost) } else { (no_cost, yes_cost) };
+        let taker_fee = taker_leg_cost
+            .checked_mul(taker_fee_bps as u64).ok_or(DegenError::MathOverflow)?
+            .checked_div(10_000).ok_or(DegenError::DivisionByZero)?;
+        taker_cost = taker_cost
+            .checked_add(taker_leg_cost).ok_or(DegenError::MathOverflow)?
+            .checked_add(taker_fee).ok_or(DegenError::MathOverflow)?;
+        taker_fee_total = taker_fee_total.checked_add(taker_fee).ok_or(DegenError::MathOverflow)?;
+    
+        require!(maker_order.fill_escrow(match_size) >= maker_cost, DegenError::InsufficientEscrow);
+    
+        // Update the maker order, refunding escrow the fill did not use and the unfilled
+        // remainder of immediate orders
+        let escrow_before = maker_order.outstanding_escrow();
+        let mut refund_amount = maker_order.fill_surplus(match_size, maker_cost);
+        maker_order.filled_size = maker_order.filled_size.checked_add(match_size).ok_or(DegenError::MathOverflow)?;
+        maker_order.status = if maker_order.filled_size >= maker_order.size { OrderStatus::Filled } else { OrderStatus::PartialFill };
+        if maker_order.is_immediate() && maker_order.is_active() {
+            refund_amount = refund_amount.checked_add(maker_order.refundable_amount()).ok_or(DegenError::MathOverflow)?;
+            maker_order.status = OrderStatus::Cancelled;
+        }
+        market.release_escrow(escrow_before.saturating_sub(maker_order.outstanding_escrow()));
+        if refund_amount > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: maker_usdc.to_account_info(),
+                authority: market_info.clone(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, refund_amount)?;
+            msg!("Refunded {} USDC of escrow to order {}", refund_amount, maker_order.key());
+        }
+    
+        // Update the maker position (written back now, so a maker with several orders sees its fills)
+        if created {
+            market.total_positions += 1;
+        }
+        maker_position.mint_opening_leg(outcome, !taker_yes_leg, market.num_buckets, match_size, maker_cost).ok_or(DegenError::MathOverflow)?;
+        maker_order.exit(&crate::ID)?;
+        maker_position.exit(&crate::ID)?;
+    
+        market.record_opening_fill(match_size, yes_cost, no_cost, taker_fee).ok_or(DegenError::MathOverflow)?;
+    
+        emit!(MatchExecuted {
+            market: market_key,
+            maker,
+            taker: ctx.accounts.taker.key(),
+            outcome,
+            price: execution_price,
+            size: match_size,
+            yes_cost,
+            no_cost,
+            taker_fee,
+            maker_has_escrow: true,
+            taker_has_escrow,
+            trade_type: TradeType::Opening,
+        });
+    }
+    
+    // An escrowed taker's escrow must cover its legs and the taker fee, which leaves the vault
+    if let Some(ref taker_order) = ctx.accounts.taker_order {
+        require!(taker_order.fill_escrow(total_size) >= taker_cost, DegenError::InsufficientEscrow);
+    }
+    
+    // Taker deposit (once for all legs) and fees
+    if !taker_has_escrow {
+        msg!("Transferring {} USDC from taker via delegation", taker_cost);
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.taker_usdc.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.relayer.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, taker_cost)?;
+    }
+    
+    if taker_fee_total > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: ctx.accounts.fee_recipient.to_account_info(),
+            authority: market_info.clone(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, taker_fee_total)?;
+    }
+    
+    // Update the taker order once, refunding escrow the fills did not use (price improvement)
+    // and the unfilled remainder of immediate orders
+    if let Some(ref mut taker_order) = ctx.accounts.taker_order {
+        let escrow_before = taker_order.outstanding_escrow();
+        let mut refund_amount = taker_order.fill_surplus(total_size, taker_cost);
+        taker_order.filled_size = taker_order.filled_size.checked_add(total_size).ok_or(DegenError::MathOverflow)?;
+        taker_order.status = if taker_order.filled_size >= taker_order.size { OrderStatus::Filled } else { OrderStatus::PartialFill };
+    
+        if taker_order.is_immediate() && taker_order.is_active() {
+            refund_amount = refund_amount.checked_add(taker_order.refundable_amount()).ok_or(DegenError::MathOverflow)?;
+            taker_order.status = OrderStatus::Cancelled;
+            msg!("Cancelled unfilled remainder of immediate order {}", taker_order.key());
+        }
+        market.release_escrow(escrow_before.saturating_sub(taker_order.outstanding_escrow()));
+        if refund_amount > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.taker_usdc.to_account_info(),
+                authority: market_info,
+            };
+            let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, refund_amount)?;
+            msg!("Refunded {} USDC of escrow to order {}", refund_amount, taker_order.key());
+        }
+    }
+    
+    // Update the taker position once
+    let taker_position = &mut ctx.accounts.taker_position;
+    if taker_position.owner == Pubkey::default() {
+        taker_position.owner = ctx.accounts.taker.key();
+        taker_position.market = market_key;
+        taker_position.bump = ctx.bumps.taker_position;
+        market.total_positions += 1;
+    }
+    taker_position.mint_opening_leg(outcome, taker_yes_leg, market.num_buckets, total_size, taker_cost).ok_or(DegenError::MathOverflow)?;
+    
+    msg!(
+        "Batch match executed: {} makers, {} shares (taker cost={}, fees={})",
+        match_sizes.len(), total_size, taker_cost, taker_fee_total
+    );
+    
+    Ok(())
+}
+
+/// Load a maker's position PDA, creating it (paid by the relayer) on the maker's first trade.
+/// Returns the position and whether it was created.
+fn load_or_create_position<'info>(
+    position_info: &'info AccountInfo<'info>,
+    market: Pubkey,
+    owner: Pubkey,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<(Account<'info, UserPosition>, bool)> {
+    let (expected, bump) = Pubkey::find_program_address(
+        &[UserPosition::SEED, market.as_ref(), owner.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(position_info.key(), expected, DegenError::InvalidBatchAccounts);
+    require!(position_info.is_writable, DegenError::InvalidBatchAccounts);
+    
+    if position_info.owner == &crate::ID {
+        return Ok((Account::try_from(position_info)?, false));
+    }
+    
+    let signer_seeds: &[&[u8]] = &[UserPosition::SEED, market.as_ref(), owner.as_ref(), &[bump]];
+    let rent = Rent::get()?.minimum_balance(UserPosition::SIZE);
+    let current_lamports = position_info.lamports();
+    if current_lamports == 0 {
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                CreateAccount {
+                    from: payer.to_account_info(),
+                    to: position_info.clone(),
+                },
+                &[signer_seeds],
+            ),
+            rent,
+            UserPosition::SIZE as u64,
+            &crate::ID,
+        )?;
+    } else {
+        // Someone pre-funded the PDA, which makes create_account fail - top it up to rent
+        // and allocate/assign it instead, as Anchor's `init` does
+        let top_up = rent.saturating_sub(current_lamports);
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: payer.to_account_info(),
+                        to: position_info.clone(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                Allocate { account_to_allocate: position_info.clone() },
+                &[signer_seeds],
+            ),
+            UserPosition::SIZE as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                Assign { account_to_assign: position_info.clone() },
+                &[signer_seeds],
+            ),
+            &crate::ID,
+        )?;
+    }
+    position_info.try_borrow_mut_data()?[..8].copy_from_slice(&UserPosition::DISCRIMINATOR);
+    
+    let mut position = Account::<UserPosition>::try_from(position_info)?;
+    position.owner = owner;
+    position.market = market;
+    position.bump = bump;
+    Ok((position, true))
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::{GlobalState, Market, UserPosition, Side, Outcome, MarketStatus, TradeType, USDC_MULTIPLIER, SHARE_MULTIPLIER, MAX_POSITION_SIZE, MIN_PRICE, MAX_PRICE, MIN_ORDER_SIZE, MAX_ORDER_SIZE};
+use crate::state::{GlobalState, Market, UserPosition, Side, Outcome, MarketStatus, TradeType, USDC_MULTIPLIER, SHARE_MULTIPLIER};
 use crate::errors::DegenError;
 
 /// Arguments for execute_close instruction
@@ -87,8 +87,10 @@ pub fn execute_close(
     require!(market.status == MarketStatus::Open, DegenError::MarketNotOpen);
     require!(market.is_trading_open(clock.unix_timestamp), DegenError::MarketClosing);
     require!(ctx.accounts.buyer.key() != ctx.accounts.seller.key(), DegenError::SelfTrade);
-    require!(args.price >= MIN_PRICE && args.price <= MAX_PRICE, DegenError::InvalidPrice);
-    require!(args.size >= MIN_ORDER_SIZE && args.size <= MAX_ORDER_SIZE, DegenError::InvalidSize);
+    require!(market.trading_params.allows_price(args.price), DegenError::InvalidPrice);
+    require!(market.trading_params.is_on_tick(args.price), DegenError::InvalidTickSize);
+    require!(market.trading_params.allows_size(args.size), DegenError::InvalidSize);
+    require!(market.accepts_outcome(args.outcome), DegenError::InvalidOutcome);
     
     let seller_position = &mut ctx.accounts.seller_position;
     let buyer_position = &mut ctx.accounts.buyer_position;
@@ -97,6 +99,7 @@ pub fn execute_close(
     let seller_shares = match args.outcome {
         Outcome::Yes => seller_position.yes_shares,
         Outcome::No => seller_position.no_shares,
+        Outcome::Bucket(bucket) => seller_position.bucket_shares[bucket as usize],
     };
     require!(seller_shares >= args.size, DegenError::InsufficientShares);
     
@@ -104,8 +107,9 @@ pub fn execute_close(
     let buyer_new_shares = match args.outcome {
         Outcome::Yes => buyer_position.yes_shares.checked_add(args.size).ok_or(DegenError::MathOverflow)?,
         Outcome::No => buyer_position.no_shares.checked_add(args.size).ok_or(DegenError::MathOverflow)?,
+        Outcome::Bucket(bucket) => buyer_position.bucket_shares[bucket as usize].checked_add(args.size).ok_or(DegenError::MathOverflow)?,
     };
-    require!(buyer_new_shares <= MAX_POSITION_SIZE, DegenError::PositionLimitExceeded);
+    require!(buyer_new_shares <= market.trading_params.max_position_size, DegenError::PositionLimitExceeded);
     
     // Calculate transfer amount: price * size / SHARE_MULTIPLIER
     let transfer_amount = args.price
@@ -113,9 +117,10 @@ pub fn execute_close(
         .checked_add(SHARE_MULTIPLIER - 1).ok_or(DegenError::MathOverflow)?
         .checked_div(SHARE_MULTIPLIER).ok_or(DegenError::DivisionByZero)?;
     
-    // Calculate fee (taker fee on buyer)
+    // Calculate fee (taker fee on buyer, from the market fee schedule or the global fee)
+    let taker_fee_bps = market.taker_fee_bps(global_state.taker_fee_bps, clock.unix_timestamp);
     let fee = transfer_amount
-        .checked_mul(global_state.taker_fee_bps as u64).ok_or(DegenError::MathOverflow)?
+        .checked_mul(taker_fee_bps as u64).ok_or(DegenError::MathOverflow)?
         .checked_div(10_000).ok_or(DegenError::DivisionByZero)?;
     
     let seller_receives = transfer_amount.saturating_sub(fee);
@@ -145,6 +150,7 @@ pub fn execute_close(
     let seller_cost_basis = match args.outcome {
         Outcome::Yes => seller_position.yes_cost_basis,
         Outcome::No => seller_position.no_cost_basis,
+        Outcome::Bucket(bucket) => seller_position.bucket_cost_basis[bucket as usize],
     };
     let cost_per_share = if seller_shares > 0 {
         seller_cost_basis.checked_div(seller_shares).unwrap_or(0)
@@ -173,6 +179,14 @@ pub fn execute_close(
                 .checked_div(seller_shares).ok_or(DegenError::DivisionByZero)?;
             seller_position.no_cost_basis = seller_position.no_cost_basis.saturating_sub(cost_reduction);
         }
+        Outcome::Bucket(bucket) => {
+            let bucket = bucket as usize;
+            seller_position.bucket_shares[bucket] = seller_position.bucket_shares[bucket].checked_sub(args.size).ok_or(DegenError::MathOverflow)?;
+            let cost_reduction = seller_cost_basis
+                .checked_mul(args.size).ok_or(DegenError::MathOverflow)?
+                .checked_div(seller_shares).ok_or(DegenError::DivisionByZero)?;
+            seller_position.bucket_cost_basis[bucket] = seller_position.bucket_cost_basis[bucket].saturating_sub(cost_reduction);
+        }
     }
     seller_position.realized_pnl = seller_position.realized_pnl.checked_add(realized_pnl).unwrap_or(seller_position.realized_pnl);
     
@@ -187,9 +201,23 @@ pub fn execute_close(
             buyer_position.no_shares = buyer_position.no_shares.checked_add(args.size).ok_or(DegenError::MathOverflow)?;
             buyer_position.no_cost_basis = buyer_position.no_cost_basis.checked_add(buyer_total_cost).ok_or(DegenError::MathOverflow)?;
         }
+        Outcome::Bucket(bucket) => {
+            let bucket = bucket as usize;
+            buyer_position.bucket_shares[bucket] = buyer_position.bucket_shares[bucket].checked_add(args.size).ok_or(DegenError::MathOverflow)?;
+            buyer_position.bucket_cost_basis[bucket] = buyer_position.bucket_cost_basis[bucket].checked_add(buyer_total_cost).ok_or(DegenError::MathOverflow)?;
+        }
     }
     
     // Update market stats (volume increases, open_interest unchanged)
+    // Cost basis moves from seller to buyer (at the buyer's price)
+    let seller_cost_remaining = match args.outcome {
+        Outcome::Yes => seller_position.yes_cost_basis,
+        Outcome::No => seller_position.no_cost_basis,
+        Outcome::Bucket(bucket) => seller_position.bucket_cost_basis[bucket as usize],
+    };
+    market.total_cost_basis = market.total_cost_basis
+        .saturating_sub(seller_cost_basis.saturating_sub(seller_cost_remaining))
+        .checked_add(buyer_total_cost).ok_or(DegenError::MathOverflow)?;
     market.total_volume = market.total_volume.checked_add(transfer_amount).ok_or(DegenError::MathOverflow)?;
     market.total_trades = market.total_trades.checked_add(1).ok_or(DegenError::MathOverflow)?;
     
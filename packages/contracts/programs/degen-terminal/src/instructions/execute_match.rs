@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::{GlobalState, Market, UserPosition, Order, OrderStatus, Side, Outcome, MarketStatus, TradeType, USDC_MULTIPLIER, SHARE_MULTIPLIER, MAX_POSITION_SIZE, MIN_PRICE, MAX_PRICE, MIN_ORDER_SIZE, MAX_ORDER_SIZE};
+use crate::state::{GlobalState, Market, UserPosition, Order, OrderStatus, OrderType, Side, Outcome, MarketStatus, TradeType, opening_costs, order_in_frame};
 use crate::instructions::PlaceOrderArgs;
 use crate::errors::DegenError;
 
@@ -52,6 +52,7 @@ pub struct ExecuteMatch<'info> {
     
     /// Maker's Order PDA (optional - only for user orders, not MM)
     /// If provided, USDC is already locked in vault. Mutable to update filled_size.
+    /// Orders resting on the order book are matched by `crank_match` only.
     #[account(mut)]
     pub maker_order: Option<Account<'info, Order>>,
     
@@ -77,6 +78,7 @@ pub struct ExecuteMatch<'info> {
     
     /// Taker's Order PDA (optional - only for user orders, not MM)
     /// If provided, USDC is already locked in vault. Mutable to update filled_size.
+    /// Orders resting on the order book are matched by `crank_match` only.
     #[account(mut)]
     pub taker_order: Option<Account<'info, Order>>,
     
@@ -112,22 +114,35 @@ pub fn execute_match(
     let taker_has_escrow = taker_has_order;
     
     // Extract order parameters
-    let (maker_side, maker_outcome, maker_price, maker_size, maker_expiry) = if let Some(ref order) = ctx.accounts.maker_order {
+    // Order PDAs expire by order type (immediate orders after their placement slot)
+    let (maker_side, maker_outcome, maker_price, maker_size, maker_expired, maker_allows_fill) = if let Some(ref order) = ctx.accounts.maker_order {
         require!(order.owner == ctx.accounts.maker.key(), DegenError::Unauthorized);
         require!(order.market == market.key(), DegenError::InvalidMarketParams);
         require!(order.is_active(), DegenError::OrderNotActive);
-        (order.side, order.outcome, order.price, order.size, order.expiry_ts)
+        require!(!order.on_book, DegenError::OrderOnBook);
+        require!(match_size <= order.remaining_size(), DegenError::InvalidSize);
+        (order.side, order.outcome, order.price, order.size, order.is_expired(clock.unix_timestamp, clock.slot), order.allows_fill(match_size))
     } else {
-        (maker_args.side, maker_args.outcome, maker_args.price, maker_args.size, maker_args.expiry_ts)
+        (
+            maker_args.side, maker_args.outcome, maker_args.price, maker_args.size,
+            maker_args.expiry_ts <= clock.unix_timestamp,
+            maker_args.order_type != OrderType::FOK || match_size == maker_args.size,
+        )
     };
     
-    let (taker_side, taker_outcome, taker_price, taker_size, taker_expiry) = if let Some(ref order) = ctx.accounts.taker_order {
+    let (taker_side, taker_outcome, taker_price, taker_size, taker_expired, taker_allows_fill) = if let Some(ref order) = ctx.accounts.taker_order {
         require!(order.owner == ctx.accounts.taker.key(), DegenError::Unauthorized);
         require!(order.market == market.key(), DegenError::InvalidMarketParams);
         require!(order.is_active(), DegenError::OrderNotActive);
-        (order.side, order.outcome, order.price, order.size, order.expiry_ts)
+        require!(!order.on_book, DegenError::OrderOnBook);
+        require!(match_size <= order.remaining_size(), DegenError::InvalidSize);
+        (order.side, order.outcome, order.price, order.size, order.is_expired(clock.unix_timestamp, clock.slot), order.allows_fill(match_size))
     } else {
-        (taker_args.side, taker_args.outcome, taker_args.price, taker_args.size, taker_args.expiry_ts)
+        (
+            taker_args.side, taker_args.outcome, taker_args.price, taker_args.size,
+            taker_args.expiry_ts <= clock.unix_timestamp,
+            taker_args.order_type != OrderType::FOK || match_size == taker_args.size,
+        )
     };
     
     msg!("Executing match: maker_has_order={}, taker_has_order={}", maker_has_order, taker_has_order);
@@ -137,15 +152,27 @@ pub fn execute_match(
     require!(market.status == MarketStatus::Open, DegenError::MarketNotOpen);
     require!(market.is_trading_open(clock.unix_timestamp), DegenError::MarketClosing);
     require!(ctx.accounts.maker.key() != ctx.accounts.taker.key(), DegenError::SelfTrade);
+    require!(market.accepts_outcome(maker_outcome), DegenError::InvalidOutcome);
+    require!(!maker_expired, DegenError::OrderExpired);
+    require!(!taker_expired, DegenError::OrderExpired);
+    require!(maker_allows_fill && taker_allows_fill, DegenError::FillOrKillNotFilled);
+    
+    // Prices, ticks and sizes are checked against the market's own trading params
+    let trading_params = market.trading_params;
+    require!(trading_params.allows_price(maker_price), DegenError::InvalidPrice);
+    require!(trading_params.allows_price(taker_price), DegenError::InvalidPrice);
+    require!(trading_params.is_on_tick(maker_price), DegenError::InvalidTickSize);
+    require!(trading_params.is_on_tick(taker_price), DegenError::InvalidTickSize);
+    require!(trading_params.allows_size(maker_size), DegenError::InvalidSize);
+    require!(trading_params.allows_size(taker_size), DegenError::InvalidSize);
+    require!(trading_params.allows_size(match_size), DegenError::InvalidSize);
+    
+    // The taker is taken in terms of the maker's outcome, so complementary orders match too:
+    // a YES bid and a NO bid whose prices sum to at least $1 fund a new pair
+    // (the NO bid at q is a YES ask at $1 - q)
+    let (taker_side, taker_price) = order_in_frame(taker_side, taker_outcome, taker_price, maker_outcome)
+        .ok_or(DegenError::OutcomeMismatch)?;
     require!(maker_side != taker_side, DegenError::SameSide);
-    require!(maker_outcome == taker_outcome, DegenError::OutcomeMismatch);
-    require!(maker_expiry > clock.unix_timestamp, DegenError::OrderExpired);
-    require!(taker_expiry > clock.unix_timestamp, DegenError::OrderExpired);
-    require!(maker_price >= MIN_PRICE && maker_price <= MAX_PRICE, DegenError::InvalidPrice);
-    require!(taker_price >= MIN_PRICE && taker_price <= MAX_PRICE, DegenError::InvalidPrice);
-    require!(maker_size >= MIN_ORDER_SIZE && maker_size <= MAX_ORDER_SIZE, DegenError::InvalidSize);
-    require!(taker_size >= MIN_ORDER_SIZE && taker_size <= MAX_ORDER_SIZE, DegenError::InvalidSize);
-    require!(match_size >= MIN_ORDER_SIZE && match_size <= MAX_ORDER_SIZE, DegenError::InvalidSize);
     
     // Price validation - orders must cross
     let execution_price = maker_price;
@@ -156,22 +183,12 @@ pub fn execute_match(
     }
     
     // Calculate costs
+    // On categorical markets the "YES" leg is the traded bucket and the "NO" leg its complement
     let outcome = maker_outcome;
-    let yes_price = if outcome == Outcome::Yes { execution_price } else { USDC_MULTIPLIER - execution_price };
-    let no_price = USDC_MULTIPLIER - yes_price;
-    
-    let yes_cost = yes_price
-        .checked_mul(match_size).ok_or(DegenError::MathOverflow)?
-        .checked_add(SHARE_MULTIPLIER - 1).ok_or(DegenError::MathOverflow)?
-        .checked_div(SHARE_MULTIPLIER).ok_or(DegenError::DivisionByZero)?;
-    
-    let no_cost = no_price
-        .checked_mul(match_size).ok_or(DegenError::MathOverflow)?
-        .checked_add(SHARE_MULTIPLIER - 1).ok_or(DegenError::MathOverflow)?
-        .checked_div(SHARE_MULTIPLIER).ok_or(DegenError::DivisionByZero)?;
+    let (yes_cost, no_cost) = opening_costs(outcome, execution_price, match_size).ok_or(DegenError::MathOverflow)?;
     
     // Determine YES/NO buyers
-    let is_maker_yes_buyer = (maker_side == Side::Bid && outcome == Outcome::Yes) ||
+    let is_maker_yes_buyer = (maker_side == Side::Bid && outcome != Outcome::No) ||
                              (maker_side == Side::Ask && outcome == Outcome::No);
     
     // Position references
@@ -179,32 +196,22 @@ pub fn execute_match(
     let taker_position = &mut ctx.accounts.taker_position;
     
     // Position limit checks
-    if is_maker_yes_buyer {
-        require!(
-            maker_position.yes_shares.checked_add(match_size).ok_or(DegenError::MathOverflow)? <= MAX_POSITION_SIZE,
-            DegenError::PositionLimitExceeded
-        );
-        require!(
-            taker_position.no_shares.checked_add(match_size).ok_or(DegenError::MathOverflow)? <= MAX_POSITION_SIZE,
-            DegenError::PositionLimitExceeded
-        );
-    } else {
-        require!(
-            taker_position.yes_shares.checked_add(match_size).ok_or(DegenError::MathOverflow)? <= MAX_POSITION_SIZE,
-            DegenError::PositionLimitExceeded
-        );
-        require!(
-            maker_position.no_shares.checked_add(match_size).ok_or(DegenError::MathOverflow)? <= MAX_POSITION_SIZE,
-            DegenError::PositionLimitExceeded
-        );
-    }
-    
-    // Calculate fees
+    require!(
+        maker_position.fits_opening_leg(outcome, is_maker_yes_buyer, market.num_buckets, match_size, trading_params.max_position_size),
+        DegenError::PositionLimitExceeded
+    );
+    require!(
+        taker_position.fits_opening_leg(outcome, !is_maker_yes_buyer, market.num_buckets, match_size, trading_params.max_position_size),
+        DegenError::PositionLimitExceeded
+    );
+    
+    // Calculate fees (market fee schedule, falling back to the global taker fee)
+    let taker_fee_bps = market.taker_fee_bps(global_state.taker_fee_bps, clock.unix_timestamp);
     let taker_fee = if is_maker_yes_buyer {
-        no_cost.checked_mul(global_state.taker_fee_bps as u64).ok_or(DegenError::MathOverflow)?
+        no_cost.checked_mul(taker_fee_bps as u64).ok_or(DegenError::MathOverflow)?
             .checked_div(10_000).ok_or(DegenError::DivisionByZero)?
     } else {
-        yes_cost.checked_mul(global_state.taker_fee_bps as u64).ok_or(DegenError::MathOverflow)?
+        yes_cost.checked_mul(taker_fee_bps as u64).ok_or(DegenError::MathOverflow)?
             .checked_div(10_000).ok_or(DegenError::DivisionByZero)?
     };
     
@@ -215,6 +222,14 @@ pub fn execute_match(
         (no_cost, yes_cost.checked_add(taker_fee).ok_or(DegenError::MathOverflow)?)
     };
     
+    // An escrowed order's escrow must cover its cost (and the taker fee)
+    if let Some(ref order) = ctx.accounts.maker_order {
+        require!(order.fill_escrow(match_size) >= maker_cost, DegenError::InsufficientEscrow);
+    }
+    if let Some(ref order) = ctx.accounts.taker_order {
+        require!(order.fill_escrow(match_size) >= taker_cost, DegenError::InsufficientEscrow);
+    }
+    
     // Token transfers - Opening trade: both parties deposit USDC to vault
     if !maker_has_escrow {
         msg!("Transferring {} USDC from maker via delegation", maker_cost);
@@ -238,17 +253,18 @@ pub fn execute_match(
         token::transfer(cpi_ctx, taker_cost)?;
     }
     
+    let market_seeds = &[
+        Market::SEED,
+        market.asset_bytes(),
+        market.timeframe_bytes(),
+        &market.expiry_at.to_le_bytes(),
+        &[market.strike_index],
+        &[market.bump],
+    ];
+    let signer_seeds = &[&market_seeds[..]];
+    
     // Transfer fees
     if taker_fee > 0 {
-        let market_seeds = &[
-            Market::SEED,
-            market.asset_bytes(),
-            market.timeframe_bytes(),
-            &market.expiry_at.to_le_bytes(),
-            &[market.bump],
-        ];
-        let signer_seeds = &[&market_seeds[..]];
-        
         let cpi_accounts = Transfer {
             from: ctx.accounts.vault.to_account_info(),
             to: ctx.accounts.fee_recipient.to_account_info(),
@@ -258,7 +274,10 @@ pub fn execute_match(
         token::transfer(cpi_ctx, taker_fee)?;
     }
     
-    // Update Order PDAs
+    // Update Order PDAs, releasing the escrow they no longer hold
+    let escrow_before = ctx.accounts.maker_order.as_ref().map_or(0, |order| order.outstanding_escrow())
+        .checked_add(ctx.accounts.taker_order.as_ref().map_or(0, |order| order.outstanding_escrow()))
+        .ok_or(DegenError::MathOverflow)?;
     if let Some(ref mut maker_order) = ctx.accounts.maker_order {
         maker_order.filled_size = maker_order.filled_size.checked_add(match_size).ok_or(DegenError::MathOverflow)?;
         maker_order.status = if maker_order.filled_size >= maker_order.size { OrderStatus::Filled } else { OrderStatus::PartialFill };
@@ -269,6 +288,36 @@ pub fn execute_match(
         taker_order.status = if taker_order.filled_size >= taker_order.size { OrderStatus::Filled } else { OrderStatus::PartialFill };
     }
     
+    // Refund escrow the fill did not use (price improvement). Immediate (IOC/Market) orders
+    // never rest: also cancel the unfilled remainder and refund its escrow.
+    for (order, owner_usdc, cost) in [
+        (ctx.accounts.maker_order.as_mut(), &ctx.accounts.maker_usdc, maker_cost),
+        (ctx.accounts.taker_order.as_mut(), &ctx.accounts.taker_usdc, taker_cost),
+    ] {
+        let Some(order) = order else { continue };
+        let mut refund_amount = order.fill_surplus(match_size, cost);
+        if order.is_immediate() && order.is_active() {
+            refund_amount = refund_amount.checked_add(order.refundable_amount()).ok_or(DegenError::MathOverflow)?;
+            order.status = OrderStatus::Cancelled;
+            msg!("Cancelled unfilled remainder of immediate order {}", order.key());
+        }
+        
+        if refund_amount > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: owner_usdc.to_account_info(),
+                authority: market_info.clone(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, refund_amount)?;
+            msg!("Refunded {} USDC of escrow to order {}", refund_amount, order.key());
+        }
+    }
+    let escrow_after = ctx.accounts.maker_order.as_ref().map_or(0, |order| order.outstanding_escrow())
+        .checked_add(ctx.accounts.taker_order.as_ref().map_or(0, |order| order.outstanding_escrow()))
+        .ok_or(DegenError::MathOverflow)?;
+    market.release_escrow(escrow_before.saturating_sub(escrow_after));
+    
     // Initialize positions if needed
     if maker_position.owner == Pubkey::default() {
         maker_position.owner = ctx.accounts.maker.key();
@@ -285,22 +334,12 @@ pub fn execute_match(
     }
     
     // Update positions - Opening trade: mint new shares
-    if is_maker_yes_buyer {
-        maker_position.yes_shares = maker_position.yes_shares.checked_add(match_size).ok_or(DegenError::MathOverflow)?;
-        maker_position.yes_cost_basis = maker_position.yes_cost_basis.checked_add(yes_cost).ok_or(DegenError::MathOverflow)?;
-        taker_position.no_shares = taker_position.no_shares.checked_add(match_size).ok_or(DegenError::MathOverflow)?;
-        taker_position.no_cost_basis = taker_position.no_cost_basis.checked_add(no_cost.checked_add(taker_fee).ok_or(DegenError::MathOverflow)?).ok_or(DegenError::MathOverflow)?;
-    } else {
-        taker_position.yes_shares = taker_position.yes_shares.checked_add(match_size).ok_or(DegenError::MathOverflow)?;
-        taker_position.yes_cost_basis = taker_position.yes_cost_basis.checked_add(yes_cost.checked_add(taker_fee).ok_or(DegenError::MathOverflow)?).ok_or(DegenError::MathOverflow)?;
-        maker_position.no_shares = maker_position.no_shares.checked_add(match_size).ok_or(DegenError::MathOverflow)?;
-        maker_position.no_cost_basis = maker_position.no_cost_basis.checked_add(no_cost).ok_or(DegenError::MathOverflow)?;
-    }
+    // (on categorical markets the bucket buyer gets the bucket, the other side every other bucket)
+    maker_position.mint_opening_leg(outcome, is_maker_yes_buyer, market.num_buckets, match_size, maker_cost).ok_or(DegenError::MathOverflow)?;
+    taker_position.mint_opening_leg(outcome, !is_maker_yes_buyer, market.num_buckets, match_size, taker_cost).ok_or(DegenError::MathOverflow)?;
     
     // Update market stats
-    market.open_interest = market.open_interest.checked_add(match_size).ok_or(DegenError::MathOverflow)?;
-    market.total_volume = market.total_volume.checked_add(yes_cost.checked_add(no_cost).ok_or(DegenError::MathOverflow)?).ok_or(DegenError::MathOverflow)?;
-    market.total_trades = market.total_trades.checked_add(1).ok_or(DegenError::MathOverflow)?;
+    market.record_opening_fill(match_size, yes_cost, no_cost, taker_fee).ok_or(DegenError::MathOverflow)?;
     
     msg!("Match executed: {} shares @ {} (yes={}, no={}, fee={})", match_size, execution_price, yes_cost, no_cost, taker_fee);
     
@@ -0,0 +1,263 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{GlobalState, Market, UserPosition, Order, OrderStatus, Outcome, MarketStatus, Side, TradeType, USDC_MULTIPLIER, SHARE_MULTIPLIER};
+use crate::errors::DegenError;
+
+/// Arguments for execute_merge instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MergeTradeArgs {
+    pub maker_outcome: Outcome, // YES or NO sold by the maker (the taker sells the other)
+    pub maker_price: u64,       // Maker's ask price (6 decimals) - the execution price
+    pub taker_price: u64,       // Taker's ask price for the other outcome (6 decimals)
+    pub size: u64,              // Number of pairs to burn (6 decimals)
+}
+
+#[derive(Accounts)]
+pub struct ExecuteMerge<'info> {
+    #[account(
+        seeds = [GlobalState::SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+    
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+    
+    /// Market's USDC vault - pays both sellers
+    #[account(
+        mut,
+        constraint = vault.owner == market.key() @ DegenError::InvalidMarketParams
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+    
+    /// Fee recipient's USDC account
+    #[account(
+        mut,
+        constraint = fee_recipient.owner == global_state.fee_recipient @ DegenError::Unauthorized
+    )]
+    pub fee_recipient: Box<Account<'info, TokenAccount>>,
+    
+    // Maker (selling `maker_outcome` shares)
+    /// CHECK: Maker wallet - validated by relayer
+    pub maker: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        seeds = [UserPosition::SEED, market.key().as_ref(), maker.key().as_ref()],
+        bump = maker_position.bump,
+        constraint = maker_position.owner == maker.key() @ DegenError::Unauthorized
+    )]
+    pub maker_position: Box<Account<'info, UserPosition>>,
+    
+    /// Maker's USDC account (receives payment)
+    #[account(
+        mut,
+        constraint = maker_usdc.owner == maker.key() @ DegenError::Unauthorized
+    )]
+    pub maker_usdc: Box<Account<'info, TokenAccount>>,
+    
+    /// Maker's ask - the merge must match its outcome, price and remaining size
+    #[account(mut)]
+    pub maker_order: Box<Account<'info, Order>>,
+    
+    // Taker (selling the opposite outcome)
+    /// CHECK: Taker wallet - validated by relayer
+    pub taker: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        seeds = [UserPosition::SEED, market.key().as_ref(), taker.key().as_ref()],
+        bump = taker_position.bump,
+        constraint = taker_position.owner == taker.key() @ DegenError::Unauthorized
+    )]
+    pub taker_position: Box<Account<'info, UserPosition>>,
+    
+    /// Taker's USDC account (receives payment)
+    #[account(
+        mut,
+        constraint = taker_usdc.owner == taker.key() @ DegenError::Unauthorized
+    )]
+    pub taker_usdc: Box<Account<'info, TokenAccount>>,
+    
+    /// Taker's ask for the opposite outcome
+    #[account(mut)]
+    pub taker_order: Box<Account<'info, Order>>,
+    
+    /// Relayer that submits the tx
+    pub relayer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Merge a YES seller with a NO seller: burn `size` pairs and pay both from the vault.
+/// 
+/// Both sellers must have placed matching ask orders (the args are checked against them).
+/// The asks must sum to at most $1. The maker gets its price and the taker the rest of
+/// the $1 per pair (price improvement), less the taker fee. The sellers deliver shares,
+/// so the orders' escrow for the merged size is refunded. Only YES/NO markets can merge.
+pub fn execute_merge(
+    ctx: Context<ExecuteMerge>,
+    args: MergeTradeArgs,
+) -> Result<()> {
+    let global_state = &ctx.accounts.global_state;
+    let market_info = ctx.accounts.market.to_account_info();
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+    
+    // Validations
+    require!(!global_state.paused, DegenError::ProtocolPaused);
+    require!(market.status == MarketStatus::Open, DegenError::MarketNotOpen);
+    require!(market.is_trading_open(clock.unix_timestamp), DegenError::MarketClosing);
+    require!(ctx.accounts.maker.key() != ctx.accounts.taker.key(), DegenError::SelfTrade);
+    require!(
+        matches!(args.maker_outcome, Outcome::Yes | Outcome::No) && market.accepts_outcome(args.maker_outcome),
+        DegenError::InvalidOutcome
+    );
+    // The args must match what both sellers signed for in their ask orders
+    let taker_outcome = if args.maker_outcome == Outcome::Yes { Outcome::No } else { Outcome::Yes };
+    let market_key = market.key();
+    check_ask(&ctx.accounts.maker_order, ctx.accounts.maker.key(), market_key, args.maker_outcome, args.maker_price, args.size, &clock)?;
+    check_ask(&ctx.accounts.taker_order, ctx.accounts.taker.key(), market_key, taker_outcome, args.taker_price, args.size, &clock)?;
+    
+    let trading_params = market.trading_params;
+    require!(trading_params.allows_price(args.maker_price), DegenError::InvalidPrice);
+    require!(trading_params.allows_price(args.taker_price), DegenError::InvalidPrice);
+    require!(trading_params.is_on_tick(args.maker_price), DegenError::InvalidTickSize);
+    require!(trading_params.is_on_tick(args.taker_price), DegenError::InvalidTickSize);
+    require!(trading_params.allows_size(args.size), DegenError::InvalidSize);
+    
+    // Both asks must fit in the $1 a pair redeems for
+    let total_price = args.maker_price.checked_add(args.taker_price).ok_or(DegenError::MathOverflow)?;
+    require!(total_price <= USDC_MULTIPLIER, DegenError::PriceMismatch);
+    
+    let maker_yes_leg = args.maker_outcome == Outcome::Yes;
+    let maker_position = &mut ctx.accounts.maker_position;
+    let taker_position = &mut ctx.accounts.taker_position;
+    let maker_shares = if maker_yes_leg { maker_position.yes_shares } else { maker_position.no_shares };
+    let taker_shares = if maker_yes_leg { taker_position.no_shares } else { taker_position.yes_shares };
+    require!(maker_shares >= args.size && taker_shares >= args.size, DegenError::InsufficientShares);
+    
+    // Each pair redeems for $1: the maker gets its price, the taker the rest
+    let payout = args.size
+        .checked_mul(USDC_MULTIPLIER).ok_or(DegenError::MathOverflow)?
+        .checked_div(SHARE_MULTIPLIER).ok_or(DegenError::DivisionByZero)?;
+    let maker_proceeds = args.maker_price
+        .checked_mul(args.size).ok_or(DegenError::MathOverflow)?
+        .checked_div(SHARE_MULTIPLIER).ok_or(DegenError::DivisionByZero)?;
+    let taker_proceeds = payout.checked_sub(maker_proceeds).ok_or(DegenError::MathUnderflow)?;
+    require!(ctx.accounts.vault.amount >= payout, DegenError::InsufficientVaultBalance);
+    
+    // Taker fee on the taker's proceeds (market fee schedule, falling back to the global taker fee)
+    let taker_fee_bps = market.taker_fee_bps(global_state.taker_fee_bps, clock.unix_timestamp);
+    let taker_fee = taker_proceeds
+        .checked_mul(taker_fee_bps as u64).ok_or(DegenError::MathOverflow)?
+        .checked_div(10_000).ok_or(DegenError::DivisionByZero)?;
+    let taker_receives = taker_proceeds.saturating_sub(taker_fee);
+    
+    // Burn both legs, realizing PnL against the cost basis released
+    let maker_released = maker_position.burn_leg(maker_yes_leg, args.size).ok_or(DegenError::MathOverflow)?;
+    let taker_released = taker_position.burn_leg(!maker_yes_leg, args.size).ok_or(DegenError::MathOverflow)?;
+    let maker_realized_pnl = maker_position.realize_pnl(maker_proceeds, maker_released).ok_or(DegenError::MathOverflow)?;
+    let taker_realized_pnl = taker_position.realize_pnl(taker_receives, taker_released).ok_or(DegenError::MathOverflow)?;
+    
+    let released = maker_released.checked_add(taker_released).ok_or(DegenError::MathOverflow)?;
+    market.record_merge(args.size, payout, released).ok_or(DegenError::MathOverflow)?;
+    
+    // Fill both asks. The sellers delivered shares, so the escrow the orders no longer hold
+    // (the merged size, plus the unfilled remainder of immediate orders) goes back to them.
+    let mut refunds = [0u64; 2];
+    for (refund, order) in refunds.iter_mut().zip([&mut ctx.accounts.maker_order, &mut ctx.accounts.taker_order]) {
+        let escrow_before = order.outstanding_escrow();
+        order.filled_size = order.filled_size.checked_add(args.size).ok_or(DegenError::MathOverflow)?;
+        order.status = if order.filled_size >= order.size { OrderStatus::Filled } else { OrderStatus::PartialFill };
+        if order.is_immediate() && order.is_active() {
+            order.status = OrderStatus::Cancelled;
+        }
+        *refund = escrow_before.saturating_sub(order.outstanding_escrow());
+        market.release_escrow(*refund);
+    }
+    let [maker_refund, taker_refund] = refunds;
+    
+    // Pay both sellers and the fee out of the vault
+    let market_seeds = &[
+        Market::SEED,
+        market.asset_bytes(),
+        market.timeframe_bytes(),
+        &market.expiry_at.to_le_bytes(),
+        &[market.strike_index],
+        &[market.bump],
+    ];
+    let signer_seeds = &[&market_seeds[..]];
+    
+    let payouts = [
+        (ctx.accounts.maker_usdc.to_account_info(), maker_proceeds.checked_add(maker_refund).ok_or(DegenError::MathOverflow)?),
+        (ctx.accounts.taker_usdc.to_account_info(), taker_receives.checked_add(taker_refund).ok_or(DegenError::MathOverflow)?),
+        (ctx.accounts.fee_recipient.to_account_info(), taker_fee),
+    ];
+    for (to, amount) in payouts {
+        if amount > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to,
+                authority: market_info.clone(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, amount)?;
+        }
+    }
+    
+    msg!(
+        "Merge executed: {} pairs, maker {:?} @ {} (maker={}, taker={}, fee={})",
+        args.size, args.maker_outcome, args.maker_price, maker_proceeds, taker_receives, taker_fee
+    );
+    
+    emit!(MergeExecuted {
+        market: market.key(),
+        maker: ctx.accounts.maker.key(),
+        taker: ctx.accounts.taker.key(),
+        maker_outcome: args.maker_outcome,
+        price: args.maker_price,
+        size: args.size,
+        maker_proceeds,
+        taker_proceeds,
+        taker_fee,
+        maker_realized_pnl,
+        taker_realized_pnl,
+        trade_type: TradeType::Merging,
+    });
+    
+    Ok(())
+}
+
+/// Check a seller's order is its own active, unexpired ask on this market for `outcome`
+/// at `price`, with at least `size` left to fill
+fn check_ask(order: &Order, seller: Pubkey, market: Pubkey, outcome: Outcome, price: u64, size: u64, clock: &Clock) -> Result<()> {
+    require!(order.owner == seller, DegenError::Unauthorized);
+    require!(order.market == market, DegenError::InvalidMarketParams);
+    require!(order.is_active(), DegenError::OrderNotActive);
+    require!(!order.on_book, DegenError::OrderOnBook);
+    require!(!order.is_expired(clock.unix_timestamp, clock.slot), DegenError::OrderExpired);
+    require!(
+        order.side == Side::Ask && order.outcome == outcome && order.price == price,
+        DegenError::MergeOrderMismatch
+    );
+    require!(size <= order.remaining_size(), DegenError::InvalidSize);
+    require!(order.allows_fill(size), DegenError::FillOrKillNotFilled);
+    Ok(())
+}
+
+#[event]
+pub struct MergeExecuted {
+    pub market: Pubkey,
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub maker_outcome: Outcome,
+    pub price: u64,
+    pub size: u64,
+    pub maker_proceeds: u64,
+    pub taker_proceeds: u64,
+    pub taker_fee: u64,
+    pub maker_realized_pnl: i64,
+    pub taker_realized_pnl: i64,
+    pub trade_type: TradeType,
+}
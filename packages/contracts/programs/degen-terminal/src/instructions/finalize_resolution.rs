@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::{Market, MarketStatus, ResolutionProposal};
+use crate::instructions::propose_resolution::release_bond;
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct FinalizeResolution<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [ResolutionProposal::SEED, market.key().as_ref()],
+        bump = proposal.bump,
+        has_one = market @ DegenError::InvalidMarketParams,
+        has_one = proposer @ DegenError::InvalidMarketParams,
+        close = proposer
+    )]
+    pub proposal: Account<'info, ResolutionProposal>,
+    
+    /// Bond escrow holding the proposer's bond
+    #[account(
+        mut,
+        address = proposal.bond_vault @ DegenError::InvalidMarketParams
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+    
+    /// Proposer (receives account rent back)
+    /// CHECK: Validated via has_one on proposal
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+    
+    /// Proposer's USDC token account (bond is returned here)
+    #[account(
+        mut,
+        constraint = proposer_usdc.owner == proposal.proposer @ DegenError::Unauthorized,
+        constraint = proposer_usdc.mint == bond_vault.mint @ DegenError::InvalidMarketParams
+    )]
+    pub proposer_usdc: Account<'info, TokenAccount>,
+    
+    /// Anyone can finalize once the dispute window has passed
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Finalize an unchallenged proposal after the dispute window.
+/// The market is resolved with the proposed price and outcome, and the
+/// proposer's bond is returned.
+pub fn finalize_resolution(ctx: Context<FinalizeResolution>) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &mut ctx.accounts.market;
+    let proposal = &ctx.accounts.proposal;
+    
+    require!(market.status == MarketStatus::Proposed, DegenError::MarketNotProposed);
+    require!(!proposal.is_challenged(), DegenError::ProposalAlreadyChallenged);
+    require!(clock.unix_timestamp >= proposal.challenge_deadline, DegenError::DisputeWindowOpen);
+    
+    let refund = release_bond(
+        proposal,
+        &ctx.accounts.bond_vault,
+        ctx.accounts.proposer_usdc.to_account_info(),
+        ctx.accounts.proposer.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    )?;
+    
+    market.final_price = proposal.final_price;
+    market.resolved_at = clock.unix_timestamp;
+    market.status = MarketStatus::Resolved;
+    market.outcome = proposal.outcome;
+    
+    msg!(
+        "Market #{} resolved after dispute window: {:?} wins (final={} strike={}), bond returned ({})",
+        market.id, market.outcome, market.final_price, market.strike_price, refund
+    );
+    
+    Ok(())
+}
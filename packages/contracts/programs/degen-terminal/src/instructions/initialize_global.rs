@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::GlobalState;
+use crate::state::{GlobalState, MAX_FEE_BPS};
 use crate::errors::DegenError;
 
 #[derive(Accounts)]
@@ -28,8 +28,8 @@ pub fn initialize_global(
     taker_fee_bps: u16,
 ) -> Result<()> {
     // Validate fee configuration (max 5% = 500 bps)
-    require!(maker_fee_bps <= 500, DegenError::InvalidFeeConfig);
-    require!(taker_fee_bps <= 500, DegenError::InvalidFeeConfig);
+    require!(maker_fee_bps <= MAX_FEE_BPS, DegenError::InvalidFeeConfig);
+    require!(taker_fee_bps <= MAX_FEE_BPS, DegenError::InvalidFeeConfig);
     
     let global_state = &mut ctx.accounts.global_state;
     
@@ -1,11 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount, Mint};
 use anchor_spl::associated_token::AssociatedToken;
-use crate::state::{GlobalState, Market, MarketStatus, MarketOutcome, str_to_bytes, MAX_ASSET_LEN, MAX_TIMEFRAME_LEN};
+use crate::state::{GlobalState, AssetConfig, TimeframeConfig, Market, MarketGroup, MarketSeries, MarketStatus, FeeSchedule, MarketOutcome, ResolutionMethod, str_to_bytes, MAX_ASSET_LEN, MAX_TIMEFRAME_LEN, MAX_GROUP_MARKETS};
 use crate::errors::DegenError;
 
 #[derive(Accounts)]
-#[instruction(asset: String, timeframe: String, strike_price: u64, expiry_ts: i64)]
+#[instruction(asset: String, timeframe: String, strike_price: u64, expiry_ts: i64, strike_index: u8)]
 pub struct InitializeMarket<'info> {
     #[account(
         mut,
@@ -14,6 +14,20 @@ pub struct InitializeMarket<'info> {
     )]
     pub global_state: Account<'info, GlobalState>,
     
+    /// Registered asset (must be enabled for this timeframe)
+    #[account(
+        seeds = [AssetConfig::SEED, asset.as_bytes()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+    
+    /// Registered timeframe (duration, close buffer, lead time bounds)
+    #[account(
+        seeds = [TimeframeConfig::SEED, timeframe.as_bytes()],
+        bump = timeframe_config.bump
+    )]
+    pub timeframe_config: Account<'info, TimeframeConfig>,
+    
     #[account(
         init,
         payer = authority,
@@ -22,12 +36,37 @@ pub struct InitializeMarket<'info> {
             Market::SEED,
             asset.as_bytes(),
             timeframe.as_bytes(),
-            &expiry_ts.to_le_bytes()
+            &expiry_ts.to_le_bytes(),
+            &[strike_index]
         ],
         bump
     )]
     pub market: Account<'info, Market>,
     
+    /// Strike ladder for this asset, timeframe and expiry (created with the first strike)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = MarketGroup::SIZE,
+        seeds = [
+            MarketGroup::SEED,
+            asset.as_bytes(),
+            timeframe.as_bytes(),
+            &expiry_ts.to_le_bytes()
+        ],
+        bump
+    )]
+    pub market_group: Account<'info, MarketGroup>,
+    
+    /// Rolling series for the asset and timeframe - always passed so a series slot cannot be
+    /// created already open. May be uninitialized if no series has been set up.
+    /// CHECK: PDA address is enforced by seeds; contents are loaded with `MarketSeries::load_optional`
+    #[account(
+        seeds = [MarketSeries::SEED, asset.as_bytes(), timeframe.as_bytes()],
+        bump
+    )]
+    pub market_series: UncheckedAccount<'info>,
+    
     /// The market's USDC vault (ATA owned by market PDA)
     #[account(
         init,
@@ -54,6 +93,7 @@ pub fn initialize_market(
     timeframe: String,
     strike_price: u64,
     expiry_ts: i64,
+    strike_index: u8,
 ) -> Result<()> {
     let clock = Clock::get()?;
     
@@ -61,15 +101,41 @@ pub fn initialize_market(
     require!(asset.len() <= MAX_ASSET_LEN, DegenError::InvalidAsset);
     require!(timeframe.len() <= MAX_TIMEFRAME_LEN, DegenError::InvalidTimeframe);
     // strike_price = 0 is allowed for PENDING markets (will be set at activation)
-    require!(expiry_ts > clock.unix_timestamp + 60, DegenError::InvalidExpiry); // At least 1 minute in future
+    require!(expiry_ts > clock.unix_timestamp, DegenError::InvalidExpiry);
+    
+    // Validate asset is registered and enabled
+    let asset_config = &ctx.accounts.asset_config;
+    require!(asset_config.enabled, DegenError::AssetDisabled);
+    
+    // Validate timeframe: enabled for the asset, expiry on the grid and within lead time bounds
+    let timeframe_config = &ctx.accounts.timeframe_config;
+    require!(asset_config.allows_timeframe(&timeframe), DegenError::TimeframeNotAllowed);
+    require!(timeframe_config.is_aligned(expiry_ts), DegenError::ExpiryNotAligned);
+    require!(timeframe_config.allows_lead_time(expiry_ts, clock.unix_timestamp), DegenError::InvalidLeadTime);
     
-    // Validate asset is supported (BTC, ETH, SOL)
-    let valid_assets = ["BTC", "ETH", "SOL"];
-    require!(valid_assets.contains(&asset.as_str()), DegenError::InvalidAsset);
+    // Strike is kept at the asset's precision
+    let strike_price = asset_config.normalize_strike(strike_price);
     
-    // Validate timeframe
-    let valid_timeframes = ["5m", "15m", "1h", "4h", "24h"];
-    require!(valid_timeframes.contains(&timeframe.as_str()), DegenError::InvalidTimeframe);
+    // Strikes are listed in order, so each index maps to exactly one market
+    let market_group = &mut ctx.accounts.market_group;
+    require!((market_group.num_markets as usize) < MAX_GROUP_MARKETS, DegenError::MarketGroupFull);
+    require!(strike_index == market_group.num_markets, DegenError::InvalidStrikeIndex);
+    
+    // Only the ladder's creator or the admin can add strikes, so nobody can fill it up
+    let authority = ctx.accounts.authority.key();
+    require!(
+        strike_index == 0 || authority == market_group.creator || authority == ctx.accounts.global_state.admin,
+        DegenError::Unauthorized
+    );
+    if market_group.num_markets == 0 {
+        market_group.asset = str_to_bytes::<MAX_ASSET_LEN>(&asset);
+        market_group.timeframe = str_to_bytes::<MAX_TIMEFRAME_LEN>(&timeframe);
+        market_group.expiry_at = expiry_ts;
+        market_group.creator = ctx.accounts.authority.key();
+        market_group.bump = ctx.bumps.market_group;
+    }
+    market_group.markets[strike_index as usize] = ctx.accounts.market.key();
+    market_group.num_markets += 1;
     
     // Update global state
     let global_state = &mut ctx.accounts.global_state;
@@ -78,7 +144,8 @@ pub fn initialize_market(
     
     // Initialize market
     // If strike_price = 0, market is created as PENDING and will be activated later
-    // If strike_price > 0, market is created as OPEN (direct activation)
+    // If strike_price > 0, market is created as OPEN (direct activation), unless its strike
+    // must come from the oracle or a series
     let market = &mut ctx.accounts.market;
     market.id = market_id;
     market.authority = ctx.accounts.authority.key();
@@ -87,6 +154,7 @@ pub fn initialize_market(
     market.strike_price = strike_price;
     market.final_price = 0;
     market.created_at = clock.unix_timestamp;
+    market.start_at = expiry_ts - timeframe_config.duration;
     market.expiry_at = expiry_ts;
     market.resolved_at = 0;
     market.settled_at = 0;
@@ -97,11 +165,31 @@ pub fn initialize_market(
     market.total_positions = 0;
     market.settled_positions = 0;
     market.open_interest = 0;
+    market.total_cost_basis = 0;
+    market.refund_vault_balance = 0;
+    market.open_order_escrow = 0;
+    market.resolution_method = ResolutionMethod::Spot;
+    market.resolution_window = 0;
+    market.resolution_samples = 0;
+    market.close_buffer = timeframe_config.close_buffer;
+    market.strike_index = strike_index;
+    market.clear_market_type();
+    market.trading_params = timeframe_config.trading_params;
+    market.fee_schedule = FeeSchedule::default();
+    market.oracle_activation = asset_config.oracle_activation;
     market.bump = ctx.bumps.market;
     
+    // Oracle-activated markets and base markets of a rolling series stay pending until
+    // their strike is set from the oracle or by `roll_series`
+    let market_series = MarketSeries::load_optional(&ctx.accounts.market_series)?;
+    if market.oracle_activation || market_series.is_some_and(|series| series.chains(market)) {
+        market.strike_price = 0;
+        market.status = MarketStatus::Pending;
+    }
+    
     msg!(
-        "Market #{} initialized: {} {} strike={} expiry={} status={:?}", 
-        market_id, asset, timeframe, strike_price, expiry_ts, market.status
+        "Market #{} initialized: {} {} strike={} expiry={} strike_index={} status={:?}", 
+        market_id, asset, timeframe, strike_price, expiry_ts, strike_index, market.status
     );
     
     Ok(())
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalState, Market, MarketStatus, OrderBook};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct InitializeOrderBook<'info> {
+    #[account(
+        seeds = [GlobalState::SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = OrderBook::SIZE,
+        seeds = [OrderBook::SEED, market.key().as_ref()],
+        bump
+    )]
+    pub order_book: AccountLoader<'info, OrderBook>,
+    
+    /// Market authority or admin (pays for the book)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the on-chain order book of a market.
+/// 
+/// Optional per market: without a book, orders are matched by the relayer through
+/// `execute_match`. With one, limit orders posted to it are filled in price-time
+/// priority by the permissionless `crank_match`.
+pub fn initialize_order_book(ctx: Context<InitializeOrderBook>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let authority = ctx.accounts.authority.key();
+    
+    require!(
+        authority == market.authority || authority == ctx.accounts.global_state.admin,
+        DegenError::Unauthorized
+    );
+    require!(
+        matches!(market.status, MarketStatus::Pending | MarketStatus::Open),
+        DegenError::MarketNotOpen
+    );
+    
+    let mut order_book = ctx.accounts.order_book.load_init()?;
+    order_book.market = market.key();
+    order_book.bump = ctx.bumps.order_book;
+    
+    msg!("Order book created for market #{}: {}", market.id, ctx.accounts.order_book.key());
+    
+    Ok(())
+}
@@ -1,27 +1,93 @@
 pub mod initialize_global;
 pub mod initialize_market;
 pub mod activate_market;
+pub mod activate_market_with_attestation;
+pub mod activate_market_with_oracle;
 pub mod place_order;
 pub mod cancel_order;
 pub mod cancel_order_by_relayer;
 pub mod execute_match;
+pub mod execute_batch_match;
 pub mod execute_close;
+pub mod execute_merge;
 pub mod resolve_market;
+pub mod resolve_market_with_oracle;
+pub mod resolve_market_with_attestation;
+pub mod resolve_market_with_oracles;
+pub mod resolve_market_on_touch;
+pub mod set_resolution_method;
+pub mod set_market_buckets;
+pub mod set_scalar_bounds;
+pub mod set_barrier;
+pub mod set_market_fees;
+pub mod record_price_sample;
+pub mod submit_resolution;
+pub mod propose_resolution;
+pub mod challenge_resolution;
+pub mod settle_dispute;
+pub mod finalize_resolution;
+pub mod void_market;
 pub mod settle_positions;
 pub mod close_market;
 pub mod pause_protocol;
 pub mod update_config;
+pub mod set_resolvers;
+pub mod set_dispute_config;
+pub mod set_price_publishers;
+pub mod set_oracle_config;
+pub mod set_asset_config;
+pub mod set_timeframe_config;
+pub mod set_market_series;
+pub mod roll_series;
+pub mod set_market_schedule;
+pub mod crank_create_market;
+pub mod initialize_order_book;
+pub mod post_order;
+pub mod crank_match;
 
 pub use initialize_global::*;
 pub use initialize_market::*;
 pub use activate_market::*;
+pub use activate_market_with_attestation::*;
+pub use activate_market_with_oracle::*;
 pub use place_order::*;
 pub use cancel_order::*;
 pub use cancel_order_by_relayer::*;
 pub use execute_match::*;
+pub use execute_batch_match::*;
 pub use execute_close::*;
+pub use execute_merge::*;
 pub use resolve_market::*;
+pub use resolve_market_with_oracle::*;
+pub use resolve_market_with_attestation::*;
+pub use resolve_market_with_oracles::*;
+pub use resolve_market_on_touch::*;
+pub use set_resolution_method::*;
+pub use set_market_buckets::*;
+pub use set_scalar_bounds::*;
+pub use set_barrier::*;
+pub use set_market_fees::*;
+pub use record_price_sample::*;
+pub use submit_resolution::*;
+pub use propose_resolution::*;
+pub use challenge_resolution::*;
+pub use settle_dispute::*;
+pub use finalize_resolution::*;
+pub use void_market::*;
 pub use settle_positions::*;
 pub use close_market::*;
 pub use pause_protocol::*;
 pub use update_config::*;
+pub use set_resolvers::*;
+pub use set_dispute_config::*;
+pub use set_price_publishers::*;
+pub use set_oracle_config::*;
+pub use set_asset_config::*;
+pub use set_timeframe_config::*;
+pub use set_market_series::*;
+pub use roll_series::*;
+pub use set_market_schedule::*;
+pub use crank_create_market::*;
+pub use initialize_order_book::*;
+pub use post_order::*;
+pub use crank_match::*;
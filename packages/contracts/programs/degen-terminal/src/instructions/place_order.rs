@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use anchor_spl::associated_token::AssociatedToken;
-use crate::state::{GlobalState, Market, Order, OrderStatus, Side, Outcome, OrderType, USDC_MULTIPLIER, SHARE_MULTIPLIER, MIN_PRICE, MAX_PRICE, MIN_ORDER_SIZE, MAX_ORDER_SIZE};
+use crate::state::{GlobalState, Market, Order, OrderStatus, Side, Outcome, OrderType, USDC_MULTIPLIER, SHARE_MULTIPLIER, MAX_FEE_BPS};
 use crate::errors::DegenError;
 
 /// Arguments for placing an order
@@ -9,7 +9,7 @@ use crate::errors::DegenError;
 pub struct PlaceOrderArgs {
     /// Order side (Bid = buy, Ask = sell)
     pub side: Side,
-    /// Outcome being traded (Yes or No)
+    /// Outcome being traded (Yes or No, or a bucket on categorical markets)
     pub outcome: Outcome,
     /// Order type (Limit, Market, IOC, FOK)
     pub order_type: OrderType,
@@ -33,6 +33,7 @@ pub struct PlaceOrder<'info> {
     pub global_state: Account<'info, GlobalState>,
     
     #[account(
+        mut,
         constraint = market.is_trading_open(Clock::get()?.unix_timestamp) @ DegenError::MarketNotOpen
     )]
     pub market: Account<'info, Market>,
@@ -81,6 +82,12 @@ pub struct PlaceOrder<'info> {
 /// 1. Creates an on-chain Order PDA
 /// 2. Transfers USDC from user to market vault (escrow)
 /// 3. The escrowed USDC is used when the order matches
+/// 
+/// The escrow is the order's cost plus a reserve for the maximum taker fee.
+/// 
+/// Limit orders rest until `expiry_ts`. Market, IOC and FOK orders ignore `expiry_ts`
+/// and can only be matched in the slot they are placed in (place and match in the
+/// same transaction); afterwards they can only be cancelled.
 pub fn place_order(
     ctx: Context<PlaceOrder>,
     args: PlaceOrderArgs,
@@ -92,14 +99,14 @@ pub fn place_order(
     // Check protocol is not paused
     require!(!global_state.paused, DegenError::ProtocolPaused);
     
-    // Validate price ($0.01 - $0.99)
-    require!(args.price >= MIN_PRICE && args.price <= MAX_PRICE, DegenError::InvalidPrice);
+    // Validate outcome (YES/NO on binary markets, a listed bucket on categorical ones)
+    require!(ctx.accounts.market.accepts_outcome(args.outcome), DegenError::InvalidOutcome);
     
-    // Validate price is on tick grid ($0.01 increments = 10_000 in 6 decimals)
-    require!(args.price % 10_000 == 0, DegenError::InvalidTickSize);
-    
-    // Validate size (1 - 100,000 contracts)
-    require!(args.size >= MIN_ORDER_SIZE && args.size <= MAX_ORDER_SIZE, DegenError::InvalidSize);
+    // Validate price, tick and size against the market's trading params
+    let trading_params = &ctx.accounts.market.trading_params;
+    require!(trading_params.allows_price(args.price), DegenError::InvalidPrice);
+    require!(trading_params.is_on_tick(args.price), DegenError::InvalidTickSize);
+    require!(trading_params.allows_size(args.size), DegenError::InvalidSize);
     
     // Check order hasn't expired (for limit orders)
     if args.order_type == OrderType::Limit {
@@ -116,7 +123,7 @@ pub fn place_order(
     //
     // For BID (buying): lock price * size / SHARE_MULTIPLIER
     // For ASK (selling): lock (1 - price) * size / SHARE_MULTIPLIER
-    let lock_amount = if args.side == Side::Bid {
+    let cost_amount = if args.side == Side::Bid {
         // Buying: lock price * size / SHARE_MULTIPLIER
         args.price
             .checked_mul(args.size)
@@ -136,6 +143,17 @@ pub fn place_order(
             .ok_or(DegenError::DivisionByZero)?
     };
     
+    // Also lock the highest possible taker fee, so the escrow covers the fee if the order is
+    // the taker (fees can change before it fills). Unused escrow is refunded on fill.
+    let fee_reserve = cost_amount
+        .checked_mul(MAX_FEE_BPS as u64)
+        .ok_or(DegenError::MathOverflow)?
+        .checked_add(9_999)  // Round up
+        .ok_or(DegenError::MathOverflow)?
+        .checked_div(10_000)
+        .ok_or(DegenError::DivisionByZero)?;
+    let lock_amount = cost_amount.checked_add(fee_reserve).ok_or(DegenError::MathOverflow)?;
+    
     // Verify user has sufficient balance
     require!(
         ctx.accounts.user_usdc.amount >= lock_amount,
@@ -151,6 +169,7 @@ pub fn place_order(
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
     token::transfer(cpi_ctx, lock_amount)?;
+    ctx.accounts.market.lock_escrow(lock_amount).ok_or(DegenError::MathOverflow)?;
     
     msg!("Locked {} USDC in vault for order", lock_amount);
     
@@ -167,6 +186,7 @@ pub fn place_order(
     order.client_order_id = args.client_order_id;
     order.expiry_ts = args.expiry_ts;
     order.created_at = clock.unix_timestamp;
+    order.placed_slot = clock.slot;
     order.bump = ctx.bumps.order;
     order.locked_amount = lock_amount;  // Track locked USDC
     
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, Order, OrderBook, OrderType, MIN_RESTING_ESCROW};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct PostOrder<'info> {
+    #[account(
+        constraint = market.key() == order.market @ DegenError::InvalidMarketParams,
+        constraint = market.is_trading_open(Clock::get()?.unix_timestamp) @ DegenError::MarketNotOpen
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [OrderBook::SEED, market.key().as_ref()],
+        bump = order_book.load()?.bump
+    )]
+    pub order_book: AccountLoader<'info, OrderBook>,
+    
+    /// The order to rest on the book
+    #[account(
+        mut,
+        has_one = owner @ DegenError::Unauthorized
+    )]
+    pub order: Account<'info, Order>,
+    
+    pub owner: Signer<'info>,
+}
+
+/// Rest a limit order on the market's order book.
+/// 
+/// Time priority starts when the order is posted, so place and post in the same
+/// transaction. The order must hold at least `MIN_RESTING_ESCROW` for its unfilled size. From then on the order is filled by `crank_match` only, and
+/// cancelling it takes the order book account.
+pub fn post_order(ctx: Context<PostOrder>) -> Result<()> {
+    let clock = Clock::get()?;
+    let order = &mut ctx.accounts.order;
+    
+    require!(
+        order.is_active()
+            && order.order_type == OrderType::Limit
+            && !order.is_expired(clock.unix_timestamp, clock.slot)
+            && !order.on_book,
+        DegenError::OrderNotRestable
+    );
+    
+    // Book slots are limited, so dust orders cannot rest
+    require!(order.outstanding_escrow() >= MIN_RESTING_ESCROW, DegenError::RestingOrderTooSmall);
+    
+    // Resting orders never cross their owner's own orders, so the crank never self-trades
+    let mut order_book = ctx.accounts.order_book.load_mut()?;
+    require!(
+        !order_book.crosses_own_order(&order.owner, order.side, order.outcome, order.price),
+        DegenError::SelfTrade
+    );
+    let seq = order_book
+        .insert(order.key(), order.owner, order.side, order.outcome, order.price)
+        .ok_or(DegenError::OrderBookFull)?;
+    order.on_book = true;
+    
+    msg!(
+        "Order posted to book: order={} {:?} {:?} {}@{} (seq={})",
+        order.key(),
+        order.side,
+        order.outcome,
+        order.remaining_size(),
+        order.price,
+        seq
+    );
+    
+    Ok(())
+}
@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer, CloseAccount};
+use anchor_spl::associated_token::AssociatedToken;
+use crate::state::{Market, MarketStatus, PriceSamples, ResolverConfig, ResolutionProposal};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct ProposeResolution<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    /// The market's USDC vault - pins the bond mint to the market's collateral mint
+    #[account(
+        associated_token::mint = usdc_mint,
+        associated_token::authority = market,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [ResolverConfig::SEED],
+        bump = resolver_config.bump
+    )]
+    pub resolver_config: Account<'info, ResolverConfig>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = ResolutionProposal::SIZE,
+        seeds = [ResolutionProposal::SEED, market.key().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, ResolutionProposal>,
+    
+    /// Bond escrow (ATA owned by the proposal PDA)
+    #[account(
+        init,
+        payer = proposer,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = proposal,
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+    
+    /// Recorded price samples (required for TWAP/median markets)
+    #[account(
+        seeds = [PriceSamples::SEED, market.key().as_ref()],
+        bump = price_samples.bump
+    )]
+    pub price_samples: Option<Account<'info, PriceSamples>>,
+    
+    /// USDC mint
+    pub usdc_mint: Account<'info, Mint>,
+    
+    /// Proposer's USDC token account (pays the bond)
+    #[account(
+        mut,
+        constraint = proposer_usdc.owner == proposer.key() @ DegenError::Unauthorized,
+        constraint = proposer_usdc.mint == usdc_mint.key() @ DegenError::InvalidMarketParams
+    )]
+    pub proposer_usdc: Account<'info, TokenAccount>,
+    
+    /// Anyone can propose - the bond is at stake if the outcome is wrong
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Propose a market's final price with a USDC bond (optimistic resolution).
+/// The outcome is derived on-chain from the final price vs strike (NO wins ties).
+/// The market moves to `Proposed` and can be challenged until the dispute window ends;
+/// it only becomes `Resolved` (and payable) via `finalize_resolution` or `settle_dispute`.
+/// On TWAP/median markets the proposal is the price computed from recorded samples;
+/// `final_price` is ignored.
+pub fn propose_resolution(ctx: Context<ProposeResolution>, final_price: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let resolver_config = &ctx.accounts.resolver_config;
+    let clock = Clock::get()?;
+    
+    require!(resolver_config.optimistic_resolution_enabled(), DegenError::OptimisticResolutionDisabled);
+    
+    // Ensure market has expired
+    require!(clock.unix_timestamp >= market.expiry_at, DegenError::MarketNotExpired);
+    
+    // Ensure not already resolved or proposed
+    require!(market.status == MarketStatus::Open || market.status == MarketStatus::Closed, DegenError::MarketAlreadyResolved);
+    
+    // TWAP/median markets resolve at the sampled price, never a reported one
+    let final_price = market
+        .resolution_price(final_price, ctx.accounts.price_samples.as_deref())
+        .ok_or(DegenError::InsufficientPriceSamples)?;
+    
+    // Validate final price
+    require!(final_price > 0, DegenError::InvalidOraclePrice);
+    
+    require!(market.can_resolve_at(final_price, clock.unix_timestamp), DegenError::BarrierTouchPending);
+    
+    // Post the bond
+    let bond = resolver_config.proposal_bond;
+    require!(ctx.accounts.proposer_usdc.amount >= bond, DegenError::InsufficientBalance);
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.proposer_usdc.to_account_info(),
+            to: ctx.accounts.bond_vault.to_account_info(),
+            authority: ctx.accounts.proposer.to_account_info(),
+        },
+    );
+    token::transfer(cpi_ctx, bond)?;
+    
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.market = market.key();
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.final_price = final_price;
+    proposal.outcome = market.outcome_for_price(final_price);
+    proposal.bond = bond;
+    proposal.bond_vault = ctx.accounts.bond_vault.key();
+    proposal.proposed_at = clock.unix_timestamp;
+    proposal.challenge_deadline = clock.unix_timestamp
+        .checked_add(resolver_config.dispute_window)
+        .ok_or(DegenError::MathOverflow)?;
+    proposal.challenger = Pubkey::default();
+    proposal.challenged_at = 0;
+    proposal.bump = ctx.bumps.proposal;
+    
+    market.status = MarketStatus::Proposed;
+    
+    msg!(
+        "Market #{} outcome proposed: {:?} (final={} strike={}) bond={} challenge until {}",
+        market.id, proposal.outcome, final_price, market.strike_price, bond, proposal.challenge_deadline
+    );
+    
+    Ok(())
+}
+
+/// Pay out everything in the bond vault to `recipient`, then close the vault
+/// (rent goes to `rent_recipient`). Signed by the proposal PDA.
+pub(crate) fn release_bond<'info>(
+    proposal: &Account<'info, ResolutionProposal>,
+    bond_vault: &Account<'info, TokenAccount>,
+    recipient: AccountInfo<'info>,
+    rent_recipient: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<u64> {
+    let bump = proposal.bump;
+    let seeds = &[
+        ResolutionProposal::SEED,
+        proposal.market.as_ref(),
+        &[bump]
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    let amount = bond_vault.amount;
+    if amount > 0 {
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_program.clone(),
+            Transfer {
+                from: bond_vault.to_account_info(),
+                to: recipient,
+                authority: proposal.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount)?;
+    }
+    
+    let cpi_ctx_close = CpiContext::new_with_signer(
+        token_program,
+        CloseAccount {
+            account: bond_vault.to_account_info(),
+            destination: rent_recipient,
+            authority: proposal.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::close_account(cpi_ctx_close)?;
+    
+    Ok(amount)
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use crate::state::{AssetConfig, Market, MarketStatus, PriceSample, PriceSamples, ResolutionMethod};
+use crate::oracle::read_pyth_price;
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct RecordPriceSample<'info> {
+    pub market: Account<'info, Market>,
+
+    /// Registered asset (oracle feed)
+    #[account(
+        seeds = [AssetConfig::SEED, market.asset_bytes()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    /// Sample slots for this market (created by the first sample)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PriceSamples::SIZE,
+        seeds = [PriceSamples::SEED, market.key().as_ref()],
+        bump
+    )]
+    pub price_samples: Account<'info, PriceSamples>,
+
+    /// Pyth `PriceUpdateV2` account for the market's asset
+    /// CHECK: Owner, discriminator and feed ID are validated in `read_pyth_price`
+    pub price_update: UncheckedAccount<'info>,
+
+    /// Anyone can record samples - prices are verified on-chain
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Record an oracle price sample for a TWAP/median market.
+/// The sample must be published inside the resolution window (the last
+/// `resolution_window` seconds before expiry). The window is split into 16 slots of
+/// `resolution_window / 15` seconds and each slot holds the first sample recorded for it,
+/// so samples can be recorded in any order and a late print cannot block earlier slots.
+pub fn record_price_sample(ctx: Context<RecordPriceSample>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let price_samples = &mut ctx.accounts.price_samples;
+
+    require!(market.resolution_method != ResolutionMethod::Spot, DegenError::InvalidResolutionMethod);
+    require!(
+        market.status == MarketStatus::Open || market.status == MarketStatus::Closed,
+        DegenError::MarketAlreadyResolved
+    );
+
+    // Read the oracle price for this market's asset
+    let oracle_price = read_pyth_price(&ctx.accounts.price_update.to_account_info(), &ctx.accounts.asset_config.oracle_feed)?;
+
+    require!(
+        oracle_price.publish_time >= market.sampling_starts_at() &&
+        oracle_price.publish_time <= market.expiry_at,
+        DegenError::SampleOutsideWindow
+    );
+
+    // Initialize samples account on first sample
+    if price_samples.market == Pubkey::default() {
+        price_samples.market = market.key();
+        price_samples.bump = ctx.bumps.price_samples;
+    }
+
+    let slot = PriceSamples::slot_for(
+        oracle_price.publish_time,
+        market.sampling_starts_at(),
+        PriceSamples::slot_length(market.resolution_window),
+    ).ok_or(DegenError::SampleOutsideWindow)?;
+    let sample = PriceSample {
+        price: oracle_price.price,
+        publish_time: oracle_price.publish_time,
+    };
+    require!(price_samples.record(slot, sample), DegenError::DuplicatePriceSample);
+
+    msg!(
+        "Market #{} price sample: {} at {} in slot {} ({} samples)",
+        market.id, oracle_price.price, oracle_price.publish_time, slot, price_samples.count
+    );
+
+    Ok(())
+}
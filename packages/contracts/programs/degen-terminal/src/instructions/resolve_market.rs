@@ -1,12 +1,10 @@
 use anchor_lang::prelude::*;
-use crate::state::{Market, MarketStatus, MarketOutcome};
+use crate::state::{GlobalState, AssetConfig, Market, MarketStatus, ResolverConfig, PriceSamples, is_authorized_resolver};
 use crate::errors::DegenError;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct ResolveMarketArgs {
-    /// Outcome determined by relayer (0 = Yes, 1 = No)
-    pub outcome: u8,
-    /// Final price at resolution (8 decimals)
+    /// Final price at resolution (8 decimals) - ignored for TWAP/median markets
     pub final_price: u64,
 }
 
@@ -15,46 +13,98 @@ pub struct ResolveMarket<'info> {
     #[account(mut)]
     pub market: Account<'info, Market>,
     
-    /// Authority (keeper/relayer) that triggers resolution
+    #[account(
+        seeds = [GlobalState::SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// Registered asset (disabled assets can still resolve existing markets)
+    #[account(
+        seeds = [AssetConfig::SEED, market.asset_bytes()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+    
+    /// Registered resolver set - always passed so a configured quorum cannot be skipped.
+    /// May be uninitialized if no resolvers have been registered yet.
+    /// CHECK: PDA address is enforced by seeds; contents are loaded with `ResolverConfig::load_optional`
+    #[account(
+        seeds = [ResolverConfig::SEED],
+        bump
+    )]
+    pub resolver_config: UncheckedAccount<'info>,
+    
+    /// Recorded price samples (required for TWAP/median markets)
+    #[account(
+        seeds = [PriceSamples::SEED, market.key().as_ref()],
+        bump = price_samples.bump
+    )]
+    pub price_samples: Option<Account<'info, PriceSamples>>,
+    
+    /// Authority (admin or registered resolver) that triggers resolution
     pub authority: Signer<'info>,
 }
 
-/// Resolve a market with outcome determined by the relayer.
-/// The relayer fetches the real price from Binance/Coinbase and determines the winner.
+/// Resolve a market with the final price reported by the relayer.
+/// The relayer fetches the real price from Binance/Coinbase; the outcome is
+/// derived on-chain from the final price vs the strike (NO wins ties).
 /// Can only be called after the market has expired.
+/// TWAP/median markets compute the final price from recorded samples instead.
+/// If a resolver quorum is configured, only the admin can resolve directly; this is
+/// also how disputed markets are settled.
 pub fn resolve_market(ctx: Context<ResolveMarket>, args: ResolveMarketArgs) -> Result<()> {
     let market = &mut ctx.accounts.market;
     let clock = Clock::get()?;
+    let authority = ctx.accounts.authority.key();
+    let is_admin = authority == ctx.accounts.global_state.admin;
+    let resolver_config = ResolverConfig::load_optional(&ctx.accounts.resolver_config)?;
+    
+    // Only authorized resolvers can report the final price
+    require!(
+        is_authorized_resolver(&authority, &ctx.accounts.global_state, resolver_config.as_ref()),
+        DegenError::Unauthorized
+    );
+    
+    // With an M-of-N quorum, a single resolver cannot decide the outcome
+    require!(
+        is_admin || !resolver_config.as_ref().is_some_and(|config| config.requires_quorum()),
+        DegenError::QuorumRequired
+    );
     
     // Ensure market has expired
     require!(clock.unix_timestamp >= market.expiry_at, DegenError::MarketNotExpired);
     
-    // Ensure not already resolved
-    require!(market.status == MarketStatus::Open || market.status == MarketStatus::Closed, DegenError::MarketAlreadyResolved);
+    // Ensure not already resolved (only the admin can resolve a disputed market)
+    require!(market.status != MarketStatus::Disputed || is_admin, DegenError::MarketDisputed);
+    require!(
+        market.status == MarketStatus::Open ||
+        market.status == MarketStatus::Closed ||
+        market.status == MarketStatus::Disputed,
+        DegenError::MarketAlreadyResolved
+    );
+    
+    // Spot markets use the reported price; TWAP/median markets use recorded samples
+    let final_price = market
+        .resolution_price(args.final_price, ctx.accounts.price_samples.as_deref())
+        .ok_or(DegenError::InsufficientPriceSamples)?;
+    
+    // Validate final price
+    require!(final_price > 0, DegenError::InvalidOraclePrice);
     
-    // Validate outcome
-    require!(args.outcome <= 1, DegenError::InvalidMarketParams);
-    require!(args.final_price > 0, DegenError::InvalidOraclePrice);
+    // A barrier market only resolves NO once a touch proof up to expiry could have landed
+    require!(market.can_resolve_at(final_price, clock.unix_timestamp), DegenError::BarrierTouchPending);
     
-    // Update market with relayer-provided data
-    market.final_price = args.final_price;
+    // Update market with final price and derived outcome
+    market.final_price = final_price;
     market.resolved_at = clock.unix_timestamp;
     market.status = MarketStatus::Resolved;
+    market.outcome = market.outcome_for_price(final_price);
     
-    // Set outcome from relayer
-    if args.outcome == 0 {
-        market.outcome = MarketOutcome::Yes;
-        msg!(
-            "Market #{} resolved: YES wins (final={} > strike={})",
-            market.id, args.final_price, market.strike_price
-        );
-    } else {
-        market.outcome = MarketOutcome::No;
-        msg!(
-            "Market #{} resolved: NO wins (final={} <= strike={})",
-            market.id, args.final_price, market.strike_price
-        );
-    }
+    msg!(
+        "Market #{} resolved: {:?} wins (final={} strike={} method={:?})",
+        market.id, market.outcome, final_price, market.strike_price, market.resolution_method
+    );
     
     Ok(())
 }
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::{AssetConfig, Market, MarketStatus, MarketType, MarketOutcome};
+use crate::oracle::read_pyth_price;
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct ResolveMarketOnTouch<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    /// Registered asset (oracle feed)
+    #[account(
+        seeds = [AssetConfig::SEED, market.asset_bytes()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    /// Pyth `PriceUpdateV2` account proving the touch
+    /// CHECK: Owner, discriminator and feed ID are validated in `read_pyth_price`
+    pub price_update: UncheckedAccount<'info>,
+
+    /// Anyone can submit a touch - the price is verified on-chain
+    pub authority: Signer<'info>,
+}
+
+/// Resolve a one-touch market YES from a Pyth price that touched the barrier.
+///
+/// Can be called before expiry. The price must be published inside the trading
+/// window (`start_at` to `expiry_at`) and be at or beyond the barrier. Trading stops
+/// immediately; resting orders can then be refunded with `cancel_order`, or
+/// force-cancelled by the relayer via `cancel_order_by_relayer`.
+pub fn resolve_market_on_touch(ctx: Context<ResolveMarketOnTouch>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    require!(market.market_type == MarketType::Barrier, DegenError::InvalidResolutionMethod);
+
+    // Ensure not already resolved
+    require!(market.status == MarketStatus::Open || market.status == MarketStatus::Closed, DegenError::MarketAlreadyResolved);
+
+    let oracle_price = read_pyth_price(&ctx.accounts.price_update.to_account_info(), &ctx.accounts.asset_config.oracle_feed)?;
+
+    // Only touches during the market's own window count
+    require!(
+        oracle_price.publish_time >= market.start_at &&
+        oracle_price.publish_time <= market.expiry_at,
+        DegenError::StaleOraclePrice
+    );
+    require!(market.touches_barrier(oracle_price.price), DegenError::BarrierNotTouched);
+
+    market.final_price = oracle_price.price;
+    market.resolved_at = clock.unix_timestamp;
+    market.status = MarketStatus::Resolved;
+    market.outcome = MarketOutcome::Yes;
+
+    msg!(
+        "Market #{} touched barrier: YES wins (price={} barrier={} direction={:?} publish_time={})",
+        market.id, oracle_price.price, market.barrier_price, market.barrier_direction, oracle_price.publish_time
+    );
+
+    Ok(())
+}
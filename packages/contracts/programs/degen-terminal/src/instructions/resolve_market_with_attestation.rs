@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use crate::state::{Market, MarketStatus, ResolutionMethod, ResolverConfig};
+use crate::attestation::{verify_price_attestation, AttestationKind, PriceAttestation};
+use crate::oracle::published_at;
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct ResolveMarketWithAttestation<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    /// Holds the whitelisted price publishers
+    #[account(
+        seeds = [ResolverConfig::SEED],
+        bump = resolver_config.bump
+    )]
+    pub resolver_config: Account<'info, ResolverConfig>,
+
+    /// Instructions sysvar (to read the preceding Ed25519 verification)
+    /// CHECK: Address is checked against the sysvar ID
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// Anyone can submit a signed attestation - the price is verified on-chain
+    pub authority: Signer<'info>,
+}
+
+/// Resolve a market from a final price signed by a whitelisted price publisher.
+///
+/// The transaction must include an Ed25519 precompile instruction, immediately
+/// before this one, over the borsh-encoded `PriceAttestation` for this market.
+/// The attested price must be published at expiry (within `ORACLE_PUBLISH_TOLERANCE`).
+pub fn resolve_market_with_attestation(
+    ctx: Context<ResolveMarketWithAttestation>,
+    final_price: u64,
+    publish_time: i64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    // Ensure market has expired
+    require!(clock.unix_timestamp >= market.expiry_at, DegenError::MarketNotExpired);
+
+    // Ensure not already resolved
+    require!(market.status == MarketStatus::Open || market.status == MarketStatus::Closed, DegenError::MarketAlreadyResolved);
+
+    // Single-print resolution only applies to spot markets
+    require!(market.resolution_method == ResolutionMethod::Spot, DegenError::InvalidResolutionMethod);
+
+    // Validate final price
+    require!(final_price > 0, DegenError::InvalidOraclePrice);
+
+    // Price must be published at expiry, not before it and not long after
+    require!(
+        published_at(publish_time, market.expiry_at),
+        DegenError::StaleOraclePrice
+    );
+
+    // Verify the publisher's signature over this exact attestation
+    let attestation = PriceAttestation {
+        market: market.key(),
+        kind: AttestationKind::Final,
+        price: final_price,
+        publish_time,
+    };
+    let signer = verify_price_attestation(&ctx.accounts.instructions_sysvar, &attestation)?;
+    require!(ctx.accounts.resolver_config.is_price_publisher(&signer), DegenError::SignerMismatch);
+
+    require!(market.can_resolve_at(final_price, clock.unix_timestamp), DegenError::BarrierTouchPending);
+
+    market.final_price = final_price;
+    market.resolved_at = clock.unix_timestamp;
+    market.status = MarketStatus::Resolved;
+    market.outcome = market.outcome_for_price(final_price);
+
+    msg!(
+        "Market #{} resolved by attestation: {:?} wins (final={} strike={} publisher={})",
+        market.id, market.outcome, final_price, market.strike_price, signer
+    );
+
+    Ok(())
+}
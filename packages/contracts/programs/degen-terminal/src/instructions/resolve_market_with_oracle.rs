@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::state::{AssetConfig, Market, MarketStatus, ResolutionMethod};
+use crate::oracle::{read_pyth_price, published_at};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct ResolveMarketWithOracle<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    /// Registered asset (oracle feed)
+    #[account(
+        seeds = [AssetConfig::SEED, market.asset_bytes()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+
+    /// Pyth `PriceUpdateV2` account for the market's asset
+    /// CHECK: Owner, discriminator and feed ID are validated in `read_pyth_price`
+    pub price_update: UncheckedAccount<'info>,
+
+    /// Anyone can trigger oracle resolution - the price is verified on-chain
+    pub authority: Signer<'info>,
+}
+
+/// Resolve a market from a Pyth pull-oracle price update.
+///
+/// The price update must be for the market's asset feed and published at expiry
+/// (within `ORACLE_PUBLISH_TOLERANCE` seconds after `expiry_at`). The final price
+/// and outcome are derived on-chain, so no trust in the caller is required.
+pub fn resolve_market_with_oracle(ctx: Context<ResolveMarketWithOracle>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    // Ensure market has expired
+    require!(clock.unix_timestamp >= market.expiry_at, DegenError::MarketNotExpired);
+
+    // Ensure not already resolved
+    require!(market.status == MarketStatus::Open || market.status == MarketStatus::Closed, DegenError::MarketAlreadyResolved);
+
+    // Single-print resolution only applies to spot markets
+    require!(market.resolution_method == ResolutionMethod::Spot, DegenError::InvalidResolutionMethod);
+
+    // Read the oracle price for this market's asset
+    let oracle_price = read_pyth_price(&ctx.accounts.price_update.to_account_info(), &ctx.accounts.asset_config.oracle_feed)?;
+
+    // Price must be published at expiry, not before it and not long after
+    require!(
+        published_at(oracle_price.publish_time, market.expiry_at),
+        DegenError::StaleOraclePrice
+    );
+
+    require!(market.can_resolve_at(oracle_price.price, clock.unix_timestamp), DegenError::BarrierTouchPending);
+
+    market.final_price = oracle_price.price;
+    market.resolved_at = clock.unix_timestamp;
+    market.status = MarketStatus::Resolved;
+    market.outcome = market.outcome_for_price(oracle_price.price);
+
+    msg!(
+        "Market #{} resolved by oracle: {:?} wins (final={} strike={} publish_time={})",
+        market.id, market.outcome, oracle_price.price, market.strike_price, oracle_price.publish_time
+    );
+
+    Ok(())
+}
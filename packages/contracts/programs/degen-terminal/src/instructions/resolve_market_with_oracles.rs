@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use crate::state::{Market, MarketStatus, OracleConfig, OracleSource, OracleSourceKind, ResolutionMethod};
+use crate::oracle::{aggregate_prices, read_pyth_price, read_switchboard_price, published_at};
+use crate::attestation::{verify_price_attestation, AttestationKind, PriceAttestation};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct ResolveMarketWithOracles<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    /// Price sources for the market's asset
+    #[account(
+        seeds = [OracleConfig::SEED, market.asset_bytes()],
+        bump = oracle_config.bump
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
+
+    /// Pyth `PriceUpdateV2` account (if a Pyth source is configured)
+    /// CHECK: Owner, discriminator and feed ID are validated in `read_pyth_price`
+    pub pyth_price_update: Option<UncheckedAccount<'info>>,
+
+    /// Switchboard V2 aggregator (if a Switchboard source is configured)
+    /// CHECK: Key, owner and discriminator are validated in `read_switchboard_price`
+    pub switchboard_aggregator: Option<UncheckedAccount<'info>>,
+
+    /// Instructions sysvar (if a signed publisher source is configured)
+    /// CHECK: Address is checked against the sysvar ID
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// Anyone can trigger resolution - every price is verified on-chain
+    pub authority: Signer<'info>,
+}
+
+/// Resolve a market from the median of its asset's configured price sources.
+///
+/// Every configured source's account must be passed and must be valid (owner, feed,
+/// key and publisher signature), otherwise resolution fails. Sources not published at
+/// expiry (within `ORACLE_PUBLISH_TOLERANCE`) are skipped, and sources too far from the
+/// others are rejected. At least `min_sources` must remain.
+///
+/// `publisher_price` / `publisher_publish_time` are the signed publisher's attested
+/// final price (ignored if no publisher source is configured).
+pub fn resolve_market_with_oracles(
+    ctx: Context<ResolveMarketWithOracles>,
+    publisher_price: u64,
+    publisher_publish_time: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let market_key = ctx.accounts.market.key();
+    let market = &ctx.accounts.market;
+
+    // Ensure market has expired
+    require!(clock.unix_timestamp >= market.expiry_at, DegenError::MarketNotExpired);
+
+    // Ensure not already resolved
+    require!(market.status == MarketStatus::Open || market.status == MarketStatus::Closed, DegenError::MarketAlreadyResolved);
+
+    // Oracle aggregation resolves on the price at expiry (spot markets only)
+    require!(market.resolution_method == ResolutionMethod::Spot, DegenError::InvalidResolutionMethod);
+
+    let mut prices: Vec<u64> = Vec::with_capacity(ctx.accounts.oracle_config.num_sources as usize);
+    for source in ctx.accounts.oracle_config.configured() {
+        let (price, publish_time) = read_source(ctx.accounts, source, market_key, publisher_price, publisher_publish_time)?;
+        if published_at(publish_time, market.expiry_at) {
+            prices.push(price);
+        } else {
+            msg!("Skipping {:?} source: not published at expiry", source.kind);
+        }
+    }
+
+    let oracle_config = &ctx.accounts.oracle_config;
+    let final_price = aggregate_prices(&prices, oracle_config.max_deviation_bps, oracle_config.min_sources)?;
+
+    let market = &mut ctx.accounts.market;
+    require!(market.can_resolve_at(final_price, clock.unix_timestamp), DegenError::BarrierTouchPending);
+
+    market.final_price = final_price;
+    market.resolved_at = clock.unix_timestamp;
+    market.status = MarketStatus::Resolved;
+    market.outcome = market.outcome_for_price(final_price);
+
+    msg!(
+        "Market #{} resolved by {} oracle sources: {:?} wins (final={} strike={})",
+        market.id, prices.len(), market.outcome, final_price, market.strike_price
+    );
+
+    Ok(())
+}
+
+/// Read one configured source, returning `(price, publish_time)`. Omitting a configured
+/// source's account or passing an invalid one is an error, so a caller cannot drop a
+/// source it dislikes.
+fn read_source(
+    accounts: &ResolveMarketWithOracles,
+    source: &OracleSource,
+    market: Pubkey,
+    publisher_price: u64,
+    publisher_publish_time: i64,
+) -> Result<(u64, i64)> {
+    match source.kind {
+        OracleSourceKind::Pyth => {
+            let account = accounts.pyth_price_update.as_ref().ok_or(DegenError::MissingOracleSource)?;
+            let price = read_pyth_price(&account.to_account_info(), &source.key.to_bytes())?;
+            Ok((price.price, price.publish_time))
+        }
+        OracleSourceKind::Switchboard => {
+            let account = accounts.switchboard_aggregator.as_ref().ok_or(DegenError::MissingOracleSource)?;
+            let price = read_switchboard_price(&account.to_account_info(), &source.key)?;
+            Ok((price.price, price.publish_time))
+        }
+        OracleSourceKind::SignedPublisher => {
+            let sysvar = accounts.instructions_sysvar.as_ref().ok_or(DegenError::MissingOracleSource)?;
+            let attestation = PriceAttestation {
+                market,
+                kind: AttestationKind::Final,
+                price: publisher_price,
+                publish_time: publisher_publish_time,
+            };
+            require!(publisher_price > 0, DegenError::InvalidOraclePrice);
+            let signer = verify_price_attestation(&sysvar.to_account_info(), &attestation)?;
+            require_keys_eq!(signer, source.key, DegenError::SignerMismatch);
+            Ok((publisher_price, publisher_publish_time))
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, MarketSeries, MarketStatus, MarketOutcome};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct RollSeries<'info> {
+    #[account(
+        mut,
+        seeds = [MarketSeries::SEED, next_market.asset_bytes(), next_market.timeframe_bytes()],
+        bump = market_series.bump
+    )]
+    pub market_series: Account<'info, MarketSeries>,
+    
+    /// Current head of the series - must be resolved
+    #[account(
+        address = market_series.last_market @ DegenError::InvalidSeriesMarket
+    )]
+    pub previous_market: Account<'info, Market>,
+    
+    /// Pending market for the next slot
+    #[account(mut)]
+    pub next_market: Account<'info, Market>,
+    
+    /// Anyone can roll the series - the strike comes from the resolved market
+    pub authority: Signer<'info>,
+}
+
+/// Activate the next market in a series with the previous market's final price as strike.
+/// 
+/// The next market must be the pending base market whose trading window starts at the
+/// previous market's expiry, so strikes are continuous and cannot be picked by the relayer.
+pub fn roll_series(ctx: Context<RollSeries>) -> Result<()> {
+    let market_series = &mut ctx.accounts.market_series;
+    let previous_market = &ctx.accounts.previous_market;
+    let next_market = &mut ctx.accounts.next_market;
+    let clock = Clock::get()?;
+    
+    require!(market_series.enabled, DegenError::SeriesDisabled);
+    
+    // Previous market must have a final price
+    require!(previous_market.status == MarketStatus::Resolved || previous_market.status == MarketStatus::Settled, DegenError::MarketNotResolved);
+    require!(previous_market.outcome != MarketOutcome::Invalid && previous_market.final_price > 0, DegenError::InvalidSeriesMarket);
+    
+    // Next market must be the following slot's base market
+    require!(next_market.status == MarketStatus::Pending, DegenError::MarketNotPending);
+    require!(next_market.strike_index == 0, DegenError::InvalidSeriesMarket);
+    require!(next_market.start_at == previous_market.expiry_at, DegenError::InvalidSeriesMarket);
+    require!(clock.unix_timestamp < next_market.expiry_at, DegenError::MarketExpired);
+    
+    next_market.strike_price = previous_market.final_price;
+    next_market.status = MarketStatus::Open;
+    
+    market_series.last_market = next_market.key();
+    market_series.last_expiry = next_market.expiry_at;
+    market_series.num_rolls = market_series.num_rolls.checked_add(1).ok_or(DegenError::MathOverflow)?;
+    
+    msg!(
+        "Market #{} rolled from #{}: {} {} strike={}",
+        next_market.id,
+        previous_market.id,
+        next_market.asset_str(),
+        next_market.timeframe_str(),
+        next_market.strike_price
+    );
+    
+    Ok(())
+}
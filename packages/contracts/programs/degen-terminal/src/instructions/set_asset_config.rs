@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalState, AssetConfig, str_to_bytes, MAX_ASSET_LEN, MAX_TIMEFRAME_LEN, MAX_ASSET_TIMEFRAMES, STRIKE_PRICE_DECIMALS};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+#[instruction(asset: String)]
+pub struct SetAssetConfig<'info> {
+    #[account(
+        seeds = [GlobalState::SEED],
+        bump = global_state.bump,
+        has_one = admin @ DegenError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = AssetConfig::SIZE,
+        seeds = [AssetConfig::SEED, asset.as_bytes()],
+        bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Register or update an asset. Disabling an asset stops new markets from being
+/// created or activated; existing markets can still be resolved and settled.
+/// With `oracle_activation`, new markets are created pending and can only be activated
+/// from the oracle or a publisher attestation.
+pub fn set_asset_config(
+    ctx: Context<SetAssetConfig>,
+    asset: String,
+    oracle_feed: [u8; 32],
+    strike_decimals: u8,
+    enabled: bool,
+    oracle_activation: bool,
+    timeframes: Vec<String>,
+) -> Result<()> {
+    require!(!asset.is_empty() && asset.len() <= MAX_ASSET_LEN, DegenError::InvalidAsset);
+    require!(oracle_feed != [0u8; 32], DegenError::InvalidAssetConfig);
+    require!(strike_decimals <= STRIKE_PRICE_DECIMALS, DegenError::InvalidAssetConfig);
+    require!(timeframes.len() <= MAX_ASSET_TIMEFRAMES, DegenError::InvalidAssetConfig);
+    
+    let asset_config = &mut ctx.accounts.asset_config;
+    asset_config.timeframes = [[0u8; MAX_TIMEFRAME_LEN]; MAX_ASSET_TIMEFRAMES];
+    for (i, timeframe) in timeframes.iter().enumerate() {
+        require!(!timeframe.is_empty() && timeframe.len() <= MAX_TIMEFRAME_LEN, DegenError::InvalidTimeframe);
+        require!(!timeframes[..i].contains(timeframe), DegenError::InvalidAssetConfig);
+        asset_config.timeframes[i] = str_to_bytes::<MAX_TIMEFRAME_LEN>(timeframe);
+    }
+    
+    asset_config.asset = str_to_bytes::<MAX_ASSET_LEN>(&asset);
+    asset_config.oracle_feed = oracle_feed;
+    asset_config.strike_decimals = strike_decimals;
+    asset_config.enabled = enabled;
+    asset_config.oracle_activation = oracle_activation;
+    asset_config.num_timeframes = timeframes.len() as u8;
+    asset_config.bump = ctx.bumps.asset_config;
+    
+    msg!(
+        "Asset {} configured: enabled={} oracle_activation={} strike_decimals={} timeframes={:?}",
+        asset, enabled, oracle_activation, strike_decimals, timeframes
+    );
+    
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalState, Market, MarketStatus, MarketType, BarrierDirection};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct SetBarrier<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [GlobalState::SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// Market authority or admin
+    pub authority: Signer<'info>,
+}
+
+/// Turn a pending market into a one-touch market on `barrier_price`.
+/// 
+/// YES wins as soon as an oracle price at or beyond the barrier (in `direction`)
+/// is proven with `resolve_market_on_touch`, which can happen before expiry.
+/// Otherwise the market resolves at expiry as usual, where the final price
+/// itself can still touch. A barrier of 0 reverts the market to binary.
+/// Only allowed before activation.
+pub fn set_barrier(ctx: Context<SetBarrier>, barrier_price: u64, direction: BarrierDirection) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let authority = ctx.accounts.authority.key();
+    
+    require!(
+        authority == market.authority || authority == ctx.accounts.global_state.admin,
+        DegenError::Unauthorized
+    );
+    require!(market.status == MarketStatus::Pending, DegenError::MarketNotPending);
+    
+    market.clear_market_type();
+    if barrier_price > 0 {
+        market.market_type = MarketType::Barrier;
+        market.barrier_price = barrier_price;
+        market.barrier_direction = direction;
+    }
+    
+    msg!(
+        "Market #{} type: {:?} (barrier={} direction={:?})",
+        market.id, market.market_type, barrier_price, direction
+    );
+    
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalState, ResolverConfig};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct SetDisputeConfig<'info> {
+    #[account(
+        seeds = [GlobalState::SEED],
+        bump = global_state.bump,
+        has_one = admin @ DegenError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [ResolverConfig::SEED],
+        bump = resolver_config.bump
+    )]
+    pub resolver_config: Account<'info, ResolverConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+/// Configure optimistic resolution.
+/// Proposers and challengers each post `proposal_bond` USDC, and a proposed outcome
+/// can be challenged for `dispute_window` seconds. A zero bond disables proposals.
+pub fn set_dispute_config(
+    ctx: Context<SetDisputeConfig>,
+    proposal_bond: u64,
+    dispute_window: i64,
+) -> Result<()> {
+    require!(dispute_window >= 0, DegenError::InvalidDisputeConfig);
+    require!(proposal_bond == 0 || dispute_window > 0, DegenError::InvalidDisputeConfig);
+    
+    let resolver_config = &mut ctx.accounts.resolver_config;
+    resolver_config.proposal_bond = proposal_bond;
+    resolver_config.dispute_window = dispute_window;
+    
+    msg!("Dispute config updated: bond={} window={}s", proposal_bond, dispute_window);
+    
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalState, Market, MarketStatus, MarketType, MAX_BUCKETS};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct SetMarketBuckets<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [GlobalState::SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// Market authority or admin
+    pub authority: Signer<'info>,
+}
+
+/// Turn a pending market into a categorical market with `bounds.len() + 1` buckets.
+/// 
+/// Bucket `i` wins if `bounds[i-1] < final_price <= bounds[i]` (the first and last
+/// buckets are open-ended). Each trade mints a $1 complete set: the buyer gets the
+/// traded bucket, the seller one share of every other bucket. Passing no bounds
+/// reverts the market to binary. Only allowed before activation, so no orders
+/// or positions exist for the old outcomes.
+pub fn set_market_buckets(ctx: Context<SetMarketBuckets>, bounds: Vec<u64>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let authority = ctx.accounts.authority.key();
+    
+    require!(
+        authority == market.authority || authority == ctx.accounts.global_state.admin,
+        DegenError::Unauthorized
+    );
+    require!(market.status == MarketStatus::Pending, DegenError::MarketNotPending);
+    
+    require!(bounds.len() < MAX_BUCKETS, DegenError::InvalidBucketBounds);
+    require!(!bounds.contains(&0), DegenError::InvalidBucketBounds);
+    require!(bounds.windows(2).all(|pair| pair[0] < pair[1]), DegenError::InvalidBucketBounds);
+    
+    market.clear_market_type();
+    if !bounds.is_empty() {
+        market.market_type = MarketType::Categorical;
+        market.num_buckets = bounds.len() as u8 + 1;
+        market.bucket_bounds[..bounds.len()].copy_from_slice(&bounds);
+    }
+    
+    msg!(
+        "Market #{} type: {:?} (buckets={} bounds={:?})",
+        market.id, market.market_type, market.num_buckets, bounds
+    );
+    
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalState, Market, MarketStatus, FeeSchedule, FeeTier, MAX_FEE_BPS, MAX_FEE_TIERS};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct SetMarketFees<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [GlobalState::SEED],
+        bump = global_state.bump,
+        has_one = admin @ DegenError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub admin: Signer<'info>,
+}
+
+/// Override the taker fee of a market, optionally stepping it as expiry approaches.
+/// 
+/// `tiers` are ordered from furthest to closest to expiry; each applies once expiry is
+/// at most `seconds_before_expiry` away. Passing no fee clears the override so the
+/// market falls back to the global taker fee. Allowed until the market is resolved.
+pub fn set_market_fees(ctx: Context<SetMarketFees>, taker_fee_bps: Option<u16>, tiers: Vec<FeeTier>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    
+    require!(
+        market.status == MarketStatus::Pending || market.status == MarketStatus::Open,
+        DegenError::MarketAlreadyResolved
+    );
+    
+    let Some(taker_fee_bps) = taker_fee_bps else {
+        require!(tiers.is_empty(), DegenError::InvalidFeeConfig);
+        market.fee_schedule = FeeSchedule::default();
+        msg!("Market #{} fees: global (taker_fee={}bps)", market.id, ctx.accounts.global_state.taker_fee_bps);
+        return Ok(());
+    };
+    
+    require!(taker_fee_bps <= MAX_FEE_BPS, DegenError::InvalidFeeConfig);
+    require!(tiers.len() <= MAX_FEE_TIERS, DegenError::InvalidFeeConfig);
+    require!(
+        tiers.iter().all(|tier| tier.seconds_before_expiry > 0 && tier.taker_fee_bps <= MAX_FEE_BPS),
+        DegenError::InvalidFeeConfig
+    );
+    require!(
+        tiers.windows(2).all(|pair| pair[0].seconds_before_expiry > pair[1].seconds_before_expiry),
+        DegenError::InvalidFeeConfig
+    );
+    
+    let mut fee_schedule = FeeSchedule {
+        enabled: true,
+        taker_fee_bps,
+        num_tiers: tiers.len() as u8,
+        ..FeeSchedule::default()
+    };
+    fee_schedule.tiers[..tiers.len()].copy_from_slice(&tiers);
+    market.fee_schedule = fee_schedule;
+    
+    msg!(
+        "Market #{} fees: taker_fee={}bps tiers={:?}",
+        market.id, taker_fee_bps, tiers
+    );
+    
+    Ok(())
+}
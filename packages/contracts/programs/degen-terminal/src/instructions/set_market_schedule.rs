@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::state::{GlobalState, MarketSchedule, TimeframeConfig, str_to_bytes, MAX_ASSET_LEN, MAX_TIMEFRAME_LEN};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+#[instruction(asset: String, timeframe: String)]
+pub struct SetMarketSchedule<'info> {
+    #[account(
+        seeds = [GlobalState::SEED],
+        bump = global_state.bump,
+        has_one = admin @ DegenError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// Registered timeframe (the cadence and lead time are checked against it)
+    #[account(
+        seeds = [TimeframeConfig::SEED, timeframe.as_bytes()],
+        bump = timeframe_config.bump
+    )]
+    pub timeframe_config: Account<'info, TimeframeConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = MarketSchedule::SIZE,
+        seeds = [MarketSchedule::SEED, asset.as_bytes(), timeframe.as_bytes()],
+        bump
+    )]
+    pub market_schedule: Account<'info, MarketSchedule>,
+    
+    /// Authority of created markets (the relayer that activates them)
+    /// CHECK: Only stored as the authority of created markets
+    pub market_authority: UncheckedAccount<'info>,
+    
+    /// USDC mint for created market vaults
+    pub usdc_mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Create or update the recurring schedule for an asset and timeframe.
+/// 
+/// The crank tip and the rent of created markets are paid from lamports held by
+/// the schedule account above its rent-exempt minimum, so fund it by transferring
+/// SOL to the schedule PDA. Updating keeps the next expiry, so markets that were
+/// already created are never created again.
+pub fn set_market_schedule(
+    ctx: Context<SetMarketSchedule>,
+    asset: String,
+    timeframe: String,
+    cadence: i64,
+    lead_time: i64,
+    tip_lamports: u64,
+    enabled: bool,
+) -> Result<()> {
+    require!(!asset.is_empty() && asset.len() <= MAX_ASSET_LEN, DegenError::InvalidAsset);
+    require!(timeframe.len() <= MAX_TIMEFRAME_LEN, DegenError::InvalidTimeframe);
+    
+    // Expiries stay on the timeframe grid and are created within its lead time bounds
+    let timeframe_config = &ctx.accounts.timeframe_config;
+    require!(cadence > 0 && cadence % timeframe_config.duration == 0, DegenError::InvalidScheduleConfig);
+    require!(
+        lead_time >= timeframe_config.min_lead_time && lead_time <= timeframe_config.max_lead_time,
+        DegenError::InvalidScheduleConfig
+    );
+    
+    let market_schedule = &mut ctx.accounts.market_schedule;
+    market_schedule.asset = str_to_bytes::<MAX_ASSET_LEN>(&asset);
+    market_schedule.timeframe = str_to_bytes::<MAX_TIMEFRAME_LEN>(&timeframe);
+    market_schedule.enabled = enabled;
+    market_schedule.cadence = cadence;
+    market_schedule.lead_time = lead_time;
+    market_schedule.market_authority = ctx.accounts.market_authority.key();
+    market_schedule.usdc_mint = ctx.accounts.usdc_mint.key();
+    market_schedule.tip_lamports = tip_lamports;
+    market_schedule.bump = ctx.bumps.market_schedule;
+    
+    msg!(
+        "Market schedule {} {} {}: cadence={}s lead_time={}s tip={} lamports",
+        asset,
+        timeframe,
+        if enabled { "enabled" } else { "disabled" },
+        cadence,
+        lead_time,
+        tip_lamports
+    );
+    
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalState, Market, MarketSeries, MarketStatus};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct SetMarketSeries<'info> {
+    #[account(
+        seeds = [GlobalState::SEED],
+        bump = global_state.bump,
+        has_one = admin @ DegenError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// Market the series continues from (its final price becomes the next strike)
+    pub head_market: Account<'info, Market>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = MarketSeries::SIZE,
+        seeds = [MarketSeries::SEED, head_market.asset_bytes(), head_market.timeframe_bytes()],
+        bump
+    )]
+    pub market_series: Account<'info, MarketSeries>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Start, re-seed or disable the rolling series for the head market's asset and timeframe.
+/// 
+/// While enabled, base markets (strike index 0) can only be activated by `roll_series`,
+/// which sets each strike from the previous market's final price. Re-seeding from a
+/// new head recovers a series after a missed slot.
+pub fn set_market_series(ctx: Context<SetMarketSeries>, enabled: bool) -> Result<()> {
+    let head_market = &ctx.accounts.head_market;
+    
+    // The head must be a base market that already has a strike
+    require!(head_market.strike_index == 0, DegenError::InvalidSeriesMarket);
+    require!(head_market.status != MarketStatus::Pending, DegenError::InvalidSeriesMarket);
+    
+    let market_series = &mut ctx.accounts.market_series;
+    market_series.asset = head_market.asset;
+    market_series.timeframe = head_market.timeframe;
+    market_series.enabled = enabled;
+    market_series.last_market = head_market.key();
+    market_series.last_expiry = head_market.expiry_at;
+    market_series.bump = ctx.bumps.market_series;
+    
+    msg!(
+        "Market series {} {} {}: head=#{} expiry={}",
+        head_market.asset_str(),
+        head_market.timeframe_str(),
+        if enabled { "enabled" } else { "disabled" },
+        head_market.id,
+        head_market.expiry_at
+    );
+    
+    Ok(())
+}
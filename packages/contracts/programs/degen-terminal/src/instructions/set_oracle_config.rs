@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalState, AssetConfig, OracleConfig, OracleSource, str_to_bytes, MAX_ASSET_LEN, MAX_ORACLE_SOURCES};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+#[instruction(asset: String)]
+pub struct SetOracleConfig<'info> {
+    #[account(
+        seeds = [GlobalState::SEED],
+        bump = global_state.bump,
+        has_one = admin @ DegenError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// Asset must be registered
+    #[account(
+        seeds = [AssetConfig::SEED, asset.as_bytes()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = OracleConfig::SIZE,
+        seeds = [OracleConfig::SEED, asset.as_bytes()],
+        bump
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Configure the price sources for an asset (max 3, at most one of each kind).
+/// `resolve_market_with_oracles` takes the median of the valid sources, dropping any
+/// more than `max_deviation_bps` from the others, and needs `min_sources` to agree.
+pub fn set_oracle_config(
+    ctx: Context<SetOracleConfig>,
+    asset: String,
+    sources: Vec<OracleSource>,
+    min_sources: u8,
+    max_deviation_bps: u16,
+) -> Result<()> {
+    require!(asset.len() <= MAX_ASSET_LEN, DegenError::InvalidAsset);
+    
+    require!(!sources.is_empty() && sources.len() <= MAX_ORACLE_SOURCES, DegenError::InvalidOracleConfig);
+    require!(min_sources > 0 && min_sources as usize <= sources.len(), DegenError::InvalidOracleConfig);
+    require!(max_deviation_bps > 0 && max_deviation_bps <= 10_000, DegenError::InvalidOracleConfig);
+    
+    // One source per kind - each kind is read from its own account
+    for (i, source) in sources.iter().enumerate() {
+        require!(source.key != Pubkey::default(), DegenError::InvalidOracleConfig);
+        require!(
+            !sources[..i].iter().any(|other| other.kind == source.kind),
+            DegenError::InvalidOracleConfig
+        );
+    }
+    
+    let oracle_config = &mut ctx.accounts.oracle_config;
+    oracle_config.asset = str_to_bytes::<MAX_ASSET_LEN>(&asset);
+    oracle_config.sources = [OracleSource::default(); MAX_ORACLE_SOURCES];
+    oracle_config.sources[..sources.len()].copy_from_slice(&sources);
+    oracle_config.num_sources = sources.len() as u8;
+    oracle_config.min_sources = min_sources;
+    oracle_config.max_deviation_bps = max_deviation_bps;
+    oracle_config.bump = ctx.bumps.oracle_config;
+    
+    msg!(
+        "Oracle config for {}: {} sources, min {} agreeing within {} bps",
+        asset, oracle_config.num_sources, min_sources, max_deviation_bps
+    );
+    
+    Ok(())
+}
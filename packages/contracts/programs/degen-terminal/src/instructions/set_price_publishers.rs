@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalState, ResolverConfig, MAX_PRICE_PUBLISHERS};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct SetPricePublishers<'info> {
+    #[account(
+        seeds = [GlobalState::SEED],
+        bump = global_state.bump,
+        has_one = admin @ DegenError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = ResolverConfig::SIZE,
+        seeds = [ResolverConfig::SEED],
+        bump
+    )]
+    pub resolver_config: Account<'info, ResolverConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Replace the whitelisted price publishers.
+/// Publishers sign Ed25519 price attestations accepted by
+/// `activate_market_with_attestation` and `resolve_market_with_attestation`.
+/// Passing an empty list disables attested prices.
+pub fn set_price_publishers(ctx: Context<SetPricePublishers>, publishers: Vec<Pubkey>) -> Result<()> {
+    require!(publishers.len() <= MAX_PRICE_PUBLISHERS, DegenError::TooManyPricePublishers);
+    
+    for (i, publisher) in publishers.iter().enumerate() {
+        require!(*publisher != Pubkey::default(), DegenError::InvalidPricePublisher);
+        require!(!publishers[..i].contains(publisher), DegenError::InvalidPricePublisher);
+    }
+    
+    let resolver_config = &mut ctx.accounts.resolver_config;
+    resolver_config.price_publishers = [Pubkey::default(); MAX_PRICE_PUBLISHERS];
+    resolver_config.price_publishers[..publishers.len()].copy_from_slice(&publishers);
+    resolver_config.num_price_publishers = publishers.len() as u8;
+    resolver_config.bump = ctx.bumps.resolver_config;
+    
+    msg!("Price publishers updated: {} publishers", resolver_config.num_price_publishers);
+    
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalState, Market, MarketStatus, ResolutionMethod, MAX_PRICE_SAMPLES};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct SetResolutionMethod<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [GlobalState::SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// Market authority or admin
+    pub authority: Signer<'info>,
+}
+
+/// Set how a market's final price is determined.
+/// 
+/// `Spot` resolves on a single price at expiry. `Twap` averages the samples recorded
+/// with `record_price_sample` over the last `window` seconds, and `Median` takes the
+/// median of the last `samples` of them. Must be set before sampling starts.
+pub fn set_resolution_method(
+    ctx: Context<SetResolutionMethod>,
+    method: ResolutionMethod,
+    window: i64,
+    samples: u8,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let authority = ctx.accounts.authority.key();
+    let clock = Clock::get()?;
+    
+    require!(
+        authority == market.authority || authority == ctx.accounts.global_state.admin,
+        DegenError::Unauthorized
+    );
+    require!(
+        market.status == MarketStatus::Pending || market.status == MarketStatus::Open,
+        DegenError::MarketNotOpen
+    );
+    
+    match method {
+        ResolutionMethod::Spot => {
+            require!(window == 0 && samples == 0, DegenError::InvalidResolutionMethod);
+        }
+        ResolutionMethod::Twap | ResolutionMethod::Median => {
+            // Window must fit inside the trading window
+            require!(window > 0 && window <= market.expiry_at - market.start_at, DegenError::InvalidResolutionMethod);
+            require!(samples > 0 && samples as usize <= MAX_PRICE_SAMPLES, DegenError::InvalidResolutionMethod);
+        }
+    }
+    
+    // Can't change the rules once sampling has started (old or new window)
+    require!(
+        clock.unix_timestamp < market.sampling_starts_at() &&
+        clock.unix_timestamp < market.expiry_at - window,
+        DegenError::InvalidResolutionMethod
+    );
+    
+    market.resolution_method = method;
+    market.resolution_window = window;
+    market.resolution_samples = samples;
+    
+    msg!(
+        "Market #{} resolution method: {:?} (window={}s samples={})",
+        market.id, method, window, samples
+    );
+    
+    Ok(())
+}
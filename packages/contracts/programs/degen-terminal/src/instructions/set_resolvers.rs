@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalState, ResolverConfig, MAX_RESOLVERS};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct SetResolvers<'info> {
+    #[account(
+        seeds = [GlobalState::SEED],
+        bump = global_state.bump,
+        has_one = admin @ DegenError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = ResolverConfig::SIZE,
+        seeds = [ResolverConfig::SEED],
+        bump
+    )]
+    pub resolver_config: Account<'info, ResolverConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Replace the registered resolver set and quorum threshold.
+/// Resolvers (plus the admin) can resolve and settle markets.
+/// With a threshold above 1, markets must be resolved by that many matching
+/// `submit_resolution` votes (the admin can still resolve disputed markets).
+/// Passing an empty list removes all registered resolvers.
+pub fn set_resolvers(ctx: Context<SetResolvers>, resolvers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    require!(resolvers.len() <= MAX_RESOLVERS, DegenError::TooManyResolvers);
+    require!(threshold as usize <= resolvers.len(), DegenError::InvalidThreshold);
+    require!(threshold > 0 || resolvers.is_empty(), DegenError::InvalidThreshold);
+    
+    // Reject duplicates so each resolver is counted once
+    for (i, resolver) in resolvers.iter().enumerate() {
+        require!(*resolver != Pubkey::default(), DegenError::InvalidResolver);
+        require!(!resolvers[..i].contains(resolver), DegenError::InvalidResolver);
+    }
+    
+    let resolver_config = &mut ctx.accounts.resolver_config;
+    resolver_config.resolvers = [Pubkey::default(); MAX_RESOLVERS];
+    resolver_config.resolvers[..resolvers.len()].copy_from_slice(&resolvers);
+    resolver_config.num_resolvers = resolvers.len() as u8;
+    resolver_config.threshold = threshold;
+    resolver_config.bump = ctx.bumps.resolver_config;
+    
+    msg!(
+        "Resolver set updated: {} resolvers, threshold {}",
+        resolver_config.num_resolvers, resolver_config.threshold
+    );
+    
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalState, Market, MarketStatus, MarketType};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct SetScalarBounds<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [GlobalState::SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// Market authority or admin
+    pub authority: Signer<'info>,
+}
+
+/// Turn a pending market into a scalar market between `lower_bound` and `upper_bound`.
+/// 
+/// YES shares are LONG and pay `(final - lower) / (upper - lower)` per contract,
+/// clamped to [$0, $1]; NO shares are SHORT and pay the complement, so every
+/// minted pair is still worth exactly $1. Both bounds at 0 revert the market to
+/// binary. Only allowed before activation.
+pub fn set_scalar_bounds(ctx: Context<SetScalarBounds>, lower_bound: u64, upper_bound: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let authority = ctx.accounts.authority.key();
+    
+    require!(
+        authority == market.authority || authority == ctx.accounts.global_state.admin,
+        DegenError::Unauthorized
+    );
+    require!(market.status == MarketStatus::Pending, DegenError::MarketNotPending);
+    
+    let revert_to_binary = lower_bound == 0 && upper_bound == 0;
+    require!(revert_to_binary || upper_bound > lower_bound, DegenError::InvalidScalarBounds);
+    
+    market.clear_market_type();
+    if !revert_to_binary {
+        market.market_type = MarketType::Scalar;
+        market.lower_bound = lower_bound;
+        market.upper_bound = upper_bound;
+    }
+    
+    msg!(
+        "Market #{} type: {:?} (lower={} upper={})",
+        market.id, market.market_type, lower_bound, upper_bound
+    );
+    
+    Ok(())
+}
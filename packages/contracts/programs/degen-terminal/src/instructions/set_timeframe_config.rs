@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use crate::state::{GlobalState, TimeframeConfig, TradingParams, str_to_bytes, MAX_TIMEFRAME_LEN};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+#[instruction(timeframe: String)]
+pub struct SetTimeframeConfig<'info> {
+    #[account(
+        seeds = [GlobalState::SEED],
+        bump = global_state.bump,
+        has_one = admin @ DegenError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = TimeframeConfig::SIZE,
+        seeds = [TimeframeConfig::SEED, timeframe.as_bytes()],
+        bump
+    )]
+    pub timeframe_config: Account<'info, TimeframeConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Register or update a timeframe. Existing markets keep the close buffer and
+/// trading params they were created with; the new rules apply to markets created
+/// afterwards. Passing no trading params keeps the current ones (or the protocol
+/// defaults for a new timeframe).
+pub fn set_timeframe_config(
+    ctx: Context<SetTimeframeConfig>,
+    timeframe: String,
+    duration: i64,
+    close_buffer: i64,
+    min_lead_time: i64,
+    max_lead_time: i64,
+    trading_params: Option<TradingParams>,
+) -> Result<()> {
+    require!(!timeframe.is_empty() && timeframe.len() <= MAX_TIMEFRAME_LEN, DegenError::InvalidTimeframe);
+    require!(duration > 0, DegenError::InvalidTimeframeConfig);
+    require!(close_buffer >= 0 && close_buffer < duration, DegenError::InvalidTimeframeConfig);
+    // Markets must still be tradable when created
+    require!(min_lead_time > close_buffer, DegenError::InvalidTimeframeConfig);
+    require!(max_lead_time >= min_lead_time, DegenError::InvalidTimeframeConfig);
+    if let Some(trading_params) = trading_params {
+        require!(trading_params.is_valid(), DegenError::InvalidTimeframeConfig);
+    }
+    
+    let timeframe_config = &mut ctx.accounts.timeframe_config;
+    timeframe_config.timeframe = str_to_bytes::<MAX_TIMEFRAME_LEN>(&timeframe);
+    timeframe_config.duration = duration;
+    timeframe_config.close_buffer = close_buffer;
+    timeframe_config.min_lead_time = min_lead_time;
+    timeframe_config.max_lead_time = max_lead_time;
+    if let Some(trading_params) = trading_params {
+        timeframe_config.trading_params = trading_params;
+    } else if !timeframe_config.trading_params.is_valid() {
+        // Newly created (zeroed) config
+        timeframe_config.trading_params = TradingParams::default();
+    }
+    timeframe_config.bump = ctx.bumps.timeframe_config;
+    
+    msg!(
+        "Timeframe {} configured: duration={}s close_buffer={}s lead_time={}..{}s trading_params={:?}",
+        timeframe, duration, close_buffer, min_lead_time, max_lead_time, timeframe_config.trading_params
+    );
+    
+    Ok(())
+}
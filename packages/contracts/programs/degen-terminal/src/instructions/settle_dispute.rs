@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::{GlobalState, Market, MarketStatus, PriceSamples, ResolverConfig, ResolutionProposal};
+use crate::instructions::propose_resolution::release_bond;
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct SettleDispute<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [GlobalState::SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        seeds = [ResolverConfig::SEED],
+        bump = resolver_config.bump
+    )]
+    pub resolver_config: Account<'info, ResolverConfig>,
+    
+    #[account(
+        mut,
+        seeds = [ResolutionProposal::SEED, market.key().as_ref()],
+        bump = proposal.bump,
+        has_one = market @ DegenError::InvalidMarketParams,
+        has_one = proposer @ DegenError::InvalidMarketParams,
+        close = proposer
+    )]
+    pub proposal: Account<'info, ResolutionProposal>,
+    
+    /// Bond escrow holding both bonds
+    #[account(
+        mut,
+        address = proposal.bond_vault @ DegenError::InvalidMarketParams
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+    
+    /// Proposer (receives account rent back)
+    /// CHECK: Validated via has_one on proposal
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+    
+    /// Proposer's USDC token account (receives both bonds if the proposal stands)
+    #[account(
+        mut,
+        constraint = proposer_usdc.owner == proposal.proposer @ DegenError::Unauthorized,
+        constraint = proposer_usdc.mint == bond_vault.mint @ DegenError::InvalidMarketParams
+    )]
+    pub proposer_usdc: Account<'info, TokenAccount>,
+    
+    /// Challenger's USDC token account (receives both bonds if the challenge succeeds)
+    #[account(
+        mut,
+        constraint = challenger_usdc.owner == proposal.challenger @ DegenError::Unauthorized,
+        constraint = challenger_usdc.mint == bond_vault.mint @ DegenError::InvalidMarketParams
+    )]
+    pub challenger_usdc: Account<'info, TokenAccount>,
+    
+    /// Recorded price samples (required for TWAP/median markets)
+    #[account(
+        seeds = [PriceSamples::SEED, market.key().as_ref()],
+        bump = price_samples.bump
+    )]
+    pub price_samples: Option<Account<'info, PriceSamples>>,
+    
+    /// Admin or registered resolver deciding the dispute (not the proposer or challenger)
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Settle a challenged proposal with the correct final price.
+/// The outcome is derived on-chain; if the proposed price settles the market the same
+/// way (same outcome, and same LONG value on scalar markets) the proposer wins,
+/// otherwise the challenger does. The loser's bond is slashed to the winner
+/// and the market is resolved.
+/// On TWAP/median markets the dispute is settled at the price computed from recorded
+/// samples; `final_price` is ignored.
+pub fn settle_dispute(ctx: Context<SettleDispute>, final_price: u64) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let clock = Clock::get()?;
+    
+    // Only the admin or the resolver set can decide disputes
+    require!(
+        authority == ctx.accounts.global_state.admin ||
+        ctx.accounts.resolver_config.is_resolver(&authority),
+        DegenError::Unauthorized
+    );
+    
+    let market = &mut ctx.accounts.market;
+    let proposal = &ctx.accounts.proposal;
+    
+    require!(market.status == MarketStatus::Proposed, DegenError::MarketNotProposed);
+    require!(proposal.is_challenged(), DegenError::ProposalNotChallenged);
+    
+    // Neither party can award itself the other side's bond
+    require!(
+        authority != proposal.proposer && authority != proposal.challenger,
+        DegenError::DisputeParticipant
+    );
+    
+    // TWAP/median markets resolve at the sampled price, never a reported one
+    let final_price = market
+        .resolution_price(final_price, ctx.accounts.price_samples.as_deref())
+        .ok_or(DegenError::InsufficientPriceSamples)?;
+    
+    // Validate final price
+    require!(final_price > 0, DegenError::InvalidOraclePrice);
+    
+    require!(market.can_resolve_at(final_price, clock.unix_timestamp), DegenError::BarrierTouchPending);
+    
+    let outcome = market.outcome_for_price(final_price);
+    let proposer_wins = market.resolves_same(final_price, proposal.final_price);
+    let winner_usdc = if proposer_wins {
+        ctx.accounts.proposer_usdc.to_account_info()
+    } else {
+        ctx.accounts.challenger_usdc.to_account_info()
+    };
+    
+    // Winner takes both bonds
+    let payout = release_bond(
+        proposal,
+        &ctx.accounts.bond_vault,
+        winner_usdc,
+        ctx.accounts.proposer.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    )?;
+    
+    market.final_price = final_price;
+    market.resolved_at = clock.unix_timestamp;
+    market.status = MarketStatus::Resolved;
+    market.outcome = outcome;
+    
+    msg!(
+        "Market #{} dispute settled: {:?} wins (final={} strike={}), {} wins bonds ({})",
+        market.id, outcome, final_price, market.strike_price,
+        if proposer_wins { "proposer" } else { "challenger" }, payout
+    );
+    
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::{Market, UserPosition, MarketStatus, MarketOutcome};
+use crate::state::{GlobalState, Market, UserPosition, ResolverConfig, MarketStatus, MarketOutcome, is_authorized_resolver};
 use crate::errors::DegenError;
 
 #[derive(Accounts)]
@@ -9,6 +9,21 @@ pub struct SettlePositions<'info> {
     #[account(mut)]
     pub market: Account<'info, Market>,
     
+    #[account(
+        seeds = [GlobalState::SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// Registered resolver set (the admin is always allowed).
+    /// May be uninitialized if no resolvers have been registered yet.
+    /// CHECK: PDA address is enforced by seeds; contents are loaded with `ResolverConfig::load_optional`
+    #[account(
+        seeds = [ResolverConfig::SEED],
+        bump
+    )]
+    pub resolver_config: UncheckedAccount<'info>,
+    
     /// Market's USDC vault - validated to be the market's ATA
     #[account(
         mut,
@@ -33,21 +48,36 @@ pub struct SettlePositions<'info> {
     )]
     pub user_usdc: Account<'info, TokenAccount>,
     
-    /// Authority (keeper) that triggers settlement
+    /// Authority (admin or registered resolver) that triggers settlement
     pub authority: Signer<'info>,
     
     pub token_program: Program<'info, Token>,
 }
 
 /// Settle a user's position after market resolution.
-/// Pays out $1.00 per winning contract.
+/// Pays out $1.00 per winning contract (YES, NO, or the winning bucket of a
+/// categorical market). Scalar markets pay both LONG (YES) and SHORT (NO)
+/// shares pro-rata by where the final price landed between the bounds.
+/// On a voided (`Invalid`) market the position's cost basis is refunded
+/// instead, scaled pro-rata if the vault is short.
 /// Shares are stored in 6 decimals (1_000_000 = 1 contract = $1 payout)
 /// So shares directly equal payout in microUSDC.
 pub fn settle_positions(ctx: Context<SettlePositions>) -> Result<()> {
     let market = &mut ctx.accounts.market;
     let position = &mut ctx.accounts.position;
+    let resolver_config = ResolverConfig::load_optional(&ctx.accounts.resolver_config)?;
+    
+    // Only authorized resolvers can trigger payouts
+    require!(
+        is_authorized_resolver(
+            &ctx.accounts.authority.key(),
+            &ctx.accounts.global_state,
+            resolver_config.as_ref(),
+        ),
+        DegenError::Unauthorized
+    );
     
-    // Ensure market is resolved
+    // Ensure market is resolved (proposed outcomes must be finalized first)
     require!(market.status == MarketStatus::Resolved, DegenError::MarketNotResolved);
     require!(market.outcome != MarketOutcome::Pending, DegenError::MarketNotResolved);
     
@@ -60,6 +90,14 @@ pub fn settle_positions(ctx: Context<SettlePositions>) -> Result<()> {
     let payout = match market.outcome {
         MarketOutcome::Yes => position.yes_shares,
         MarketOutcome::No => position.no_shares,
+        MarketOutcome::Bucket(bucket) => position.bucket_shares[bucket as usize],
+        MarketOutcome::Scalar => market
+            .scalar_payout(position.yes_shares, position.no_shares)
+            .ok_or(DegenError::MathOverflow)?,
+        MarketOutcome::Invalid => position
+            .total_cost()
+            .and_then(|cost_basis| market.refund_for_cost_basis(cost_basis))
+            .ok_or(DegenError::MathOverflow)?,
         MarketOutcome::Pending => {
             return Err(DegenError::MarketNotResolved.into());
         }
@@ -78,6 +116,7 @@ pub fn settle_positions(ctx: Context<SettlePositions>) -> Result<()> {
             market.asset_bytes(),
             market.timeframe_bytes(),
             expiry_bytes.as_ref(),
+            &[market.strike_index],
             &[bump]
         ];
         let signer_seeds = &[&seeds[..]];
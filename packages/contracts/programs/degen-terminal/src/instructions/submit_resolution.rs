@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, MarketStatus, PriceSamples, ResolverConfig, ResolutionVote, ResolutionVotes, MAX_RESOLVERS};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct SubmitResolution<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        seeds = [ResolverConfig::SEED],
+        bump = resolver_config.bump,
+        constraint = resolver_config.is_resolver(&resolver.key()) @ DegenError::Unauthorized
+    )]
+    pub resolver_config: Account<'info, ResolverConfig>,
+
+    /// Votes collected for this market (created by the first voter)
+    #[account(
+        init_if_needed,
+        payer = resolver,
+        space = ResolutionVotes::SIZE,
+        seeds = [ResolutionVotes::SEED, market.key().as_ref()],
+        bump
+    )]
+    pub resolution_votes: Account<'info, ResolutionVotes>,
+
+    /// Recorded price samples (required for TWAP/median markets)
+    #[account(
+        seeds = [PriceSamples::SEED, market.key().as_ref()],
+        bump = price_samples.bump
+    )]
+    pub price_samples: Option<Account<'info, PriceSamples>>,
+
+    /// Registered resolver submitting the vote
+    #[account(mut)]
+    pub resolver: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Record a resolver's vote on the final price of an expired market.
+/// The outcome is derived on-chain from the final price vs strike (NO wins ties).
+/// Once `threshold` registered resolvers agree on the same final price and outcome
+/// the market is resolved. Any conflicting vote moves the market to `Disputed`,
+/// after which only the admin can resolve it via `resolve_market`.
+/// On TWAP/median markets the vote is for the price computed from recorded samples;
+/// `final_price` is ignored.
+pub fn submit_resolution(ctx: Context<SubmitResolution>, final_price: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let resolver_config = &ctx.accounts.resolver_config;
+    let votes = &mut ctx.accounts.resolution_votes;
+    let resolver = ctx.accounts.resolver.key();
+    let clock = Clock::get()?;
+
+    // Ensure market has expired
+    require!(clock.unix_timestamp >= market.expiry_at, DegenError::MarketNotExpired);
+
+    // Votes are only accepted while the outcome is undecided
+    require!(market.status != MarketStatus::Disputed, DegenError::MarketDisputed);
+    require!(market.status == MarketStatus::Open || market.status == MarketStatus::Closed, DegenError::MarketAlreadyResolved);
+
+    // TWAP/median markets resolve at the sampled price, never a reported one
+    let final_price = market
+        .resolution_price(final_price, ctx.accounts.price_samples.as_deref())
+        .ok_or(DegenError::InsufficientPriceSamples)?;
+    
+    // Validate final price
+    require!(final_price > 0, DegenError::InvalidOraclePrice);
+
+    require!(market.can_resolve_at(final_price, clock.unix_timestamp), DegenError::BarrierTouchPending);
+
+    // Initialize votes account on first vote
+    if votes.market == Pubkey::default() {
+        votes.market = market.key();
+        votes.bump = ctx.bumps.resolution_votes;
+    }
+
+    // Votes from resolvers removed since they voted no longer count, and must not
+    // hold slots that current resolvers need
+    votes.prune_removed(resolver_config);
+
+    require!(!votes.has_voted(&resolver), DegenError::AlreadyVoted);
+    require!((votes.num_votes as usize) < MAX_RESOLVERS, DegenError::TooManyResolvers);
+
+    let vote = ResolutionVote {
+        resolver,
+        outcome: market.outcome_for_price(final_price),
+        final_price,
+    };
+    let index = votes.num_votes as usize;
+    votes.votes[index] = vote;
+    votes.num_votes += 1;
+
+    msg!(
+        "Resolution vote on market #{}: {:?} (final={}) by {}",
+        market.id, vote.outcome, final_price, resolver
+    );
+
+    // Any disagreement between current resolvers escalates to the admin
+    if votes.submitted().iter().any(|other| !other.matches(&vote)) {
+        market.status = MarketStatus::Disputed;
+        msg!("Market #{} disputed: conflicting resolution votes", market.id);
+        return Ok(());
+    }
+
+    // All remaining votes agree
+    let matching = votes.num_votes as usize;
+
+    if matching >= resolver_config.threshold.max(1) as usize {
+        market.final_price = final_price;
+        market.resolved_at = clock.unix_timestamp;
+        market.status = MarketStatus::Resolved;
+        market.outcome = vote.outcome;
+
+        msg!(
+            "Market #{} resolved by quorum ({}/{}): {:?} wins (final={} strike={})",
+            market.id, matching, resolver_config.threshold, market.outcome, final_price, market.strike_price
+        );
+    }
+
+    Ok(())
+}
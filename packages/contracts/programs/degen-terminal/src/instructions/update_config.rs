@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::GlobalState;
+use crate::state::{GlobalState, MAX_FEE_BPS};
 use crate::errors::DegenError;
 
 #[derive(Accounts)]
@@ -26,12 +26,12 @@ pub fn update_config(
     let global_state = &mut ctx.accounts.global_state;
     
     if let Some(fee) = maker_fee_bps {
-        require!(fee <= 500, DegenError::InvalidFeeConfig);
+        require!(fee <= MAX_FEE_BPS, DegenError::InvalidFeeConfig);
         global_state.maker_fee_bps = fee;
     }
     
     if let Some(fee) = taker_fee_bps {
-        require!(fee <= 500, DegenError::InvalidFeeConfig);
+        require!(fee <= MAX_FEE_BPS, DegenError::InvalidFeeConfig);
         global_state.taker_fee_bps = fee;
     }
     
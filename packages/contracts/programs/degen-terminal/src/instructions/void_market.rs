@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::{GlobalState, Market, MarketStatus, MarketOutcome};
+use crate::errors::DegenError;
+
+#[derive(Accounts)]
+pub struct VoidMarket<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [GlobalState::SEED],
+        bump = global_state.bump,
+        has_one = admin @ DegenError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// Market's USDC vault - balance (less open-order escrow) is snapshotted for refunds
+    #[account(
+        constraint = vault.owner == market.key() @ DegenError::InvalidMarketParams
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    
+    pub admin: Signer<'info>,
+}
+
+/// Void an unresolvable market (e.g. oracle/feed outage).
+/// The market is resolved with `MarketOutcome::Invalid`; `settle_positions` then
+/// refunds each position's cost basis instead of paying winning shares.
+/// The vault balance, less escrow still owed to open orders, is snapshotted and split
+/// pro-rata by cost basis, so positions share any surplus or shortfall.
+pub fn void_market(ctx: Context<VoidMarket>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+    
+    // Proposed markets must go through settle_dispute/finalize so bonds are released
+    require!(
+        market.status == MarketStatus::Pending ||
+        market.status == MarketStatus::Open ||
+        market.status == MarketStatus::Closed ||
+        market.status == MarketStatus::Disputed,
+        DegenError::MarketAlreadyResolved
+    );
+    
+    // Open orders are still refunded their escrow on cancel, so it is not position collateral
+    market.refund_vault_balance = ctx.accounts.vault.amount.saturating_sub(market.open_order_escrow);
+    market.final_price = 0;
+    market.resolved_at = clock.unix_timestamp;
+    market.status = MarketStatus::Resolved;
+    market.outcome = MarketOutcome::Invalid;
+    
+    msg!(
+        "Market #{} voided: refunding cost basis {} from vault {}",
+        market.id, market.total_cost_basis, market.refund_vault_balance
+    );
+    
+    Ok(())
+}
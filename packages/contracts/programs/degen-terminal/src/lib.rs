@@ -5,8 +5,11 @@ declare_id!("5Kq43SR2HUNsyNZWaau1p8kQzAvW2UA2mAvempdchTrk");
 pub mod state;
 pub mod instructions;
 pub mod errors;
+pub mod oracle;
+pub mod attestation;
 
 use instructions::*;
+use state::{BarrierDirection, FeeTier, OracleSource, Outcome, ResolutionMethod, TradingParams};
 
 #[program]
 pub mod degen_terminal {
@@ -60,6 +63,129 @@ pub mod degen_terminal {
         instructions::transfer_admin(ctx)
     }
 
+    /// Replace the registered resolver set
+    /// 
+    /// Registered resolvers and the admin are the only keys
+    /// allowed to resolve and settle markets.
+    /// 
+    /// # Arguments
+    /// * `resolvers` - New resolver keys (max 10)
+    /// * `threshold` - Matching votes required to resolve a market (above 1 enables M-of-N quorum)
+    pub fn set_resolvers(ctx: Context<SetResolvers>, resolvers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        instructions::set_resolvers(ctx, resolvers, threshold)
+    }
+
+    /// Configure optimistic resolution bonds and dispute window
+    /// 
+    /// # Arguments
+    /// * `proposal_bond` - USDC bond posted by proposers and challengers (0 disables proposals)
+    /// * `dispute_window` - Seconds a proposed outcome can be challenged
+    pub fn set_dispute_config(
+        ctx: Context<SetDisputeConfig>,
+        proposal_bond: u64,
+        dispute_window: i64,
+    ) -> Result<()> {
+        instructions::set_dispute_config(ctx, proposal_bond, dispute_window)
+    }
+
+    /// Replace the whitelisted price publishers
+    /// 
+    /// Publishers sign Ed25519 price attestations used to activate and resolve markets.
+    /// 
+    /// # Arguments
+    /// * `publishers` - New publisher keys (max 5)
+    pub fn set_price_publishers(ctx: Context<SetPricePublishers>, publishers: Vec<Pubkey>) -> Result<()> {
+        instructions::set_price_publishers(ctx, publishers)
+    }
+
+    /// Configure the price sources for an asset (Pyth, Switchboard, signed publisher)
+    /// 
+    /// # Arguments
+    /// * `asset` - Registered asset symbol
+    /// * `sources` - Up to 3 sources, at most one of each kind
+    /// * `min_sources` - Valid, agreeing sources required to resolve
+    /// * `max_deviation_bps` - Max distance from the median before a source is rejected
+    pub fn set_oracle_config(
+        ctx: Context<SetOracleConfig>,
+        asset: String,
+        sources: Vec<OracleSource>,
+        min_sources: u8,
+        max_deviation_bps: u16,
+    ) -> Result<()> {
+        instructions::set_oracle_config(ctx, asset, sources, min_sources, max_deviation_bps)
+    }
+
+    /// Register or update an asset markets can be created for
+    /// 
+    /// # Arguments
+    /// * `asset` - Asset symbol (e.g. BTC)
+    /// * `oracle_feed` - Pyth price feed ID
+    /// * `strike_decimals` - Strike price precision (0-8 decimals)
+    /// * `enabled` - Whether new markets can be created and activated
+    /// * `oracle_activation` - Whether new markets must take their strike from the oracle
+    /// * `timeframes` - Timeframes markets can be created for (max 8)
+    pub fn set_asset_config(
+        ctx: Context<SetAssetConfig>,
+        asset: String,
+        oracle_feed: [u8; 32],
+        strike_decimals: u8,
+        enabled: bool,
+        oracle_activation: bool,
+        timeframes: Vec<String>,
+    ) -> Result<()> {
+        instructions::set_asset_config(ctx, asset, oracle_feed, strike_decimals, enabled, oracle_activation, timeframes)
+    }
+
+    /// Register or update a market timeframe and its trading rules
+    /// 
+    /// # Arguments
+    /// * `timeframe` - Timeframe label (e.g. 5m)
+    /// * `duration` - Trading window in seconds (expiries must be a multiple of it)
+    /// * `close_buffer` - Seconds before expiry when trading closes
+    /// * `min_lead_time` - Min seconds between market creation and expiry
+    /// * `max_lead_time` - Max seconds between market creation and expiry
+    /// * `trading_params` - Optional tick size, price/size bounds and position cap for new markets
+    pub fn set_timeframe_config(
+        ctx: Context<SetTimeframeConfig>,
+        timeframe: String,
+        duration: i64,
+        close_buffer: i64,
+        min_lead_time: i64,
+        max_lead_time: i64,
+        trading_params: Option<TradingParams>,
+    ) -> Result<()> {
+        instructions::set_timeframe_config(ctx, timeframe, duration, close_buffer, min_lead_time, max_lead_time, trading_params)
+    }
+
+    /// Start, re-seed or disable the rolling Up/Down series for a market's asset and timeframe
+    /// 
+    /// # Arguments
+    /// * `enabled` - Whether base markets must be activated by `roll_series`
+    pub fn set_market_series(ctx: Context<SetMarketSeries>, enabled: bool) -> Result<()> {
+        instructions::set_market_series(ctx, enabled)
+    }
+
+    /// Create or update the recurring market schedule for an asset and timeframe
+    /// 
+    /// # Arguments
+    /// * `asset` - Asset symbol (e.g., "BTC")
+    /// * `timeframe` - Timeframe label (e.g., "5m")
+    /// * `cadence` - Seconds between expiries (a multiple of the timeframe duration)
+    /// * `lead_time` - Markets are created once their expiry is at most this many seconds away
+    /// * `tip_lamports` - Lamports paid to the caller per created market, on top of its rent
+    /// * `enabled` - Whether the crank can create markets
+    pub fn set_market_schedule(
+        ctx: Context<SetMarketSchedule>,
+        asset: String,
+        timeframe: String,
+        cadence: i64,
+        lead_time: i64,
+        tip_lamports: u64,
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::set_market_schedule(ctx, asset, timeframe, cadence, lead_time, tip_lamports, enabled)
+    }
+
     // =========================================================================
     // Market Instructions
     // =========================================================================
@@ -68,20 +194,35 @@ pub mod degen_terminal {
     /// 
     /// If strike_price = 0, market is created with PENDING status.
     /// If strike_price > 0, market is created with OPEN status (direct activation).
+    /// Several strikes can share an expiry; they are linked by the expiry's `MarketGroup`.
+    /// Strikes after the first can only be added by the group's creator or the admin.
     /// 
     /// # Arguments
-    /// * `asset` - Asset symbol (BTC, ETH, SOL)
-    /// * `timeframe` - Market timeframe (5m, 15m, 1h, 4h, 24h)
+    /// * `asset` - Registered asset symbol (e.g. BTC)
+    /// * `timeframe` - Registered timeframe (e.g. 5m, 1h)
     /// * `strike_price` - Strike price with 8 decimals (0 for pending markets)
-    /// * `expiry_ts` - Unix timestamp when market expires
+    /// * `expiry_ts` - Unix timestamp when market expires (on the timeframe grid)
+    /// * `strike_index` - Next free slot in the expiry's strike ladder (0 for the first strike)
     pub fn initialize_market(
         ctx: Context<InitializeMarket>,
         asset: String,
         timeframe: String,
         strike_price: u64,
         expiry_ts: i64,
+        strike_index: u8,
     ) -> Result<()> {
-        instructions::initialize_market(ctx, asset, timeframe, strike_price, expiry_ts)
+        instructions::initialize_market(ctx, asset, timeframe, strike_price, expiry_ts, strike_index)
+    }
+
+    /// Create the next pending market of a schedule
+    /// 
+    /// Permissionless. The expiry must be the schedule's due slot, and the caller is paid
+    /// back the rent of the new accounts plus the schedule's tip.
+    /// 
+    /// # Arguments
+    /// * `expiry_ts` - Expiry of the due slot (used for the market PDA)
+    pub fn crank_create_market(ctx: Context<CrankCreateMarket>, expiry_ts: i64) -> Result<()> {
+        instructions::crank_create_market(ctx, expiry_ts)
     }
 
     /// Activate a pending market by setting the strike price
@@ -98,17 +239,234 @@ pub mod degen_terminal {
         instructions::activate_market(ctx, strike_price)
     }
 
-    /// Resolve a market with outcome from relayer
+    /// Activate a pending market with a signed strike price attestation
     /// 
-    /// Called by keeper after market expiry. The relayer determines the outcome
-    /// by comparing the final price (from Binance/Coinbase) to the strike price.
+    /// Permissionless. Requires an Ed25519 verification instruction, immediately before
+    /// this one, signed by a whitelisted price publisher.
     /// 
     /// # Arguments
-    /// * `args` - Resolution parameters (outcome, final_price)
+    /// * `strike_price` - Attested strike price (8 decimals)
+    /// * `publish_time` - When the price was observed (must be current)
+    pub fn activate_market_with_attestation(
+        ctx: Context<ActivateMarketWithAttestation>,
+        strike_price: u64,
+        publish_time: i64,
+    ) -> Result<()> {
+        instructions::activate_market_with_attestation(ctx, strike_price, publish_time)
+    }
+
+    /// Activate a pending market with the strike read from the asset's Pyth feed
+    /// 
+    /// Permissionless once the market's start time has arrived. The price update must
+    /// be published at `start_at`, so nobody can pick a favorable strike.
+    pub fn activate_market_with_oracle(ctx: Context<ActivateMarketWithOracle>) -> Result<()> {
+        instructions::activate_market_with_oracle(ctx)
+    }
+
+    /// Activate the next market in a rolling series
+    /// 
+    /// Permissionless. The strike is the previous market's final price, so strikes
+    /// are continuous across the series.
+    pub fn roll_series(ctx: Context<RollSeries>) -> Result<()> {
+        instructions::roll_series(ctx)
+    }
+
+    /// Resolve a market with the final price from relayer
+    /// 
+    /// Called by an authorized resolver after market expiry. The relayer reports the final price
+    /// (from Binance/Coinbase); the outcome is derived on-chain by comparing it to
+    /// the strike price. YES wins if final > strike, NO wins ties.
+    /// When a resolver quorum is configured, only the admin can resolve directly
+    /// (including disputed markets).
+    /// 
+    /// # Arguments
+    /// * `args` - Resolution parameters (final_price)
     pub fn resolve_market(ctx: Context<ResolveMarket>, args: ResolveMarketArgs) -> Result<()> {
         instructions::resolve_market(ctx, args)
     }
 
+    /// Submit a resolver vote on a market's final price
+    /// 
+    /// Called by registered resolvers after expiry. The market is resolved once
+    /// `threshold` votes agree on the same final price and outcome; a conflicting
+    /// vote moves the market to `Disputed` for the admin to resolve.
+    /// 
+    /// # Arguments
+    /// * `final_price` - Final price at expiry (8 decimals) - ignored for TWAP/median markets
+    pub fn submit_resolution(ctx: Context<SubmitResolution>, final_price: u64) -> Result<()> {
+        instructions::submit_resolution(ctx, final_price)
+    }
+
+    /// Propose a market's final price with a USDC bond
+    /// 
+    /// Anyone can propose after expiry. The market moves to `Proposed` and can be
+    /// challenged during the dispute window; positions cannot be settled until it
+    /// is finalized or the dispute is settled.
+    /// 
+    /// # Arguments
+    /// * `final_price` - Proposed final price at expiry (8 decimals) - ignored for TWAP/median markets
+    pub fn propose_resolution(ctx: Context<ProposeResolution>, final_price: u64) -> Result<()> {
+        instructions::propose_resolution(ctx, final_price)
+    }
+
+    /// Challenge a proposed outcome by posting a matching bond
+    pub fn challenge_resolution(ctx: Context<ChallengeResolution>) -> Result<()> {
+        instructions::challenge_resolution(ctx)
+    }
+
+    /// Settle a challenged proposal
+    /// 
+    /// Called by the admin or a registered resolver. The loser's bond is slashed
+    /// to the winner and the market is resolved.
+    /// 
+    /// # Arguments
+    /// * `final_price` - Correct final price at expiry (8 decimals) - ignored for TWAP/median markets
+    pub fn settle_dispute(ctx: Context<SettleDispute>, final_price: u64) -> Result<()> {
+        instructions::settle_dispute(ctx, final_price)
+    }
+
+    /// Resolve a market from an unchallenged proposal once the dispute window has passed
+    /// 
+    /// Permissionless. Returns the proposer's bond.
+    pub fn finalize_resolution(ctx: Context<FinalizeResolution>) -> Result<()> {
+        instructions::finalize_resolution(ctx)
+    }
+
+    /// Void an unresolvable market (admin only)
+    /// 
+    /// Resolves the market as `Invalid`. Positions are then refunded at cost basis
+    /// by `settle_positions`, scaled pro-rata if the vault cannot cover them all.
+    pub fn void_market(ctx: Context<VoidMarket>) -> Result<()> {
+        instructions::void_market(ctx)
+    }
+
+    /// Resolve a market from a Pyth pull-oracle price update
+    /// 
+    /// Permissionless. Reads the Pyth `PriceUpdateV2` account for the market's asset,
+    /// checks it was published at expiry, and derives the final price and outcome on-chain.
+    pub fn resolve_market_with_oracle(ctx: Context<ResolveMarketWithOracle>) -> Result<()> {
+        instructions::resolve_market_with_oracle(ctx)
+    }
+
+    /// Resolve a market with a signed final price attestation
+    /// 
+    /// Permissionless. Requires an Ed25519 verification instruction, immediately before
+    /// this one, signed by a whitelisted price publisher. The outcome is derived on-chain.
+    /// 
+    /// # Arguments
+    /// * `final_price` - Attested final price (8 decimals)
+    /// * `publish_time` - When the price was observed (must be at expiry)
+    pub fn resolve_market_with_attestation(
+        ctx: Context<ResolveMarketWithAttestation>,
+        final_price: u64,
+        publish_time: i64,
+    ) -> Result<()> {
+        instructions::resolve_market_with_attestation(ctx, final_price, publish_time)
+    }
+
+    /// Resolve a market from the median of its asset's configured price sources
+    /// 
+    /// Permissionless. Invalid sources fail the call, sources not published at expiry
+    /// are skipped and outliers rejected; at least `min_sources` must agree.
+    /// 
+    /// # Arguments
+    /// * `publisher_price` - Signed publisher's attested final price (if configured)
+    /// * `publisher_publish_time` - When the publisher observed the price
+    pub fn resolve_market_with_oracles(
+        ctx: Context<ResolveMarketWithOracles>,
+        publisher_price: u64,
+        publisher_publish_time: i64,
+    ) -> Result<()> {
+        instructions::resolve_market_with_oracles(ctx, publisher_price, publisher_publish_time)
+    }
+
+    /// Resolve a one-touch market YES from a Pyth price that touched its barrier
+    /// 
+    /// Permissionless and allowed before expiry. The price must be published
+    /// during the market's trading window.
+    pub fn resolve_market_on_touch(ctx: Context<ResolveMarketOnTouch>) -> Result<()> {
+        instructions::resolve_market_on_touch(ctx)
+    }
+
+    /// Set how a market's final price is determined (spot, TWAP or median)
+    /// 
+    /// Called by the market authority or admin before the sampling window opens.
+    /// 
+    /// # Arguments
+    /// * `method` - Resolution method
+    /// * `window` - Seconds before expiry in which samples are recorded (0 for spot)
+    /// * `samples` - Minimum samples required (K for median, 0 for spot)
+    pub fn set_resolution_method(
+        ctx: Context<SetResolutionMethod>,
+        method: ResolutionMethod,
+        window: i64,
+        samples: u8,
+    ) -> Result<()> {
+        instructions::set_resolution_method(ctx, method, window, samples)
+    }
+
+    /// Make a pending market categorical, with one bucket per price range
+    /// 
+    /// Called by the market authority or admin before activation. The bucket
+    /// containing the final price pays $1 per share; no bounds reverts to binary.
+    /// 
+    /// # Arguments
+    /// * `bounds` - Ascending bucket boundaries (8 decimals), at most `MAX_BUCKETS - 1`
+    pub fn set_market_buckets(ctx: Context<SetMarketBuckets>, bounds: Vec<u64>) -> Result<()> {
+        instructions::set_market_buckets(ctx, bounds)
+    }
+
+    /// Make a pending market scalar, paying LONG/SHORT linearly between two bounds
+    /// 
+    /// Called by the market authority or admin before activation. YES shares are
+    /// LONG and NO shares SHORT; both bounds at 0 revert to binary.
+    /// 
+    /// # Arguments
+    /// * `lower_bound` - Final price at which LONG pays $0 (8 decimals)
+    /// * `upper_bound` - Final price at which LONG pays $1 (8 decimals)
+    pub fn set_scalar_bounds(ctx: Context<SetScalarBounds>, lower_bound: u64, upper_bound: u64) -> Result<()> {
+        instructions::set_scalar_bounds(ctx, lower_bound, upper_bound)
+    }
+
+    /// Make a pending market one-touch: YES wins once the price touches the barrier
+    /// 
+    /// Called by the market authority or admin before activation. A barrier of 0
+    /// reverts to binary.
+    /// 
+    /// # Arguments
+    /// * `barrier_price` - Price that resolves YES when touched (8 decimals)
+    /// * `direction` - Whether the price must rise to (`Up`) or fall to (`Down`) the barrier
+    pub fn set_barrier(
+        ctx: Context<SetBarrier>,
+        barrier_price: u64,
+        direction: BarrierDirection,
+    ) -> Result<()> {
+        instructions::set_barrier(ctx, barrier_price, direction)
+    }
+
+    /// Override a market's taker fee, optionally with tiers that apply as expiry approaches
+    /// 
+    /// Admin only, until the market is resolved. Passing no fee reverts to the global fee.
+    /// 
+    /// # Arguments
+    /// * `taker_fee_bps` - Base taker fee in basis points (None = use the global fee)
+    /// * `tiers` - Fee tiers ordered from furthest to closest to expiry
+    pub fn set_market_fees(
+        ctx: Context<SetMarketFees>,
+        taker_fee_bps: Option<u16>,
+        tiers: Vec<FeeTier>,
+    ) -> Result<()> {
+        instructions::set_market_fees(ctx, taker_fee_bps, tiers)
+    }
+
+    /// Record a Pyth price sample for a TWAP/median market
+    /// 
+    /// Permissionless. Samples must fall inside the resolution window before expiry,
+    /// one per time slot; `resolve_market` computes the final price from them.
+    pub fn record_price_sample(ctx: Context<RecordPriceSample>) -> Result<()> {
+        instructions::record_price_sample(ctx)
+    }
+
     // =========================================================================
     // Trading Instructions
     // =========================================================================
@@ -117,6 +475,7 @@ pub mod degen_terminal {
     /// 
     /// This instruction creates an on-chain order that can be matched by the relayer.
     /// The order is stored in a PDA, providing trustless order storage for users.
+    /// Market, IOC and FOK orders can only be matched in the slot they are placed in.
     /// 
     /// # Arguments
     /// * `args` - Order parameters (side, outcome, price, size, etc.)
@@ -135,10 +494,11 @@ pub mod degen_terminal {
         instructions::cancel_order(ctx)
     }
 
-    /// Cancel an order after the market has closed, called by the market authority (relayer).
+    /// Cancel an order after the market has closed or the order expired, called by the market authority (relayer).
     ///
     /// This is used to recover user SOL rent + refund escrowed USDC when users leave open
-    /// on-chain orders past market close/expiry.
+    /// on-chain orders past market close/expiry, and for IOC/FOK/Market orders that were
+    /// not matched in their placement slot.
     pub fn cancel_order_by_relayer(ctx: Context<CancelOrderByRelayer>) -> Result<()> {
         instructions::cancel_order_by_relayer(ctx)
     }
@@ -151,6 +511,10 @@ pub mod degen_terminal {
     /// 3. Updates position accounts with YES/NO shares
     /// 4. Collects trading fees
     /// 
+    /// On binary markets a YES bid and a NO bid are complementary and match into a new
+    /// YES/NO pair when their prices sum to at least $1. The trade executes at the maker's
+    /// price and escrow the taker does not need is refunded.
+    /// 
     /// # Arguments
     /// * `maker_args` - Maker's order parameters
     /// * `taker_args` - Taker's order parameters  
@@ -164,6 +528,23 @@ pub mod degen_terminal {
         instructions::execute_match(ctx, maker_args, taker_args, match_size)
     }
 
+    /// Execute one taker against several maker orders (Opening Trades)
+    /// 
+    /// Makers are passed in `remaining_accounts` as (Order PDA, position PDA, USDC account)
+    /// triples and filled in sequence at their own prices. The taker is validated,
+    /// charged and updated once.
+    /// 
+    /// # Arguments
+    /// * `taker_args` - Taker's order parameters (ignored if the taker Order PDA is passed)
+    /// * `match_sizes` - Contracts to fill against each maker, in order
+    pub fn execute_batch_match<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteBatchMatch<'info>>,
+        taker_args: PlaceOrderArgs,
+        match_sizes: Vec<u64>,
+    ) -> Result<()> {
+        instructions::execute_batch_match(ctx, taker_args, match_sizes)
+    }
+
     /// Execute a closing trade (seller sells existing shares to buyer)
     /// 
     /// This instruction handles secondary market trades where:
@@ -180,6 +561,45 @@ pub mod degen_terminal {
     ) -> Result<()> {
         instructions::execute_close(ctx, args)
     }
+    
+    /// Execute a merging trade (YES seller and NO seller burn a pair)
+    /// 
+    /// The two asks must sum to at most $1:
+    /// 1. Both sellers' shares are burned
+    /// 2. The vault pays $1 per pair, split at the maker's price (signed by the market PDA)
+    /// 3. Open interest decreases by the merged size
+    /// 
+    /// # Arguments
+    /// * `args` - Merge trade parameters (maker outcome, both prices, size), checked against both ask orders
+    pub fn execute_merge(
+        ctx: Context<ExecuteMerge>,
+        args: MergeTradeArgs,
+    ) -> Result<()> {
+        instructions::execute_merge(ctx, args)
+    }
+
+    /// Create the market's on-chain order book (market authority or admin)
+    pub fn initialize_order_book(ctx: Context<InitializeOrderBook>) -> Result<()> {
+        instructions::initialize_order_book(ctx)
+    }
+
+    /// Rest a limit order on the market's order book
+    /// 
+    /// Posted orders are filled by `crank_match` only. Cancelling them takes the order book account.
+    pub fn post_order(ctx: Context<PostOrder>) -> Result<()> {
+        instructions::post_order(ctx)
+    }
+
+    /// Fill the best bid against the best ask of an outcome on the order book (permissionless)
+    /// 
+    /// Matches in price-time priority at the price of the order posted first.
+    /// Binary books are kept in YES terms, so YES and NO bids cross each other.
+    /// 
+    /// # Arguments
+    /// * `outcome` - Outcome whose book to match (`Yes` on binary markets)
+    pub fn crank_match(ctx: Context<CrankMatch>, outcome: Outcome) -> Result<()> {
+        instructions::crank_match(ctx, outcome)
+    }
 
     // =========================================================================
     // Settlement Instructions
@@ -188,7 +608,7 @@ pub mod degen_terminal {
     /// Settle a user's position after market resolution
     /// 
     /// Pays out $1.00 per winning share to the user.
-    /// Called by keeper in batches after resolve_market.
+    /// Called by an authorized resolver in batches after resolve_market.
     pub fn settle_positions(ctx: Context<SettlePositions>) -> Result<()> {
         instructions::settle_positions(ctx)
     }
@@ -0,0 +1,318 @@
+use anchor_lang::prelude::*;
+use crate::errors::DegenError;
+use crate::state::{MAX_ORACLE_CONF_BPS, ORACLE_PUBLISH_TOLERANCE, STRIKE_PRICE_DECIMALS};
+
+// ============================================================================
+// PYTH PULL ORACLE
+// ============================================================================
+
+/// Pyth Solana receiver program (owns all `PriceUpdateV2` accounts)
+/// rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
+    12, 183, 250, 187, 82, 247, 166, 72, 187, 91, 49, 125, 154, 1, 139, 144,
+    87, 203, 2, 71, 116, 250, 254, 1, 230, 196, 223, 152, 204, 56, 88, 129,
+]);
+
+/// Anchor discriminator of `PriceUpdateV2` = sha256("account:PriceUpdateV2")[0:8]
+pub const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+/// Pyth price feed IDs (same on every cluster) - registered per asset with `set_asset_config`
+pub const PYTH_FEED_BTC_USD: [u8; 32] = [
+    0xe6, 0x2d, 0xf6, 0xc8, 0xb4, 0xa8, 0x5f, 0xe1, 0xa6, 0x7d, 0xb4, 0x4d, 0xc1, 0x2d, 0xe5, 0xdb,
+    0x33, 0x0f, 0x7a, 0xc6, 0x6b, 0x72, 0xdc, 0x65, 0x8a, 0xfe, 0xdf, 0x0f, 0x4a, 0x41, 0x5b, 0x43,
+];
+pub const PYTH_FEED_ETH_USD: [u8; 32] = [
+    0xff, 0x61, 0x49, 0x1a, 0x93, 0x11, 0x12, 0xdd, 0xf1, 0xbd, 0x81, 0x47, 0xcd, 0x1b, 0x64, 0x13,
+    0x75, 0xf7, 0x9f, 0x58, 0x25, 0x12, 0x6d, 0x66, 0x54, 0x80, 0x87, 0x46, 0x34, 0xfd, 0x0a, 0xce,
+];
+pub const PYTH_FEED_SOL_USD: [u8; 32] = [
+    0xef, 0x0d, 0x8b, 0x6f, 0xda, 0x2c, 0xeb, 0xa4, 0x1d, 0xa1, 0x5d, 0x40, 0x95, 0xd1, 0xda, 0x39,
+    0x2a, 0x0d, 0x2f, 0x8e, 0xd0, 0xc6, 0xc7, 0xbc, 0x0f, 0x4c, 0xfa, 0xc8, 0xc2, 0x80, 0xb5, 0x6d,
+];
+
+/// Wormhole verification level of a posted price update
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VerificationLevel {
+    Partial { num_signatures: u8 },
+    Full,
+}
+
+/// Price message as posted by the Pyth receiver
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct PriceFeedMessage {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+    pub prev_publish_time: i64,
+    pub ema_price: i64,
+    pub ema_conf: u64,
+}
+
+/// Mirror of the receiver's `PriceUpdateV2` account (without the discriminator)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct PriceUpdateV2 {
+    pub write_authority: Pubkey,
+    pub verification_level: VerificationLevel,
+    pub price_message: PriceFeedMessage,
+    pub posted_slot: u64,
+}
+
+/// A validated oracle price, normalized to strike price precision (8 decimals)
+#[derive(Clone, Copy, Debug)]
+pub struct OraclePrice {
+    pub price: u64,
+    pub conf: u64,
+    pub publish_time: i64,
+}
+
+/// Read and validate a Pyth `PriceUpdateV2` account for the given feed.
+///
+/// Checks:
+/// - Account is owned by the Pyth receiver program and has the right discriminator
+/// - Update was fully verified by Wormhole guardians
+/// - Feed ID matches the expected feed
+/// - Price is positive and the confidence interval is within `MAX_ORACLE_CONF_BPS`
+pub fn read_pyth_price(price_update: &AccountInfo, feed_id: &[u8; 32]) -> Result<OraclePrice> {
+    require_keys_eq!(*price_update.owner, PYTH_RECEIVER_PROGRAM_ID, DegenError::InvalidOracle);
+
+    let data = price_update.try_borrow_data()?;
+    require!(data.len() > 8, DegenError::InvalidOracle);
+    require!(data[..8] == PRICE_UPDATE_V2_DISCRIMINATOR, DegenError::InvalidOracle);
+
+    let mut body: &[u8] = &data[8..];
+    let update = PriceUpdateV2::deserialize(&mut body)
+        .map_err(|_| error!(DegenError::InvalidOracle))?;
+
+    require!(update.verification_level == VerificationLevel::Full, DegenError::InvalidOracle);
+    require!(update.price_message.feed_id == *feed_id, DegenError::InvalidOracle);
+    require!(update.price_message.price > 0, DegenError::InvalidOraclePrice);
+
+    let raw_price = update.price_message.price as u64;
+    let raw_conf = update.price_message.conf;
+
+    // Reject prices whose confidence interval is too wide relative to the price
+    let conf_bps = (raw_conf as u128)
+        .checked_mul(10_000).ok_or(DegenError::MathOverflow)?
+        .checked_div(raw_price as u128).ok_or(DegenError::DivisionByZero)?;
+    require!(conf_bps <= MAX_ORACLE_CONF_BPS as u128, DegenError::OracleConfidenceTooWide);
+
+    let exponent = update.price_message.exponent;
+    Ok(OraclePrice {
+        price: scale_to_strike_decimals(raw_price, exponent)?,
+        conf: scale_to_strike_decimals(raw_conf, exponent)?,
+        publish_time: update.price_message.publish_time,
+    })
+}
+
+/// Whether a price was published at `target`: not before it and at most
+/// `ORACLE_PUBLISH_TOLERANCE` seconds after it
+pub fn published_at(publish_time: i64, target: i64) -> bool {
+    publish_time >= target && publish_time <= target + ORACLE_PUBLISH_TOLERANCE
+}
+
+// ============================================================================
+// SWITCHBOARD V2
+// ============================================================================
+
+/// Switchboard V2 program (owns all aggregator accounts)
+/// SW1TCH7qEPTdLsDHRgPuMQjbQxKdH2aBStViMFnt64f
+pub const SWITCHBOARD_V2_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
+    6, 136, 81, 198, 140, 104, 50, 240, 47, 165, 129, 177, 191, 73, 27, 119,
+    202, 65, 119, 107, 162, 185, 136, 181, 166, 250, 186, 142, 227, 162, 236, 144,
+]);
+
+/// Anchor discriminator of `AggregatorAccountData` = sha256("account:AggregatorAccountData")[0:8]
+pub const AGGREGATOR_ACCOUNT_DATA_DISCRIMINATOR: [u8; 8] = [217, 230, 65, 101, 201, 162, 27, 125];
+
+/// Byte offsets into the packed `AggregatorAccountData` layout (`latest_confirmed_round` starts at 341)
+const SB_ROUND_OPEN_TIMESTAMP_OFFSET: usize = 358;
+const SB_RESULT_OFFSET: usize = 366;
+const SB_STD_DEVIATION_OFFSET: usize = 386;
+/// `SwitchboardDecimal` = mantissa (i128) + scale (u32)
+const SB_DECIMAL_SIZE: usize = 20;
+
+/// Read a `SwitchboardDecimal` at `offset` as an unsigned value at strike precision
+fn read_switchboard_decimal(data: &[u8], offset: usize) -> Result<u64> {
+    let bytes = data.get(offset..offset + SB_DECIMAL_SIZE).ok_or(DegenError::InvalidOracle)?;
+    let mantissa = i128::from_le_bytes(bytes[..16].try_into().unwrap());
+    let scale = u32::from_le_bytes(bytes[16..].try_into().unwrap());
+    require!(mantissa >= 0, DegenError::InvalidOraclePrice);
+
+    let target = STRIKE_PRICE_DECIMALS as u32;
+    let scaled = if scale >= target {
+        (mantissa as u128)
+            .checked_div(10u128.checked_pow(scale - target).ok_or(DegenError::MathOverflow)?)
+            .ok_or(DegenError::DivisionByZero)?
+    } else {
+        (mantissa as u128)
+            .checked_mul(10u128.checked_pow(target - scale).ok_or(DegenError::MathOverflow)?)
+            .ok_or(DegenError::MathOverflow)?
+    };
+    u64::try_from(scaled).map_err(|_| error!(DegenError::MathOverflow))
+}
+
+/// Read and validate a Switchboard V2 aggregator's latest confirmed round.
+///
+/// Checks:
+/// - Account is the expected aggregator, owned by Switchboard, with the right discriminator
+/// - Result is positive and the standard deviation is within `MAX_ORACLE_CONF_BPS`
+///
+/// `publish_time` is the round's open timestamp.
+pub fn read_switchboard_price(aggregator: &AccountInfo, expected: &Pubkey) -> Result<OraclePrice> {
+    require_keys_eq!(aggregator.key(), *expected, DegenError::InvalidOracle);
+    require_keys_eq!(*aggregator.owner, SWITCHBOARD_V2_PROGRAM_ID, DegenError::InvalidOracle);
+
+    let data = aggregator.try_borrow_data()?;
+    require!(data.len() >= SB_STD_DEVIATION_OFFSET + SB_DECIMAL_SIZE, DegenError::InvalidOracle);
+    require!(data[..8] == AGGREGATOR_ACCOUNT_DATA_DISCRIMINATOR, DegenError::InvalidOracle);
+
+    let price = read_switchboard_decimal(&data, SB_RESULT_OFFSET)?;
+    let conf = read_switchboard_decimal(&data, SB_STD_DEVIATION_OFFSET)?;
+    require!(price > 0, DegenError::InvalidOraclePrice);
+
+    let conf_bps = (conf as u128)
+        .checked_mul(10_000).ok_or(DegenError::MathOverflow)?
+        .checked_div(price as u128).ok_or(DegenError::DivisionByZero)?;
+    require!(conf_bps <= MAX_ORACLE_CONF_BPS as u128, DegenError::OracleConfidenceTooWide);
+
+    let timestamp = &data[SB_ROUND_OPEN_TIMESTAMP_OFFSET..SB_ROUND_OPEN_TIMESTAMP_OFFSET + 8];
+    Ok(OraclePrice {
+        price,
+        conf,
+        publish_time: i64::from_le_bytes(timestamp.try_into().unwrap()),
+    })
+}
+
+// ============================================================================
+// AGGREGATION
+// ============================================================================
+
+/// Median of a set of prices (average of the middle two for an even count)
+pub fn median(prices: &mut [u64]) -> Option<u64> {
+    if prices.is_empty() {
+        return None;
+    }
+    prices.sort_unstable();
+    let mid = prices.len() / 2;
+    if prices.len() % 2 == 1 {
+        Some(prices[mid])
+    } else {
+        u64::try_from((prices[mid - 1] as u128 + prices[mid] as u128) / 2).ok()
+    }
+}
+
+/// Aggregate prices from independent sources.
+/// Sources more than `max_deviation_bps` away from the median are dropped, and the
+/// median of the rest is returned if at least `min_sources` remain.
+pub fn aggregate_prices(prices: &[u64], max_deviation_bps: u16, min_sources: u8) -> Result<u64> {
+    let mut all = prices.to_vec();
+    let reference = median(&mut all).ok_or(DegenError::InsufficientOracleSources)?;
+
+    let mut agreeing: Vec<u64> = prices
+        .iter()
+        .copied()
+        .filter(|price| {
+            let deviation = (*price as u128).abs_diff(reference as u128) * 10_000 / reference as u128;
+            deviation <= max_deviation_bps as u128
+        })
+        .collect();
+
+    require!(agreeing.len() >= min_sources.max(1) as usize, DegenError::InsufficientOracleSources);
+    median(&mut agreeing).ok_or(error!(DegenError::InsufficientOracleSources))
+}
+
+/// Convert a `value * 10^exponent` oracle number to 8 decimals (strike price precision)
+pub fn scale_to_strike_decimals(value: u64, exponent: i32) -> Result<u64> {
+    let shift = exponent + STRIKE_PRICE_DECIMALS as i32;
+    let scaled = if shift >= 0 {
+        (value as u128)
+            .checked_mul(10u128.checked_pow(shift as u32).ok_or(DegenError::MathOverflow)?)
+            .ok_or(DegenError::MathOverflow)?
+    } else {
+        (value as u128)
+            .checked_div(10u128.checked_pow(shift.unsigned_abs()).ok_or(DegenError::MathOverflow)?)
+            .ok_or(DegenError::DivisionByZero)?
+    };
+    u64::try_from(scaled).map_err(|_| error!(DegenError::MathOverflow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BTC_PRICE: i64 = 9_412_345_678_900; // $94,123.456789 at exponent -8
+
+    fn price_update(feed_id: [u8; 32], price: i64, conf: u64, exponent: i32) -> Vec<u8> {
+        let update = PriceUpdateV2 {
+            write_authority: Pubkey::new_unique(),
+            verification_level: VerificationLevel::Full,
+            price_message: PriceFeedMessage {
+                feed_id,
+                price,
+                conf,
+                exponent,
+                publish_time: 1_700_000_000,
+                prev_publish_time: 1_699_999_999,
+                ema_price: price,
+                ema_conf: conf,
+            },
+            posted_slot: 1,
+        };
+        let mut data = PRICE_UPDATE_V2_DISCRIMINATOR.to_vec();
+        data.extend(update.try_to_vec().unwrap());
+        data
+    }
+
+    fn read(mut data: Vec<u8>, feed_id: &[u8; 32]) -> Result<OraclePrice> {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &PYTH_RECEIVER_PROGRAM_ID, false, 0);
+        read_pyth_price(&info, feed_id)
+    }
+
+    #[test]
+    fn scales_any_exponent_to_strike_decimals() {
+        let price = read(price_update(PYTH_FEED_BTC_USD, BTC_PRICE, 0, -8), &PYTH_FEED_BTC_USD).unwrap();
+        assert_eq!(price.price, 9_412_345_678_900);
+        assert_eq!(price.publish_time, 1_700_000_000);
+
+        // Finer exponents are truncated, coarser ones padded
+        let price = read(price_update(PYTH_FEED_BTC_USD, BTC_PRICE * 100 + 99, 0, -10), &PYTH_FEED_BTC_USD).unwrap();
+        assert_eq!(price.price, 9_412_345_678_900);
+        let price = read(price_update(PYTH_FEED_BTC_USD, 94_123, 0, 0), &PYTH_FEED_BTC_USD).unwrap();
+        assert_eq!(price.price, 9_412_300_000_000);
+    }
+
+    #[test]
+    fn rejects_confidence_wider_than_the_limit() {
+        let limit = BTC_PRICE as u64 * MAX_ORACLE_CONF_BPS / 10_000;
+        let price = read(price_update(PYTH_FEED_BTC_USD, BTC_PRICE, limit, -8), &PYTH_FEED_BTC_USD).unwrap();
+        assert_eq!(price.conf, limit);
+
+        let err = read(price_update(PYTH_FEED_BTC_USD, BTC_PRICE, limit * 2, -8), &PYTH_FEED_BTC_USD).unwrap_err();
+        assert_eq!(err, DegenError::OracleConfidenceTooWide.into());
+    }
+
+    #[test]
+    fn rejects_another_assets_feed() {
+        let err = read(price_update(PYTH_FEED_ETH_USD, BTC_PRICE, 0, -8), &PYTH_FEED_BTC_USD).unwrap_err();
+        assert_eq!(err, DegenError::InvalidOracle.into());
+    }
+
+    #[test]
+    fn rejects_non_positive_prices() {
+        let err = read(price_update(PYTH_FEED_BTC_USD, 0, 0, -8), &PYTH_FEED_BTC_USD).unwrap_err();
+        assert_eq!(err, DegenError::InvalidOraclePrice.into());
+    }
+
+    #[test]
+    fn publish_window_starts_at_the_target() {
+        let expiry = 1_700_000_000;
+        assert!(!published_at(expiry - 1, expiry));
+        assert!(published_at(expiry, expiry));
+        assert!(published_at(expiry + ORACLE_PUBLISH_TOLERANCE, expiry));
+        assert!(!published_at(expiry + ORACLE_PUBLISH_TOLERANCE + 1, expiry));
+    }
+}
@@ -11,18 +11,30 @@ pub enum Side {
     Ask = 1,  // Sell
 }
 
+impl Side {
+    /// The other side of the book
+    pub fn opposite(self) -> Self {
+        match self {
+            Side::Bid => Side::Ask,
+            Side::Ask => Side::Bid,
+        }
+    }
+}
+
 /// Order outcome
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Outcome {
-    Yes = 0,
-    No = 1,
+    Yes,
+    No,
+    /// Bucket index (categorical markets only)
+    Bucket(u8),
 }
 
 /// Order type
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum OrderType {
-    Limit = 0,
-    Market = 1,
+    Limit = 0,  // Rests until filled, cancelled or expired
+    Market = 1, // Same as IOC (the price is the worst acceptable price)
     IOC = 2,    // Immediate-Or-Cancel
     FOK = 3,    // Fill-Or-Kill
 }
@@ -35,6 +47,8 @@ pub enum MarketStatus {
     Closed = 2,     // Trading stopped, awaiting resolution
     Resolved = 3,   // Outcome determined
     Settled = 4,    // All positions paid out
+    Disputed = 5,   // Resolvers submitted conflicting votes, awaiting admin resolution
+    Proposed = 6,   // Outcome proposed with a bond, open to challenge until the dispute window ends
 }
 
 /// Order status
@@ -62,9 +76,36 @@ impl Default for MarketStatus {
 /// Market outcome result
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum MarketOutcome {
-    Pending = 0,
-    Yes = 1,
-    No = 2,
+    Pending,
+    Yes,
+    No,
+    Invalid,        // Voided by admin - positions are refunded at cost basis
+    Bucket(u8),     // Winning bucket index (categorical markets)
+    Scalar,         // Both sides paid pro-rata by the final price (scalar markets)
+}
+
+/// Market type - determines what the shares pay out
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum MarketType {
+    /// YES pays $1 if the final price is above the strike, NO otherwise
+    #[default]
+    Binary = 0,
+    /// N buckets split by `bucket_bounds` - the bucket containing the final price pays $1
+    Categorical = 1,
+    /// YES (LONG) pays linearly between `lower_bound` and `upper_bound`, NO (SHORT) the rest
+    Scalar = 2,
+    /// One-touch: YES wins as soon as the price touches `barrier_price` before expiry
+    Barrier = 3,
+}
+
+/// Which way the price must cross a one-touch barrier
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum BarrierDirection {
+    /// Touched when the price reaches or rises above the barrier
+    #[default]
+    Up = 0,
+    /// Touched when the price reaches or falls below the barrier
+    Down = 1,
 }
 
 /// Trade type - determines how USDC and shares flow
@@ -81,6 +122,24 @@ pub enum TradeType {
     /// Shares: transfer from seller to buyer
     /// Open interest: unchanged
     Closing = 1,
+    
+    /// Merging trade: YES seller and NO seller burn a pair against the vault
+    /// USDC: vault → both sellers ($1 per pair, split at the trade price)
+    /// Shares: burn YES from one seller, NO from the other
+    /// Open interest: -match_size
+    Merging = 2,
+}
+
+/// How a market's final price is determined
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ResolutionMethod {
+    /// Single price at expiry
+    #[default]
+    Spot = 0,
+    /// Time-weighted average of recorded samples over the resolution window
+    Twap = 1,
+    /// Median of the last K recorded samples in the resolution window
+    Median = 2,
 }
 
 impl Default for MarketOutcome {
@@ -106,25 +165,70 @@ pub const PRICE_MULTIPLIER: u64 = 1_000_000;
 pub const SHARE_DECIMALS: u8 = 6;
 pub const SHARE_MULTIPLIER: u64 = 1_000_000;
 
-/// Min/max price bounds ($0.01 - $0.99)
+/// Default price tick ($0.01 increments)
+pub const TICK_SIZE: u64 = 10_000;
+
+/// Default min/max price bounds ($0.01 - $0.99)
 pub const MIN_PRICE: u64 = 10_000;      // $0.01
 pub const MAX_PRICE: u64 = 990_000;     // $0.99
 
-/// Min/max order size (in 6 decimals: 1_000_000 = 1 contract)
+/// Default min/max order size (in 6 decimals: 1_000_000 = 1 contract)
 pub const MIN_ORDER_SIZE: u64 = 1_000;         // 0.001 contracts minimum
 pub const MAX_ORDER_SIZE: u64 = 100_000_000_000;  // 100,000 contracts max
 
-/// Max position size per user per market (in 6 decimals)
+/// Default max position size per user per market (in 6 decimals)
 pub const MAX_POSITION_SIZE: u64 = 500_000_000_000;  // 500,000 contracts
 
-/// Trading closes 30 seconds before expiry
-pub const TRADING_CLOSE_BUFFER: i64 = 30;
+/// Max maker/taker fee in basis points (5%)
+pub const MAX_FEE_BPS: u16 = 500;
+
+/// Strike/final prices use 8 decimals (95_000_00000000 = $95,000)
+pub const STRIKE_PRICE_DECIMALS: u8 = 8;
+
+/// Max oracle confidence interval relative to price (100 = 1%)
+pub const MAX_ORACLE_CONF_BPS: u64 = 100;
+
+/// Oracle price used for resolution must be published within 10 seconds after expiry
+pub const ORACLE_PUBLISH_TOLERANCE: i64 = 10;
 
 /// Max string lengths
 pub const MAX_ASSET_LEN: usize = 10;
 pub const MAX_TIMEFRAME_LEN: usize = 10;
 pub const MAX_PAUSE_REASON_LEN: usize = 100;
 
+/// Max number of registered resolvers
+pub const MAX_RESOLVERS: usize = 10;
+
+/// Max number of whitelisted price publishers (Ed25519 attestation signers)
+pub const MAX_PRICE_PUBLISHERS: usize = 5;
+
+/// Max strikes listed for the same asset, timeframe and expiry (one market each)
+pub const MAX_GROUP_MARKETS: usize = 10;
+
+/// Max timeframes an asset can be enabled for
+pub const MAX_ASSET_TIMEFRAMES: usize = 8;
+
+/// Price samples kept per market for TWAP/median resolution (ring buffer)
+pub const MAX_PRICE_SAMPLES: usize = 16;
+
+/// Max number of price sources configured per asset
+pub const MAX_ORACLE_SOURCES: usize = 3;
+
+/// Max outcomes of a categorical market (split by `MAX_BUCKETS - 1` bounds)
+pub const MAX_BUCKETS: usize = 8;
+
+/// Max tiers in a market fee schedule
+pub const MAX_FEE_TIERS: usize = 4;
+
+/// Max resting orders in a market's order book (all outcomes and sides)
+pub const MAX_BOOK_ORDERS: usize = 96;
+
+/// Min escrow a resting order must hold (USDC, 6 decimals), so filling the book with dust costs real capital
+pub const MIN_RESTING_ESCROW: u64 = 1_000_000;  // $1
+
+/// Max maker orders filled by one batched match
+pub const MAX_BATCH_MAKERS: usize = 8;
+
 // ============================================================================
 // ACCOUNTS
 // ============================================================================
@@ -170,7 +274,202 @@ impl GlobalState {
         1;                          // bump
 }
 
-/// A binary outcome market
+/// Registered resolvers allowed to resolve and settle markets (singleton)
+#[account]
+pub struct ResolverConfig {
+    /// Resolver keys (only the first `num_resolvers` entries are valid)
+    pub resolvers: [Pubkey; MAX_RESOLVERS],
+    /// Number of registered resolvers
+    pub num_resolvers: u8,
+    /// Matching votes required to resolve via `submit_resolution` (0/1 = single resolver can resolve directly)
+    pub threshold: u8,
+    /// USDC bond posted by proposers and challengers (0 = optimistic resolution disabled)
+    pub proposal_bond: u64,
+    /// Seconds a proposed outcome can be challenged
+    pub dispute_window: i64,
+    /// Keys allowed to sign price attestations (only the first `num_price_publishers` are valid)
+    pub price_publishers: [Pubkey; MAX_PRICE_PUBLISHERS],
+    /// Number of whitelisted price publishers
+    pub num_price_publishers: u8,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ResolverConfig {
+    pub const SEED: &'static [u8] = b"resolver_config";
+    
+    pub const SIZE: usize = 8 +     // discriminator
+        32 * MAX_RESOLVERS +        // resolvers
+        1 +                         // num_resolvers
+        1 +                         // threshold
+        8 +                         // proposal_bond
+        8 +                         // dispute_window
+        32 * MAX_PRICE_PUBLISHERS + // price_publishers
+        1 +                         // num_price_publishers
+        1;                          // bump
+    
+    /// Check if a key is a registered resolver
+    pub fn is_resolver(&self, key: &Pubkey) -> bool {
+        self.resolvers[..self.num_resolvers as usize].contains(key)
+    }
+    
+    /// Check if a key is a whitelisted price publisher
+    pub fn is_price_publisher(&self, key: &Pubkey) -> bool {
+        self.price_publishers[..self.num_price_publishers as usize].contains(key)
+    }
+    
+    /// Check if outcomes can be proposed with a bond and a dispute window
+    pub fn optimistic_resolution_enabled(&self) -> bool {
+        self.proposal_bond > 0 && self.dispute_window > 0
+    }
+    
+    /// Check if markets must be resolved by an M-of-N quorum of resolver votes
+    pub fn requires_quorum(&self) -> bool {
+        self.threshold > 1
+    }
+    
+    /// Load the resolver config from its PDA, or None if it has not been created yet
+    pub fn load_optional(info: &AccountInfo) -> Result<Option<Self>> {
+        if info.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*info.owner, crate::ID, crate::errors::DegenError::InvalidResolver);
+        let data = info.try_borrow_data()?;
+        Ok(Some(Self::try_deserialize(&mut &data[..])?))
+    }
+}
+
+/// Check if a key may resolve/settle a market.
+/// Allowed: the protocol admin or a registered resolver. The market authority is not
+/// trusted, since any signer can create a market and become its authority.
+pub fn is_authorized_resolver(
+    key: &Pubkey,
+    global_state: &GlobalState,
+    resolver_config: Option<&ResolverConfig>,
+) -> bool {
+    *key == global_state.admin ||
+    resolver_config.is_some_and(|config| config.is_resolver(key))
+}
+
+/// A single resolver's vote on a market outcome
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct ResolutionVote {
+    /// Resolver that submitted the vote
+    pub resolver: Pubkey,
+    /// Outcome derived from the reported final price
+    pub outcome: MarketOutcome,
+    /// Reported final price (8 decimals)
+    pub final_price: u64,
+}
+
+impl ResolutionVote {
+    pub const SIZE: usize = 32 +    // resolver
+        2 +                         // outcome
+        8;                          // final_price
+    
+    /// Check if two votes agree on both outcome and final price
+    pub fn matches(&self, other: &ResolutionVote) -> bool {
+        self.outcome == other.outcome && self.final_price == other.final_price
+    }
+}
+
+/// Resolver votes collected for a market (one per market)
+#[account]
+pub struct ResolutionVotes {
+    /// The market being voted on
+    pub market: Pubkey,
+    /// Submitted votes (only the first `num_votes` entries are valid)
+    pub votes: [ResolutionVote; MAX_RESOLVERS],
+    /// Number of votes submitted
+    pub num_votes: u8,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ResolutionVotes {
+    pub const SEED: &'static [u8] = b"resolution_votes";
+    
+    pub const SIZE: usize = 8 +     // discriminator
+        32 +                        // market
+        ResolutionVote::SIZE * MAX_RESOLVERS + // votes
+        1 +                         // num_votes
+        1;                          // bump
+    
+    /// Submitted votes
+    pub fn submitted(&self) -> &[ResolutionVote] {
+        &self.votes[..self.num_votes as usize]
+    }
+    
+    /// Check if a resolver has already voted
+    pub fn has_voted(&self, resolver: &Pubkey) -> bool {
+        self.submitted().iter().any(|vote| vote.resolver == *resolver)
+    }
+    
+    /// Drop votes from resolvers that are no longer registered, freeing their slots
+    pub fn prune_removed(&mut self, resolver_config: &ResolverConfig) {
+        let submitted = self.num_votes as usize;
+        let mut kept = 0;
+        for index in 0..submitted {
+            if resolver_config.is_resolver(&self.votes[index].resolver) {
+                self.votes[kept] = self.votes[index];
+                kept += 1;
+            }
+        }
+        self.votes[kept..submitted].fill(ResolutionVote::default());
+        self.num_votes = kept as u8;
+    }
+}
+
+/// A bonded outcome proposal for optimistic resolution (one per market)
+#[account]
+pub struct ResolutionProposal {
+    /// The market being resolved
+    pub market: Pubkey,
+    /// Who proposed the outcome (and posted the bond)
+    pub proposer: Pubkey,
+    /// Proposed final price (8 decimals)
+    pub final_price: u64,
+    /// Outcome derived from the proposed final price
+    pub outcome: MarketOutcome,
+    /// Bond amount posted by each side (USDC, 6 decimals)
+    pub bond: u64,
+    /// USDC token account holding the bonds (ATA owned by this PDA)
+    pub bond_vault: Pubkey,
+    /// When the outcome was proposed
+    pub proposed_at: i64,
+    /// Challenges are accepted until this timestamp
+    pub challenge_deadline: i64,
+    /// Who challenged the proposal (default = unchallenged)
+    pub challenger: Pubkey,
+    /// When the proposal was challenged
+    pub challenged_at: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ResolutionProposal {
+    pub const SEED: &'static [u8] = b"proposal";
+    
+    pub const SIZE: usize = 8 +     // discriminator
+        32 +                        // market
+        32 +                        // proposer
+        8 +                         // final_price
+        2 +                         // outcome
+        8 +                         // bond
+        32 +                        // bond_vault
+        8 +                         // proposed_at
+        8 +                         // challenge_deadline
+        32 +                        // challenger
+        8 +                         // challenged_at
+        1;                          // bump
+    
+    /// Check if the proposal has been challenged
+    pub fn is_challenged(&self) -> bool {
+        self.challenger != Pubkey::default()
+    }
+}
+
+/// A binary, categorical, scalar or one-touch outcome market
 #[account]
 pub struct Market {
     /// Unique market ID (incrementing)
@@ -187,6 +486,8 @@ pub struct Market {
     pub final_price: u64,
     /// Market creation timestamp
     pub created_at: i64,
+    /// Trading window start (expiry minus the timeframe duration) - strike is fixed here
+    pub start_at: i64,
     /// Market expiry timestamp
     pub expiry_at: i64,
     /// Resolution timestamp (when outcome was set)
@@ -207,6 +508,42 @@ pub struct Market {
     pub settled_positions: u32,
     /// Open interest (number of YES/NO pairs)
     pub open_interest: u64,
+    /// Sum of all positions' cost basis (USDC, 6 decimals) - used for refunds if voided
+    pub total_cost_basis: u64,
+    /// Vault balance when the market was voided, less open-order escrow (refunds are split pro-rata)
+    pub refund_vault_balance: u64,
+    /// USDC escrowed by open orders (6 decimals) - still owed to their owners if voided
+    pub open_order_escrow: u64,
+    /// How the final price is determined (spot, TWAP or median of samples)
+    pub resolution_method: ResolutionMethod,
+    /// Seconds before expiry in which price samples are recorded (TWAP/median only)
+    pub resolution_window: i64,
+    /// Minimum samples required to resolve (K for median)
+    pub resolution_samples: u8,
+    /// Trading closes this many seconds before expiry (from the timeframe config)
+    pub close_buffer: i64,
+    /// Position in the strike ladder for this asset, timeframe and expiry (PDA seed)
+    pub strike_index: u8,
+    /// Binary (strike) or categorical (buckets)
+    pub market_type: MarketType,
+    /// Number of buckets (categorical markets only)
+    pub num_buckets: u8,
+    /// Ascending bucket boundaries, 8 decimals (only the first `num_buckets - 1` are valid)
+    pub bucket_bounds: [u64; MAX_BUCKETS - 1],
+    /// Final price at which LONG pays $0 (scalar markets, 8 decimals)
+    pub lower_bound: u64,
+    /// Final price at which LONG pays $1 (scalar markets, 8 decimals)
+    pub upper_bound: u64,
+    /// Price that resolves YES when touched (barrier markets, 8 decimals)
+    pub barrier_price: u64,
+    /// Whether the barrier is touched from below or above
+    pub barrier_direction: BarrierDirection,
+    /// Tick size, price bounds, order size bounds and position cap (from the timeframe config)
+    pub trading_params: TradingParams,
+    /// Taker fee override (the global fee applies when disabled)
+    pub fee_schedule: FeeSchedule,
+    /// Whether the strike must come from the oracle (`activate_market` is rejected)
+    pub oracle_activation: bool,
     /// Bump seed for PDA
     pub bump: u8,
 }
@@ -222,22 +559,226 @@ impl Market {
         8 +                         // strike_price
         8 +                         // final_price
         8 +                         // created_at
+        8 +                         // start_at
         8 +                         // expiry_at
         8 +                         // resolved_at
         8 +                         // settled_at
         1 +                         // status
-        1 +                         // outcome
+        2 +                         // outcome
         8 +                         // total_volume
         4 +                         // total_trades
         4 +                         // total_positions
         4 +                         // settled_positions
         8 +                         // open_interest
+        8 +                         // total_cost_basis
+        8 +                         // refund_vault_balance
+        8 +                         // open_order_escrow
+        1 +                         // resolution_method
+        8 +                         // resolution_window
+        1 +                         // resolution_samples
+        8 +                         // close_buffer
+        1 +                         // strike_index
+        1 +                         // market_type
+        1 +                         // num_buckets
+        8 * (MAX_BUCKETS - 1) +     // bucket_bounds
+        8 +                         // lower_bound
+        8 +                         // upper_bound
+        8 +                         // barrier_price
+        1 +                         // barrier_direction
+        TradingParams::SIZE +       // trading_params
+        FeeSchedule::SIZE +         // fee_schedule
+        1 +                         // oracle_activation
         1;                          // bump
     
     /// Check if market is open for trading
     pub fn is_trading_open(&self, current_time: i64) -> bool {
         self.status == MarketStatus::Open && 
-        current_time < self.expiry_at - TRADING_CLOSE_BUFFER
+        current_time < self.closes_at()
+    }
+    
+    /// When trading closes (expiry minus the timeframe's close buffer)
+    pub fn closes_at(&self) -> i64 {
+        self.expiry_at - self.close_buffer
+    }
+    
+    /// Derive the outcome for a final price.
+    /// YES wins only if final price is strictly above the strike; NO wins ties.
+    /// Categorical markets pick the bucket with `bound[i-1] < final <= bound[i]`,
+    /// so a price on a boundary falls in the lower bucket (like NO on the strike).
+    pub fn outcome_for_price(&self, final_price: u64) -> MarketOutcome {
+        match self.market_type {
+            MarketType::Binary => {
+                if final_price > self.strike_price {
+                    MarketOutcome::Yes
+                } else {
+                    MarketOutcome::No
+                }
+            }
+            MarketType::Categorical => {
+                let bucket = self.bounds().iter().filter(|&&bound| final_price > bound).count();
+                MarketOutcome::Bucket(bucket as u8)
+            }
+            MarketType::Scalar => MarketOutcome::Scalar,
+            MarketType::Barrier => {
+                if self.touches_barrier(final_price) {
+                    MarketOutcome::Yes
+                } else {
+                    MarketOutcome::No
+                }
+            }
+        }
+    }
+    
+    /// Check a final price can settle the market at `current_time`. A barrier market cannot
+    /// resolve NO until `ORACLE_PUBLISH_TOLERANCE` after expiry, so a touch proof published up
+    /// to expiry can still be submitted with `resolve_market_on_touch`.
+    pub fn can_resolve_at(&self, final_price: u64, current_time: i64) -> bool {
+        self.market_type != MarketType::Barrier ||
+            self.outcome_for_price(final_price) == MarketOutcome::Yes ||
+            current_time >= self.expiry_at + ORACLE_PUBLISH_TOLERANCE
+    }
+    
+    /// Check if a price touches the barrier (the barrier price itself counts)
+    pub fn touches_barrier(&self, price: u64) -> bool {
+        match self.barrier_direction {
+            BarrierDirection::Up => price >= self.barrier_price,
+            BarrierDirection::Down => price <= self.barrier_price,
+        }
+    }
+    
+    /// Reset to a plain binary market, clearing bucket, scalar and barrier parameters
+    pub fn clear_market_type(&mut self) {
+        self.market_type = MarketType::Binary;
+        self.num_buckets = 0;
+        self.bucket_bounds = [0; MAX_BUCKETS - 1];
+        self.lower_bound = 0;
+        self.upper_bound = 0;
+        self.barrier_price = 0;
+        self.barrier_direction = BarrierDirection::Up;
+    }
+    
+    /// Check if two final prices settle the market the same way: the same outcome and,
+    /// on scalar markets, the same LONG value (so prices past a bound still agree)
+    pub fn resolves_same(&self, price: u64, other_price: u64) -> bool {
+        match self.market_type {
+            MarketType::Scalar => self.scalar_long_price_at(price) == self.scalar_long_price_at(other_price),
+            _ => self.outcome_for_price(price) == self.outcome_for_price(other_price),
+        }
+    }
+    
+    /// Value of one LONG contract at the final price (6 decimals):
+    /// `(final - lower) / (upper - lower)`, clamped to [$0, $1]. SHORT gets the rest.
+    pub fn scalar_long_price(&self) -> Option<u64> {
+        self.scalar_long_price_at(self.final_price)
+    }
+    
+    /// Value of one LONG contract at `final_price` (6 decimals)
+    pub fn scalar_long_price_at(&self, final_price: u64) -> Option<u64> {
+        let final_price = final_price.clamp(self.lower_bound, self.upper_bound);
+        let price = ((final_price - self.lower_bound) as u128)
+            .checked_mul(USDC_MULTIPLIER as u128)?
+            .checked_div((self.upper_bound - self.lower_bound) as u128)?;
+        u64::try_from(price).ok()
+    }
+    
+    /// Scalar payout for LONG (YES) and SHORT (NO) shares, each side rounded down
+    pub fn scalar_payout(&self, long_shares: u64, short_shares: u64) -> Option<u64> {
+        let long_price = self.scalar_long_price()? as u128;
+        let short_price = USDC_MULTIPLIER as u128 - long_price;
+        let long_payout = (long_shares as u128).checked_mul(long_price)? / SHARE_MULTIPLIER as u128;
+        let short_payout = (short_shares as u128).checked_mul(short_price)? / SHARE_MULTIPLIER as u128;
+        u64::try_from(long_payout + short_payout).ok()
+    }
+    
+    /// Valid bucket boundaries (empty for binary markets)
+    pub fn bounds(&self) -> &[u64] {
+        &self.bucket_bounds[..(self.num_buckets as usize).saturating_sub(1)]
+    }
+    
+    /// Check if an order outcome can be traded on this market
+    pub fn accepts_outcome(&self, outcome: Outcome) -> bool {
+        match (self.market_type, outcome) {
+            (MarketType::Binary | MarketType::Scalar | MarketType::Barrier, Outcome::Yes | Outcome::No) => true,
+            (MarketType::Categorical, Outcome::Bucket(bucket)) => bucket < self.num_buckets,
+            _ => false,
+        }
+    }
+    
+    /// Start of the price sampling window (TWAP/median markets)
+    pub fn sampling_starts_at(&self) -> i64 {
+        self.expiry_at - self.resolution_window
+    }
+    
+    /// Final price from recorded samples, per the market's resolution method.
+    /// Returns None for spot markets or if there are not enough samples.
+    pub fn sampled_final_price(&self, samples: &PriceSamples) -> Option<u64> {
+        let from = self.sampling_starts_at();
+        match self.resolution_method {
+            ResolutionMethod::Spot => None,
+            ResolutionMethod::Twap => samples.twap(from, self.expiry_at, self.resolution_samples),
+            ResolutionMethod::Median => samples.median(from, self.expiry_at, self.resolution_samples),
+        }
+    }
+    
+    /// Final price a resolution path may record: the reported price on spot markets, the
+    /// sampled price on TWAP/median markets (None without enough samples)
+    pub fn resolution_price(&self, reported_price: u64, samples: Option<&PriceSamples>) -> Option<u64> {
+        match self.resolution_method {
+            ResolutionMethod::Spot => Some(reported_price),
+            _ => self.sampled_final_price(samples?),
+        }
+    }
+    
+    /// Refund owed for a cost basis on a voided market: its pro-rata share of the
+    /// snapshotted vault balance (so a surplus or shortfall is split between positions).
+    pub fn refund_for_cost_basis(&self, cost_basis: u64) -> Option<u64> {
+        if self.total_cost_basis == 0 {
+            return Some(0);
+        }
+        let refund = (cost_basis as u128)
+            .checked_mul(self.refund_vault_balance as u128)?
+            .checked_div(self.total_cost_basis as u128)?;
+        u64::try_from(refund).ok()
+    }
+    
+    /// Track escrow locked by a newly placed order
+    pub fn lock_escrow(&mut self, amount: u64) -> Option<()> {
+        self.open_order_escrow = self.open_order_escrow.checked_add(amount)?;
+        Some(())
+    }
+    
+    /// Release escrow orders no longer hold (refunded or spent on fills).
+    /// Saturates so orders placed before escrow was tracked can still be released.
+    pub fn release_escrow(&mut self, amount: u64) {
+        self.open_order_escrow = self.open_order_escrow.saturating_sub(amount);
+    }
+    
+    /// Record an opening trade of `size` shares in the market stats
+    pub fn record_opening_fill(&mut self, size: u64, yes_cost: u64, no_cost: u64, taker_fee: u64) -> Option<()> {
+        let volume = yes_cost.checked_add(no_cost)?;
+        self.open_interest = self.open_interest.checked_add(size)?;
+        self.total_cost_basis = self.total_cost_basis.checked_add(volume)?.checked_add(taker_fee)?;
+        self.total_volume = self.total_volume.checked_add(volume)?;
+        self.total_trades = self.total_trades.checked_add(1)?;
+        Some(())
+    }
+    
+    /// Record a merging trade burning `size` pairs for `payout` USDC out of the vault
+    pub fn record_merge(&mut self, size: u64, payout: u64, cost_basis_released: u64) -> Option<()> {
+        self.open_interest = self.open_interest.checked_sub(size)?;
+        self.total_cost_basis = self.total_cost_basis.saturating_sub(cost_basis_released);
+        self.total_volume = self.total_volume.checked_add(payout)?;
+        self.total_trades = self.total_trades.checked_add(1)?;
+        Some(())
+    }
+    
+    /// Taker fee for a trade at `current_time`: the market's fee schedule if set, else the global fee
+    pub fn taker_fee_bps(&self, global_taker_fee_bps: u16, current_time: i64) -> u16 {
+        if self.fee_schedule.enabled {
+            self.fee_schedule.taker_fee_bps_at(self.expiry_at - current_time)
+        } else {
+            global_taker_fee_bps
+        }
     }
     
     /// Get asset as string
@@ -267,6 +808,530 @@ impl Market {
     }
 }
 
+/// Kind of price source used for multi-oracle resolution
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum OracleSourceKind {
+    /// Pyth pull oracle (`key` = feed ID bytes)
+    #[default]
+    Pyth = 0,
+    /// Switchboard V2 aggregator (`key` = aggregator account)
+    Switchboard = 1,
+    /// Ed25519-signed price attestation (`key` = publisher)
+    SignedPublisher = 2,
+}
+
+/// A configured price source for an asset
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct OracleSource {
+    /// Source type
+    pub kind: OracleSourceKind,
+    /// Feed ID, aggregator account or publisher key (depending on `kind`)
+    pub key: Pubkey,
+}
+
+impl OracleSource {
+    pub const SIZE: usize = 1 +     // kind
+        32;                         // key
+}
+
+/// Price sources for an asset (one per asset)
+#[account]
+pub struct OracleConfig {
+    /// Asset symbol (e.g. BTC)
+    pub asset: [u8; MAX_ASSET_LEN],
+    /// Configured sources (only the first `num_sources` are valid)
+    pub sources: [OracleSource; MAX_ORACLE_SOURCES],
+    /// Number of configured sources
+    pub num_sources: u8,
+    /// Valid, agreeing sources required to resolve
+    pub min_sources: u8,
+    /// Max distance from the median before a source is rejected (basis points)
+    pub max_deviation_bps: u16,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl OracleConfig {
+    pub const SEED: &'static [u8] = b"oracle_config";
+    
+    pub const SIZE: usize = 8 +     // discriminator
+        MAX_ASSET_LEN +             // asset
+        OracleSource::SIZE * MAX_ORACLE_SOURCES + // sources
+        1 +                         // num_sources
+        1 +                         // min_sources
+        2 +                         // max_deviation_bps
+        1;                          // bump
+    
+    /// Configured sources
+    pub fn configured(&self) -> &[OracleSource] {
+        &self.sources[..self.num_sources as usize]
+    }
+}
+
+/// Strike ladder: all markets for the same asset, timeframe and expiry
+#[account]
+pub struct MarketGroup {
+    /// Asset symbol
+    pub asset: [u8; MAX_ASSET_LEN],
+    /// Timeframe
+    pub timeframe: [u8; MAX_TIMEFRAME_LEN],
+    /// Shared expiry timestamp
+    pub expiry_at: i64,
+    /// Who listed the first strike - only they or the admin can add further strikes
+    pub creator: Pubkey,
+    /// Member markets, indexed by `strike_index` (only the first `num_markets` are valid)
+    pub markets: [Pubkey; MAX_GROUP_MARKETS],
+    /// Number of listed strikes
+    pub num_markets: u8,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl MarketGroup {
+    pub const SEED: &'static [u8] = b"market_group";
+    
+    pub const SIZE: usize = 8 +     // discriminator
+        MAX_ASSET_LEN +             // asset
+        MAX_TIMEFRAME_LEN +         // timeframe
+        8 +                         // expiry_at
+        32 +                        // creator
+        32 * MAX_GROUP_MARKETS +    // markets
+        1 +                         // num_markets
+        1;                          // bump
+    
+    /// Listed markets
+    pub fn members(&self) -> &[Pubkey] {
+        &self.markets[..self.num_markets as usize]
+    }
+}
+
+/// Rolling Up/Down series: consecutive base markets (strike index 0) for an asset and
+/// timeframe, where each market's strike is the previous market's final price
+#[account]
+pub struct MarketSeries {
+    /// Asset symbol
+    pub asset: [u8; MAX_ASSET_LEN],
+    /// Timeframe
+    pub timeframe: [u8; MAX_TIMEFRAME_LEN],
+    /// Whether base markets must be activated by `roll_series`
+    pub enabled: bool,
+    /// Most recent market in the series (the next one rolls from its final price)
+    pub last_market: Pubkey,
+    /// Expiry of the most recent market
+    pub last_expiry: i64,
+    /// Number of markets rolled into the series
+    pub num_rolls: u64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl MarketSeries {
+    pub const SEED: &'static [u8] = b"market_series";
+    
+    pub const SIZE: usize = 8 +     // discriminator
+        MAX_ASSET_LEN +             // asset
+        MAX_TIMEFRAME_LEN +         // timeframe
+        1 +                         // enabled
+        32 +                        // last_market
+        8 +                         // last_expiry
+        8 +                         // num_rolls
+        1;                          // bump
+    
+    /// Check if a market's strike must come from the series rather than an activation price
+    pub fn chains(&self, market: &Market) -> bool {
+        self.enabled && market.strike_index == 0
+    }
+    
+    /// Load the series from its PDA, or None if it has not been created yet
+    pub fn load_optional(info: &AccountInfo) -> Result<Option<Self>> {
+        if info.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*info.owner, crate::ID, crate::errors::DegenError::InvalidSeriesMarket);
+        let data = info.try_borrow_data()?;
+        Ok(Some(Self::try_deserialize(&mut &data[..])?))
+    }
+}
+
+/// Recurring market schedule: base markets for an asset and timeframe that anyone can
+/// create on the cadence grid with `crank_create_market`
+#[account]
+pub struct MarketSchedule {
+    /// Asset symbol
+    pub asset: [u8; MAX_ASSET_LEN],
+    /// Timeframe
+    pub timeframe: [u8; MAX_TIMEFRAME_LEN],
+    /// Whether the crank can create markets
+    pub enabled: bool,
+    /// Seconds between expiries (a multiple of the timeframe duration)
+    pub cadence: i64,
+    /// Markets are created once their expiry is at most this many seconds away
+    pub lead_time: i64,
+    /// Authority of created markets (activates them with the strike)
+    pub market_authority: Pubkey,
+    /// USDC mint for created market vaults
+    pub usdc_mint: Pubkey,
+    /// Lamports paid to the caller per created market, on top of the rent it paid
+    pub tip_lamports: u64,
+    /// Earliest expiry the next market can have (0 before the first market)
+    pub next_expiry: i64,
+    /// Most recently created market
+    pub last_market: Pubkey,
+    /// Number of markets created by the crank
+    pub num_created: u64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl MarketSchedule {
+    pub const SEED: &'static [u8] = b"market_schedule";
+    
+    pub const SIZE: usize = 8 +     // discriminator
+        MAX_ASSET_LEN +             // asset
+        MAX_TIMEFRAME_LEN +         // timeframe
+        1 +                         // enabled
+        8 +                         // cadence
+        8 +                         // lead_time
+        32 +                        // market_authority
+        32 +                        // usdc_mint
+        8 +                         // tip_lamports
+        8 +                         // next_expiry
+        32 +                        // last_market
+        8 +                         // num_created
+        1;                          // bump
+    
+    /// Expiry of the next market: the first cadence slot far enough out to be created now.
+    /// Slots missed while nobody cranked are skipped rather than back-filled.
+    pub fn due_expiry(&self, current_time: i64, min_lead_time: i64) -> i64 {
+        let earliest = current_time + min_lead_time;
+        let earliest_slot = (earliest + self.cadence - 1) / self.cadence * self.cadence;
+        earliest_slot.max(self.next_expiry)
+    }
+    
+    /// Get asset bytes without null padding (for PDA seeds)
+    pub fn asset_bytes(&self) -> &[u8] {
+        let len = self.asset.iter().position(|&x| x == 0).unwrap_or(self.asset.len());
+        &self.asset[..len]
+    }
+    
+    /// Get timeframe bytes without null padding (for PDA seeds)
+    pub fn timeframe_bytes(&self) -> &[u8] {
+        let len = self.timeframe.iter().position(|&x| x == 0).unwrap_or(self.timeframe.len());
+        &self.timeframe[..len]
+    }
+}
+
+/// Registered asset (one per symbol) - markets can only be created for enabled assets
+#[account]
+pub struct AssetConfig {
+    /// Asset symbol (e.g. BTC)
+    pub asset: [u8; MAX_ASSET_LEN],
+    /// Pyth price feed ID for the asset (checked against `PriceUpdateV2` accounts)
+    pub oracle_feed: [u8; 32],
+    /// Strike price precision (strikes are truncated to this many of the 8 decimals)
+    pub strike_decimals: u8,
+    /// Whether new markets can be created and activated
+    pub enabled: bool,
+    /// Whether new markets must be activated from the oracle (no authority-chosen strikes)
+    pub oracle_activation: bool,
+    /// Timeframes markets can be created for (only the first `num_timeframes` are valid)
+    pub timeframes: [[u8; MAX_TIMEFRAME_LEN]; MAX_ASSET_TIMEFRAMES],
+    /// Number of allowed timeframes
+    pub num_timeframes: u8,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl AssetConfig {
+    pub const SEED: &'static [u8] = b"asset_config";
+    
+    pub const SIZE: usize = 8 +     // discriminator
+        MAX_ASSET_LEN +             // asset
+        32 +                        // oracle_feed
+        1 +                         // strike_decimals
+        1 +                         // enabled
+        1 +                         // oracle_activation
+        MAX_TIMEFRAME_LEN * MAX_ASSET_TIMEFRAMES + // timeframes
+        1 +                         // num_timeframes
+        1;                          // bump
+    
+    /// Check if markets can be created for a timeframe
+    pub fn allows_timeframe(&self, timeframe: &str) -> bool {
+        let timeframe = str_to_bytes::<MAX_TIMEFRAME_LEN>(timeframe);
+        self.timeframes[..self.num_timeframes as usize].contains(&timeframe)
+    }
+    
+    /// Truncate a strike price (8 decimals) to the asset's strike precision
+    pub fn normalize_strike(&self, strike_price: u64) -> u64 {
+        let tick = 10u64.pow(STRIKE_PRICE_DECIMALS.saturating_sub(self.strike_decimals) as u32);
+        strike_price - strike_price % tick
+    }
+}
+
+/// Fee step of a market fee schedule
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct FeeTier {
+    /// Tier applies once expiry is at most this many seconds away
+    pub seconds_before_expiry: i64,
+    /// Taker fee in basis points
+    pub taker_fee_bps: u16,
+}
+
+impl FeeTier {
+    pub const SIZE: usize = 8 +     // seconds_before_expiry
+        2;                          // taker_fee_bps
+}
+
+/// Per-market taker fee, optionally changing as expiry approaches
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct FeeSchedule {
+    /// Whether the market overrides the global taker fee
+    pub enabled: bool,
+    /// Taker fee in basis points before the first tier
+    pub taker_fee_bps: u16,
+    /// Tiers ordered from furthest to closest to expiry (only the first `num_tiers` are valid)
+    pub tiers: [FeeTier; MAX_FEE_TIERS],
+    /// Number of tiers
+    pub num_tiers: u8,
+}
+
+impl FeeSchedule {
+    pub const SIZE: usize = 1 +     // enabled
+        2 +                         // taker_fee_bps
+        FeeTier::SIZE * MAX_FEE_TIERS + // tiers
+        1;                          // num_tiers
+    
+    /// Taker fee with `seconds_to_expiry` left: the closest tier that has started, else the base fee
+    pub fn taker_fee_bps_at(&self, seconds_to_expiry: i64) -> u16 {
+        self.tiers[..self.num_tiers as usize]
+            .iter()
+            .rev()
+            .find(|tier| seconds_to_expiry <= tier.seconds_before_expiry)
+            .map_or(self.taker_fee_bps, |tier| tier.taker_fee_bps)
+    }
+}
+
+/// Order price and size limits for a market
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TradingParams {
+    /// Prices must be a multiple of this (6 decimals)
+    pub tick_size: u64,
+    /// Lowest order price (6 decimals)
+    pub min_price: u64,
+    /// Highest order price (6 decimals)
+    pub max_price: u64,
+    /// Smallest order size (6 decimals)
+    pub min_order_size: u64,
+    /// Largest order size (6 decimals)
+    pub max_order_size: u64,
+    /// Max shares per user per outcome (6 decimals)
+    pub max_position_size: u64,
+}
+
+impl Default for TradingParams {
+    fn default() -> Self {
+        Self {
+            tick_size: TICK_SIZE,
+            min_price: MIN_PRICE,
+            max_price: MAX_PRICE,
+            min_order_size: MIN_ORDER_SIZE,
+            max_order_size: MAX_ORDER_SIZE,
+            max_position_size: MAX_POSITION_SIZE,
+        }
+    }
+}
+
+impl TradingParams {
+    pub const SIZE: usize = 8 +     // tick_size
+        8 +                         // min_price
+        8 +                         // max_price
+        8 +                         // min_order_size
+        8 +                         // max_order_size
+        8;                          // max_position_size
+    
+    /// Check the limits are consistent: prices on the tick grid strictly inside $0-$1
+    pub fn is_valid(&self) -> bool {
+        self.tick_size > 0
+            && self.min_price > 0
+            && self.is_on_tick(self.min_price)
+            && self.is_on_tick(self.max_price)
+            && self.min_price <= self.max_price
+            && self.max_price < PRICE_MULTIPLIER
+            && self.min_order_size > 0
+            && self.min_order_size <= self.max_order_size
+            && self.max_order_size <= self.max_position_size
+    }
+    
+    /// Check a price is within the bounds
+    pub fn allows_price(&self, price: u64) -> bool {
+        price >= self.min_price && price <= self.max_price
+    }
+    
+    /// Check a price is on the tick grid
+    pub fn is_on_tick(&self, price: u64) -> bool {
+        price.checked_rem(self.tick_size) == Some(0)
+    }
+    
+    /// Check an order or fill size is within the bounds
+    pub fn allows_size(&self, size: u64) -> bool {
+        size >= self.min_order_size && size <= self.max_order_size
+    }
+}
+
+/// Trading rules for a market timeframe (one per timeframe)
+#[account]
+pub struct TimeframeConfig {
+    /// Timeframe label (e.g. 5m)
+    pub timeframe: [u8; MAX_TIMEFRAME_LEN],
+    /// Trading window length in seconds - expiries must be a multiple of it
+    pub duration: i64,
+    /// Trading closes this many seconds before expiry
+    pub close_buffer: i64,
+    /// Minimum seconds between market creation and expiry
+    pub min_lead_time: i64,
+    /// Maximum seconds between market creation and expiry
+    pub max_lead_time: i64,
+    /// Trading limits copied to markets created for this timeframe
+    pub trading_params: TradingParams,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl TimeframeConfig {
+    pub const SEED: &'static [u8] = b"timeframe_config";
+    
+    pub const SIZE: usize = 8 +     // discriminator
+        MAX_TIMEFRAME_LEN +         // timeframe
+        8 +                         // duration
+        8 +                         // close_buffer
+        8 +                         // min_lead_time
+        8 +                         // max_lead_time
+        TradingParams::SIZE +       // trading_params
+        1;                          // bump
+    
+    /// Check if an expiry is on the timeframe grid
+    pub fn is_aligned(&self, expiry_ts: i64) -> bool {
+        expiry_ts % self.duration == 0
+    }
+    
+    /// Check if a market created now with this expiry is within the lead time bounds
+    pub fn allows_lead_time(&self, expiry_ts: i64, current_time: i64) -> bool {
+        let lead_time = expiry_ts - current_time;
+        lead_time >= self.min_lead_time && lead_time <= self.max_lead_time
+    }
+}
+
+/// A single oracle price sample
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct PriceSample {
+    /// Price (8 decimals)
+    pub price: u64,
+    /// Oracle publish time
+    pub publish_time: i64,
+}
+
+impl PriceSample {
+    pub const SIZE: usize = 8 +     // price
+        8;                          // publish_time
+    
+    /// Whether a sample was recorded (oracle prices are always positive)
+    pub fn is_recorded(&self) -> bool {
+        self.price > 0
+    }
+}
+
+/// Oracle samples recorded near expiry (one per market), one per time slot of the window
+#[account]
+pub struct PriceSamples {
+    /// The market these samples are for
+    pub market: Pubkey,
+    /// Samples indexed by time slot (see `slot_for`), oldest slot first
+    pub samples: [PriceSample; MAX_PRICE_SAMPLES],
+    /// Number of recorded samples (max `MAX_PRICE_SAMPLES`)
+    pub count: u8,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl PriceSamples {
+    pub const SEED: &'static [u8] = b"price_samples";
+    
+    pub const SIZE: usize = 8 +     // discriminator
+        32 +                        // market
+        PriceSample::SIZE * MAX_PRICE_SAMPLES + // samples
+        1 +                         // count
+        1;                          // bump
+    
+    /// Length of a time slot for a `resolution_window`, so the window fits in
+    /// `MAX_PRICE_SAMPLES` slots (the window end falls in the last one)
+    pub fn slot_length(resolution_window: i64) -> i64 {
+        let gaps = MAX_PRICE_SAMPLES as i64 - 1;
+        ((resolution_window + gaps - 1) / gaps).max(1)
+    }
+    
+    /// Slot of a sample published at `publish_time` in a window starting at `window_start`
+    pub fn slot_for(publish_time: i64, window_start: i64, slot_length: i64) -> Option<usize> {
+        let slot = usize::try_from((publish_time - window_start) / slot_length).ok()?;
+        (slot < MAX_PRICE_SAMPLES).then_some(slot)
+    }
+    
+    /// Record a sample in its slot. Each slot is filled once, so samples can arrive in any
+    /// order and neither a burst of prints nor an early late-window print blocks other slots.
+    /// Returns false if the slot is already filled.
+    pub fn record(&mut self, slot: usize, sample: PriceSample) -> bool {
+        if self.samples[slot].is_recorded() {
+            return false;
+        }
+        self.samples[slot] = sample;
+        self.count += 1;
+        true
+    }
+    
+    /// Samples published within `[from, to]`, oldest first
+    pub fn in_window(&self, from: i64, to: i64) -> Vec<PriceSample> {
+        self.samples
+            .iter()
+            .filter(|sample| sample.is_recorded())
+            .filter(|sample| sample.publish_time >= from && sample.publish_time <= to)
+            .copied()
+            .collect()
+    }
+    
+    /// Time-weighted average price over `[from, to]`.
+    /// Each sample is weighted by how long it stood until the next sample (or `to`).
+    pub fn twap(&self, from: i64, to: i64, min_samples: u8) -> Option<u64> {
+        let samples = self.in_window(from, to);
+        if samples.is_empty() || samples.len() < min_samples as usize {
+            return None;
+        }
+        let mut weighted_sum: u128 = 0;
+        let mut total_weight: u128 = 0;
+        for (i, sample) in samples.iter().enumerate() {
+            let until = samples.get(i + 1).map_or(to, |next| next.publish_time);
+            let weight = (until - sample.publish_time) as u128;
+            weighted_sum = weighted_sum.checked_add((sample.price as u128).checked_mul(weight)?)?;
+            total_weight += weight;
+        }
+        if total_weight == 0 {
+            // All samples at `to` - plain average
+            let sum: u128 = samples.iter().map(|sample| sample.price as u128).sum();
+            return u64::try_from(sum / samples.len() as u128).ok();
+        }
+        u64::try_from(weighted_sum / total_weight).ok()
+    }
+    
+    /// Median of the last `k` samples in `[from, to]`
+    pub fn median(&self, from: i64, to: i64, k: u8) -> Option<u64> {
+        let samples = self.in_window(from, to);
+        let k = (k as usize).max(1);
+        if samples.len() < k {
+            return None;
+        }
+        let mut prices: Vec<u64> = samples[samples.len() - k..].iter().map(|sample| sample.price).collect();
+        crate::oracle::median(&mut prices)
+    }
+}
+
 /// Market vault for holding USDC collateral
 #[account]
 pub struct MarketVault {
@@ -302,6 +1367,10 @@ pub struct UserPosition {
     pub yes_cost_basis: u64,
     /// Total USDC paid for NO shares (cost basis)
     pub no_cost_basis: u64,
+    /// Shares per bucket (categorical markets, 6 decimals)
+    pub bucket_shares: [u64; MAX_BUCKETS],
+    /// Total USDC paid per bucket (categorical markets, cost basis)
+    pub bucket_cost_basis: [u64; MAX_BUCKETS],
     /// Realized P&L from closing positions
     pub realized_pnl: i64,
     /// Whether position has been settled
@@ -322,6 +1391,8 @@ impl UserPosition {
         8 +                         // no_shares
         8 +                         // yes_cost_basis
         8 +                         // no_cost_basis
+        8 * MAX_BUCKETS +           // bucket_shares
+        8 * MAX_BUCKETS +           // bucket_cost_basis
         8 +                         // realized_pnl
         1 +                         // settled
         8 +                         // payout
@@ -329,12 +1400,95 @@ impl UserPosition {
     
     /// Check if position has any shares
     pub fn has_position(&self) -> bool {
-        self.yes_shares > 0 || self.no_shares > 0
+        self.yes_shares > 0 || self.no_shares > 0 || self.bucket_shares.iter().any(|&shares| shares > 0)
+    }
+    
+    /// Total cost basis (None on overflow)
+    pub fn total_cost(&self) -> Option<u64> {
+        self.bucket_cost_basis
+            .iter()
+            .try_fold(self.yes_cost_basis.checked_add(self.no_cost_basis)?, |total, &cost| total.checked_add(cost))
+    }
+    
+    /// Buckets credited by one leg of a categorical complete set:
+    /// the traded bucket itself, or every other bucket for the complement leg
+    fn bucket_leg(bucket: u8, complement: bool, num_buckets: u8) -> impl Iterator<Item = usize> {
+        (0..num_buckets as usize).filter(move |&i| (i == bucket as usize) != complement)
     }
     
-    /// Total cost basis
-    pub fn total_cost(&self) -> u64 {
-        self.yes_cost_basis + self.no_cost_basis
+    /// Largest holding among the buckets a categorical leg would credit
+    pub fn bucket_leg_shares(&self, bucket: u8, complement: bool, num_buckets: u8) -> u64 {
+        Self::bucket_leg(bucket, complement, num_buckets)
+            .map(|i| self.bucket_shares[i])
+            .max()
+            .unwrap_or(0)
+    }
+    
+    /// Check an opening trade of `size` keeps the leg this position receives within `max_position_size`.
+    /// `yes_leg` is the YES side (the traded bucket on categorical markets).
+    pub fn fits_opening_leg(&self, outcome: Outcome, yes_leg: bool, num_buckets: u8, size: u64, max_position_size: u64) -> bool {
+        let held = match outcome {
+            Outcome::Bucket(bucket) => self.bucket_leg_shares(bucket, !yes_leg, num_buckets),
+            _ if yes_leg => self.yes_shares,
+            _ => self.no_shares,
+        };
+        held.checked_add(size).is_some_and(|total| total <= max_position_size)
+    }
+    
+    /// Mint the leg of an opening trade this position receives, adding `cost` to its cost basis
+    pub fn mint_opening_leg(&mut self, outcome: Outcome, yes_leg: bool, num_buckets: u8, size: u64, cost: u64) -> Option<()> {
+        match outcome {
+            Outcome::Bucket(bucket) => self.mint_bucket_leg(bucket, !yes_leg, num_buckets, size, cost),
+            _ if yes_leg => {
+                self.yes_shares = self.yes_shares.checked_add(size)?;
+                self.yes_cost_basis = self.yes_cost_basis.checked_add(cost)?;
+                Some(())
+            }
+            _ => {
+                self.no_shares = self.no_shares.checked_add(size)?;
+                self.no_cost_basis = self.no_cost_basis.checked_add(cost)?;
+                Some(())
+            }
+        }
+    }
+    
+    /// Mint one leg of a categorical complete set.
+    /// The complement leg's cost is split evenly across its buckets (remainder to the first).
+    pub fn mint_bucket_leg(&mut self, bucket: u8, complement: bool, num_buckets: u8, size: u64, cost: u64) -> Option<()> {
+        let legs = Self::bucket_leg(bucket, complement, num_buckets).count() as u64;
+        let share_of_cost = cost.checked_div(legs)?;
+        let mut remainder = cost - share_of_cost * legs;
+        for i in Self::bucket_leg(bucket, complement, num_buckets) {
+            self.bucket_shares[i] = self.bucket_shares[i].checked_add(size)?;
+            self.bucket_cost_basis[i] = self.bucket_cost_basis[i].checked_add(share_of_cost + remainder)?;
+            remainder = 0;
+        }
+        Some(())
+    }
+    
+    /// Burn `size` YES or NO shares, releasing their proportional cost basis.
+    /// Returns the cost basis released (None if the position holds fewer shares).
+    pub fn burn_leg(&mut self, yes_leg: bool, size: u64) -> Option<u64> {
+        let (shares, cost_basis) = if yes_leg {
+            (&mut self.yes_shares, &mut self.yes_cost_basis)
+        } else {
+            (&mut self.no_shares, &mut self.no_cost_basis)
+        };
+        let released = (*cost_basis as u128)
+            .checked_mul(size as u128)?
+            .checked_div(*shares as u128)?;
+        let released = u64::try_from(released).ok()?;
+        *shares = shares.checked_sub(size)?;
+        *cost_basis = cost_basis.saturating_sub(released);
+        Some(released)
+    }
+    
+    /// Realize PnL for shares sold for `proceeds` against the `cost_basis` they released.
+    /// Returns the PnL (None on overflow).
+    pub fn realize_pnl(&mut self, proceeds: u64, cost_basis: u64) -> Option<i64> {
+        let pnl = i64::try_from(proceeds).ok()?.checked_sub(i64::try_from(cost_basis).ok()?)?;
+        self.realized_pnl = self.realized_pnl.checked_add(pnl)?;
+        Some(pnl)
     }
 }
 
@@ -347,7 +1501,7 @@ pub struct Order {
     pub market: Pubkey,
     /// Order side (Bid = buy, Ask = sell)
     pub side: Side,
-    /// Outcome being traded (Yes or No)
+    /// Outcome being traded (Yes or No, or a bucket on categorical markets)
     pub outcome: Outcome,
     /// Order type (Limit, Market, IOC, FOK)
     pub order_type: OrderType,
@@ -365,7 +1519,11 @@ pub struct Order {
     pub expiry_ts: i64,
     /// Order creation timestamp
     pub created_at: i64,
-    /// Amount of USDC locked in vault for this order
+    /// Slot the order was placed in (Market/IOC/FOK orders can only fill in this slot)
+    pub placed_slot: u64,
+    /// Whether the order rests on the market's order book (matched by `crank_match` only)
+    pub on_book: bool,
+    /// Amount of USDC locked in vault for this order (cost plus the taker fee reserve)
     pub locked_amount: u64,
     /// Bump seed for PDA
     pub bump: u8,
@@ -378,7 +1536,7 @@ impl Order {
         32 +                        // owner
         32 +                        // market
         1 +                         // side
-        1 +                         // outcome
+        2 +                         // outcome
         1 +                         // order_type
         8 +                         // price
         8 +                         // size
@@ -387,6 +1545,8 @@ impl Order {
         8 +                         // client_order_id
         8 +                         // expiry_ts
         8 +                         // created_at
+        8 +                         // placed_slot
+        1 +                         // on_book
         8 +                         // locked_amount
         1;                          // bump
     
@@ -400,9 +1560,202 @@ impl Order {
         matches!(self.status, OrderStatus::Open | OrderStatus::PartialFill)
     }
     
-    /// Check if order is expired
-    pub fn is_expired(&self, current_time: i64) -> bool {
-        self.order_type == OrderType::Limit && current_time > self.expiry_ts
+    /// Check if the order must fill in its placement slot (Market, IOC, FOK)
+    pub fn is_immediate(&self) -> bool {
+        self.order_type != OrderType::Limit
+    }
+    
+    /// Check if order is expired: limit orders at `expiry_ts`, immediate orders after their placement slot
+    pub fn is_expired(&self, current_time: i64, current_slot: u64) -> bool {
+        if self.is_immediate() {
+            current_slot > self.placed_slot
+        } else {
+            current_time >= self.expiry_ts
+        }
+    }
+    
+    /// Check if a fill of `fill_size` is allowed (FOK orders must fill their whole remaining size)
+    pub fn allows_fill(&self, fill_size: u64) -> bool {
+        self.order_type != OrderType::FOK || fill_size == self.remaining_size()
+    }
+    
+    /// Escrow backing a `fill_size` fill (its share of the locked amount)
+    pub fn fill_escrow(&self, fill_size: u64) -> u64 {
+        let escrow = (self.locked_amount as u128)
+            .checked_mul(fill_size as u128)
+            .and_then(|v| v.checked_div(self.size as u128))
+            .unwrap_or(0);
+        u64::try_from(escrow).unwrap_or(0)
+    }
+    
+    /// Escrow of a `fill_size` fill left over after paying `cost` for it (price improvement)
+    pub fn fill_surplus(&self, fill_size: u64, cost: u64) -> u64 {
+        self.fill_escrow(fill_size).saturating_sub(cost)
+    }
+    
+    /// Escrow the order still holds (none once filled or cancelled)
+    pub fn outstanding_escrow(&self) -> u64 {
+        if self.is_active() {
+            self.refundable_amount()
+        } else {
+            0
+        }
+    }
+    
+    /// Escrowed USDC for the unfilled size (proportional to the locked amount)
+    pub fn refundable_amount(&self) -> u64 {
+        if self.filled_size == 0 {
+            self.locked_amount
+        } else if self.filled_size >= self.size {
+            0
+        } else {
+            self.locked_amount
+                .checked_mul(self.remaining_size())
+                .unwrap_or(0)
+                .checked_div(self.size)
+                .unwrap_or(0)
+        }
+    }
+}
+
+/// Resting order in an order book slab
+#[zero_copy]
+#[derive(Default, Debug)]
+pub struct BookEntry {
+    /// Order PDA (default when the slot is free)
+    pub order: Pubkey,
+    /// Order owner
+    pub owner: Pubkey,
+    /// Limit price in 6 decimals
+    pub price: u64,
+    /// Posting sequence number (time priority - lower is earlier)
+    pub seq: u64,
+    /// Order side (`Side as u8`)
+    pub side: u8,
+    /// Outcome code (see `OrderBook::outcome_code`)
+    pub outcome: u8,
+    /// Padding for 8-byte alignment
+    pub padding: [u8; 6],
+}
+
+impl BookEntry {
+    pub const SIZE: usize = 32 +    // order
+        32 +                        // owner
+        8 +                         // price
+        8 +                         // seq
+        1 +                         // side
+        1 +                         // outcome
+        6;                          // padding
+    
+    /// Check if the slot holds an order
+    pub fn is_used(&self) -> bool {
+        self.order != Pubkey::default()
+    }
+}
+
+/// On-chain order book for a market (zero-copy slab of resting limit orders).
+/// Bids and asks of every outcome share the slab; `crank_match` fills the best
+/// bid against the best ask of an outcome in price-time priority. YES and NO orders
+/// form one book: NO orders are stored as the opposite YES order at $1 - price.
+#[account(zero_copy)]
+pub struct OrderBook {
+    /// Market this book is for
+    pub market: Pubkey,
+    /// Sequence number of the next posted order
+    pub next_seq: u64,
+    /// Number of resting orders
+    pub num_orders: u32,
+    /// Bump seed for PDA
+    pub bump: u8,
+    /// Padding for 8-byte alignment
+    pub padding: [u8; 3],
+    /// Resting orders (free slots have a default order key)
+    pub orders: [BookEntry; MAX_BOOK_ORDERS],
+}
+
+impl OrderBook {
+    pub const SEED: &'static [u8] = b"order_book";
+    
+    pub const SIZE: usize = 8 +     // discriminator
+        32 +                        // market
+        8 +                         // next_seq
+        4 +                         // num_orders
+        1 +                         // bump
+        3 +                         // padding
+        BookEntry::SIZE * MAX_BOOK_ORDERS; // orders
+    
+    /// Outcome code stored in book entries: YES = 0, NO = 1, bucket i = 2 + i
+    pub fn outcome_code(outcome: Outcome) -> u8 {
+        match outcome {
+            Outcome::Yes => 0,
+            Outcome::No => 1,
+            Outcome::Bucket(bucket) => bucket.saturating_add(2),
+        }
+    }
+    
+    /// Book side, outcome and price of an order (NO orders in YES terms)
+    pub fn book_terms(side: Side, outcome: Outcome, price: u64) -> (Side, Outcome, u64) {
+        match outcome {
+            Outcome::No => (side.opposite(), Outcome::Yes, USDC_MULTIPLIER - price),
+            _ => (side, outcome, price),
+        }
+    }
+    
+    /// Add a resting order, returning its sequence number (None if the book is full)
+    pub fn insert(&mut self, order: Pubkey, owner: Pubkey, side: Side, outcome: Outcome, price: u64) -> Option<u64> {
+        let (side, outcome, price) = Self::book_terms(side, outcome, price);
+        let seq = self.next_seq;
+        let entry = self.orders.iter_mut().find(|entry| !entry.is_used())?;
+        *entry = BookEntry {
+            order,
+            owner,
+            price,
+            seq,
+            side: side as u8,
+            outcome: Self::outcome_code(outcome),
+            padding: [0; 6],
+        };
+        self.next_seq = seq.checked_add(1)?;
+        self.num_orders += 1;
+        Some(seq)
+    }
+    
+    /// Remove a resting order, returning whether it was on the book
+    pub fn remove(&mut self, order: &Pubkey) -> bool {
+        match self.orders.iter_mut().find(|entry| entry.is_used() && entry.order == *order) {
+            Some(entry) => {
+                *entry = BookEntry::default();
+                self.num_orders -= 1;
+                true
+            }
+            None => false,
+        }
+    }
+    
+    /// Check if `owner` rests an order on the other side of `outcome` that a `side` order at `price` would cross
+    pub fn crosses_own_order(&self, owner: &Pubkey, side: Side, outcome: Outcome, price: u64) -> bool {
+        let (side, outcome, price) = Self::book_terms(side, outcome, price);
+        let outcome = Self::outcome_code(outcome);
+        self.orders.iter().any(|entry| {
+            entry.is_used()
+                && entry.owner == *owner
+                && entry.outcome == outcome
+                && entry.side != side as u8
+                && if side == Side::Bid { entry.price <= price } else { entry.price >= price }
+        })
+    }
+    
+    /// Best resting order of a side and outcome: highest bid / lowest ask, earliest first at the same price.
+    /// Binary books are in YES terms, so pass `Outcome::Yes`.
+    pub fn best(&self, side: Side, outcome: Outcome) -> Option<&BookEntry> {
+        let outcome = Self::outcome_code(outcome);
+        self.orders
+            .iter()
+            .filter(|entry| entry.is_used() && entry.side == side as u8 && entry.outcome == outcome)
+            .min_by_key(|entry| {
+                let price_rank = if side == Side::Bid { u64::MAX - entry.price } else { entry.price };
+                (price_rank, entry.seq)
+            })
     }
 }
 
@@ -410,6 +1763,30 @@ impl Order {
 // HELPER FUNCTIONS
 // ============================================================================
 
+/// Express an order in terms of `frame`: a NO order at `price` is the opposite YES order at
+/// $1 - `price` (and vice versa). None if the outcomes are neither equal nor complementary.
+pub fn order_in_frame(side: Side, outcome: Outcome, price: u64, frame: Outcome) -> Option<(Side, u64)> {
+    match (outcome, frame) {
+        _ if outcome == frame => Some((side, price)),
+        (Outcome::Yes, Outcome::No) | (Outcome::No, Outcome::Yes) => Some((side.opposite(), USDC_MULTIPLIER - price)),
+        _ => None,
+    }
+}
+
+/// USDC cost of each leg of an opening trade of `size` shares at `price` (rounded up).
+/// On categorical markets the "YES" leg is the traded bucket and the "NO" leg its complement.
+pub fn opening_costs(outcome: Outcome, price: u64, size: u64) -> Option<(u64, u64)> {
+    let yes_price = if outcome != Outcome::No { price } else { USDC_MULTIPLIER - price };
+    let no_price = USDC_MULTIPLIER - yes_price;
+    let leg_cost = |leg_price: u64| {
+        leg_price
+            .checked_mul(size)?
+            .checked_add(SHARE_MULTIPLIER - 1)?
+            .checked_div(SHARE_MULTIPLIER)
+    };
+    Some((leg_cost(yes_price)?, leg_cost(no_price)?))
+}
+
 /// Convert a string to a fixed-size byte array, padding with zeros
 pub fn str_to_bytes<const N: usize>(s: &str) -> [u8; N] {
     let mut bytes = [0u8; N];
@@ -418,3 +1795,116 @@ pub fn str_to_bytes<const N: usize>(s: &str) -> [u8; N] {
     bytes[..len].copy_from_slice(&s_bytes[..len]);
     bytes
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn empty_samples() -> PriceSamples {
+        PriceSamples {
+            market: Pubkey::default(),
+            samples: [PriceSample::default(); MAX_PRICE_SAMPLES],
+            count: 0,
+            bump: 0,
+        }
+    }
+    
+    /// Record a sample in its slot (as `record_price_sample` does)
+    fn try_record(samples: &mut PriceSamples, price: u64, publish_time: i64, from: i64, slot_length: i64) -> bool {
+        let slot = PriceSamples::slot_for(publish_time, from, slot_length).unwrap();
+        samples.record(slot, PriceSample { price, publish_time })
+    }
+    
+    #[test]
+    fn sample_flood_cannot_evict_window_history() {
+        let (expiry, window) = (10_000, 300);
+        let from = expiry - window;
+        let slot_length = PriceSamples::slot_length(window);
+        let mut samples = empty_samples();
+    
+        // Honest samples over the first four minutes of the window at 100
+        let mut honest = 0;
+        let mut publish_time = from;
+        while publish_time < expiry - 60 {
+            assert!(try_record(&mut samples, 100, publish_time, from, slot_length));
+            honest += 1;
+            publish_time += slot_length;
+        }
+    
+        // A print every second over the last minute at 200 - most are rejected
+        let flooded = (expiry - 59..=expiry).filter(|&t| try_record(&mut samples, 200, t, from, slot_length)).count();
+        assert!(flooded <= 60 / slot_length as usize + 1);
+    
+        // Every honest sample is still in the window and dominates the TWAP
+        let in_window = samples.in_window(from, expiry);
+        assert_eq!(in_window.iter().filter(|sample| sample.price == 100).count(), honest);
+        assert!(samples.twap(from, expiry, 1).unwrap() < 150);
+    }
+    
+    #[test]
+    fn every_second_fills_each_slot_once() {
+        let (expiry, window) = (10_000, 301);
+        let from = expiry - window;
+        let slot_length = PriceSamples::slot_length(window);
+        let mut samples = empty_samples();
+    
+        let recorded = (from..=expiry).filter(|&t| try_record(&mut samples, 100, t, from, slot_length)).count();
+        assert!(recorded <= MAX_PRICE_SAMPLES);
+        assert_eq!(samples.in_window(from, expiry).len(), recorded);
+        assert_eq!(samples.in_window(from, expiry)[0].publish_time, from);
+    }
+    
+    #[test]
+    fn early_slot_can_be_filled_after_a_later_one() {
+        let (expiry, window) = (10_000, 300);
+        let from = expiry - window;
+        let slot_length = PriceSamples::slot_length(window);
+        let mut samples = empty_samples();
+    
+        // A print at expiry is recorded first and fills the last slot
+        assert!(try_record(&mut samples, 200, expiry, from, slot_length));
+        assert!(!try_record(&mut samples, 300, expiry, from, slot_length));
+    
+        // Earlier slots can still be filled, and come first in the window
+        assert!(try_record(&mut samples, 100, from, from, slot_length));
+        assert!(try_record(&mut samples, 100, from + slot_length, from, slot_length));
+        let in_window = samples.in_window(from, expiry);
+        assert_eq!(in_window.len(), 3);
+        assert_eq!(in_window[0].publish_time, from);
+        assert_eq!(in_window[2].publish_time, expiry);
+    }
+    
+    #[test]
+    fn removed_resolvers_votes_free_their_slots() {
+        let current = Pubkey::new_unique();
+        let mut resolver_config = ResolverConfig {
+            resolvers: [Pubkey::default(); MAX_RESOLVERS],
+            num_resolvers: 1,
+            threshold: 1,
+            proposal_bond: 0,
+            dispute_window: 0,
+            price_publishers: [Pubkey::default(); MAX_PRICE_PUBLISHERS],
+            num_price_publishers: 0,
+            bump: 0,
+        };
+        resolver_config.resolvers[0] = current;
+    
+        // Every slot is taken, one by a current resolver and the rest by removed ones
+        let mut votes = ResolutionVotes {
+            market: Pubkey::default(),
+            votes: [ResolutionVote::default(); MAX_RESOLVERS],
+            num_votes: MAX_RESOLVERS as u8,
+            bump: 0,
+        };
+        for vote in votes.votes.iter_mut() {
+            vote.resolver = Pubkey::new_unique();
+            vote.final_price = 100;
+        }
+        votes.votes[3].resolver = current;
+    
+        votes.prune_removed(&resolver_config);
+        assert_eq!(votes.num_votes, 1);
+        assert_eq!(votes.submitted()[0].resolver, current);
+        assert!(votes.votes[1..].iter().all(|vote| *vote == ResolutionVote::default()));
+    }
+}
@@ -4,7 +4,10 @@ import {
   PublicKey, 
   Keypair, 
   SystemProgram, 
-  LAMPORTS_PER_SOL 
+  Transaction,
+  LAMPORTS_PER_SOL,
+  Ed25519Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
 } from "@solana/web3.js";
 import { 
   TOKEN_PROGRAM_ID, 
@@ -19,6 +22,7 @@ import BN from "bn.js";
 
 // Import the IDL type (generated after anchor build)
 import { DegenTerminal } from "../target/types/degen_terminal";
+import { waitForTimestamp } from "./utils";
 
 describe("degen-terminal", () => {
   // Configure the client to use the local cluster
@@ -38,10 +42,97 @@ describe("degen-terminal", () => {
   // PDAs
   let globalStatePda: PublicKey;
   let globalStateBump: number;
+  let resolverConfigPda: PublicKey;
   let marketPda: PublicKey;
   let marketBump: number;
   let vaultPda: PublicKey;
 
+  const getAssetConfigPda = (asset: string) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("asset_config"), Buffer.from(asset)],
+      program.programId
+    )[0];
+
+  const getTimeframeConfigPda = (timeframe: string) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("timeframe_config"), Buffer.from(timeframe)],
+      program.programId
+    )[0];
+
+  const getMarketGroupPda = (asset: string, timeframe: string, expiry: BN) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("market_group"), Buffer.from(asset), Buffer.from(timeframe), expiry.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+
+  const getMarketSeriesPda = (asset: string, timeframe: string) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("market_series"), Buffer.from(asset), Buffer.from(timeframe)],
+      program.programId
+    )[0];
+
+  const getMarketSchedulePda = (asset: string, timeframe: string) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("market_schedule"), Buffer.from(asset), Buffer.from(timeframe)],
+      program.programId
+    )[0];
+
+  // Next expiry on the timeframe grid at least `minLead` seconds from now
+  const alignedExpiry = (timeframeSecs: number, minLead: number) =>
+    new BN(Math.ceil((Math.floor(Date.now() / 1000) + minLead) / timeframeSecs) * timeframeSecs);
+
+  // Open 1m market expiring within ~75s, for tests that need an expired market
+  const createExpiringMarket = async (asset: string, strike: BN) => {
+    const expiry = alignedExpiry(60, 15);
+    const [market] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("market"),
+        Buffer.from(asset),
+        Buffer.from("1m"),
+        expiry.toArrayLike(Buffer, "le", 8),
+        Buffer.from([0]),
+      ],
+      program.programId
+    );
+    const vault = await anchor.utils.token.associatedAddress({ mint: usdcMint, owner: market });
+
+    await program.methods
+      .initializeMarket(asset, "1m", strike, expiry, 0)
+      .accounts({
+        globalState: globalStatePda,
+        assetConfig: getAssetConfigPda(asset),
+        timeframeConfig: getTimeframeConfigPda("1m"),
+        market,
+        marketGroup: getMarketGroupPda(asset, "1m", expiry),
+        marketSeries: getMarketSeriesPda(asset, "1m"),
+        vault,
+        usdcMint: usdcMint,
+        authority: relayer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([relayer])
+      .rpc();
+
+    return { market, vault, expiry };
+  };
+
+  // Wait until the validator clock passes the expiry
+  const waitForExpiry = (expiry: BN) => waitForTimestamp(provider.connection, expiry.toNumber() + 1);
+
+  // Suites that wait for a market to expire only run with SLOW_TESTS=1
+  const describeSlow = process.env.SLOW_TESTS ? describe : describe.skip;
+
+  // Borsh-encoded PriceAttestation { market, kind, price, publish_time }
+  const attestationMessage = (market: PublicKey, kind: number, price: BN, publishTime: BN) =>
+    Buffer.concat([
+      market.toBuffer(),
+      Buffer.from([kind]),
+      price.toArrayLike(Buffer, "le", 8),
+      publishTime.toArrayLike(Buffer, "le", 8),
+    ]);
+
   // Token accounts
   let usdcMint: PublicKey;
   let user1Usdc: PublicKey;
@@ -88,6 +179,11 @@ describe("degen-terminal", () => {
       program.programId
     );
 
+    [resolverConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("resolver_config")],
+      program.programId
+    );
+
     // Create USDC mock mint
     usdcMint = await createMint(
       provider.connection,
@@ -208,6 +304,208 @@ describe("degen-terminal", () => {
     });
   });
 
+  // ============================================================================
+  // TIMEFRAME REGISTRY TESTS
+  // ============================================================================
+
+  describe("set_timeframe_config", () => {
+    const MAX_LEAD_TIME = 3 * 24 * 3600;
+    // 24h markets trade on $0.001 ticks with 10x the default size limits
+    const DAILY_TRADING_PARAMS = {
+      tickSize: new BN(1_000),
+      minPrice: new BN(1_000),
+      maxPrice: new BN(999_000),
+      minOrderSize: new BN(1_000),
+      maxOrderSize: new BN(1_000_000_000_000),
+      maxPositionSize: new BN(5_000_000_000_000),
+    };
+
+    const setTimeframeConfig = (
+      timeframe: string,
+      duration: number,
+      closeBuffer: number,
+      minLeadTime: number,
+      signer = admin,
+      tradingParams: typeof DAILY_TRADING_PARAMS | null = null
+    ) =>
+      program.methods
+        .setTimeframeConfig(timeframe, new BN(duration), new BN(closeBuffer), new BN(minLeadTime), new BN(MAX_LEAD_TIME), tradingParams)
+        .accounts({
+          globalState: globalStatePda,
+          timeframeConfig: getTimeframeConfigPda(timeframe),
+          admin: signer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([signer])
+        .rpc();
+
+    it("successfully registers the market timeframes", async () => {
+      // 1m markets keep the time-dependent tests short
+      await setTimeframeConfig("1m", 60, 5, 10);
+      await setTimeframeConfig("5m", 5 * 60, 30, 60);
+      await setTimeframeConfig("15m", 15 * 60, 30, 60);
+      await setTimeframeConfig("1h", 3600, 30, 60);
+      await setTimeframeConfig("4h", 4 * 3600, 30, 60);
+      await setTimeframeConfig("24h", 24 * 3600, 30, 60, admin, DAILY_TRADING_PARAMS);
+
+      const timeframeConfig = await program.account.timeframeConfig.fetch(getTimeframeConfigPda("15m"));
+      expect(timeframeConfig.duration.toNumber()).to.equal(15 * 60);
+      expect(timeframeConfig.closeBuffer.toNumber()).to.equal(30);
+      expect(timeframeConfig.minLeadTime.toNumber()).to.equal(60);
+      expect(timeframeConfig.maxLeadTime.toNumber()).to.equal(MAX_LEAD_TIME);
+      expect(timeframeConfig.tradingParams.tickSize.toNumber()).to.equal(10_000);
+      expect(timeframeConfig.tradingParams.maxPositionSize.toString()).to.equal("500000000000");
+
+      const dailyConfig = await program.account.timeframeConfig.fetch(getTimeframeConfigPda("24h"));
+      expect(dailyConfig.tradingParams.tickSize.toNumber()).to.equal(1_000);
+      expect(dailyConfig.tradingParams.maxPositionSize.toString()).to.equal(DAILY_TRADING_PARAMS.maxPositionSize.toString());
+    });
+
+    it("keeps the trading params when they are not passed", async () => {
+      await setTimeframeConfig("24h", 24 * 3600, 30, 60);
+
+      const dailyConfig = await program.account.timeframeConfig.fetch(getTimeframeConfigPda("24h"));
+      expect(dailyConfig.tradingParams.tickSize.toNumber()).to.equal(1_000);
+    });
+
+    it("fails with price bounds off the tick grid", async () => {
+      try {
+        await setTimeframeConfig("30m", 30 * 60, 30, 60, admin, {
+          ...DAILY_TRADING_PARAMS,
+          tickSize: new BN(10_000),
+          minPrice: new BN(15_000),
+        });
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("InvalidTimeframeConfig");
+      }
+    });
+
+    it("fails with a close buffer longer than the timeframe", async () => {
+      try {
+        await setTimeframeConfig("30m", 30 * 60, 30 * 60, 60);
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("InvalidTimeframeConfig");
+      }
+    });
+
+    it("fails when markets would be created already closed", async () => {
+      try {
+        await setTimeframeConfig("30m", 30 * 60, 30, 30);
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("InvalidTimeframeConfig");
+      }
+    });
+
+    it("fails when non-admin tries to register a timeframe", async () => {
+      try {
+        await setTimeframeConfig("30m", 30 * 60, 30, 60, user1);
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.satisfy(
+          (msg: string) => msg.includes("Unauthorized") || msg.includes("unauthorized") || msg.includes("constraint")
+        );
+      }
+    });
+  });
+
+  // ============================================================================
+  // ASSET REGISTRY TESTS
+  // ============================================================================
+
+  describe("set_asset_config", () => {
+    const PYTH_FEEDS: Record<string, string> = {
+      BTC: "e62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43",
+      ETH: "ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace",
+      SOL: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
+    };
+    const ALL_TIMEFRAMES = ["1m", "5m", "15m", "1h", "4h", "24h"];
+
+    const setAssetConfig = (
+      asset: string,
+      strikeDecimals: number,
+      enabled: boolean,
+      timeframes: string[],
+      signer = admin,
+      oracleActivation = false
+    ) =>
+      program.methods
+        .setAssetConfig(
+          asset,
+          Array.from(Buffer.from(PYTH_FEEDS[asset] ?? PYTH_FEEDS.BTC, "hex")),
+          strikeDecimals,
+          enabled,
+          oracleActivation,
+          timeframes
+        )
+        .accounts({
+          globalState: globalStatePda,
+          assetConfig: getAssetConfigPda(asset),
+          admin: signer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([signer])
+        .rpc();
+
+    it("successfully registers BTC, ETH and SOL", async () => {
+      for (const asset of ["BTC", "ETH", "SOL"]) {
+        await setAssetConfig(asset, 8, true, ALL_TIMEFRAMES);
+      }
+
+      const assetConfig = await program.account.assetConfig.fetch(getAssetConfigPda("ETH"));
+      expect(Buffer.from(assetConfig.oracleFeed).toString("hex")).to.equal(PYTH_FEEDS.ETH);
+      expect(assetConfig.strikeDecimals).to.equal(8);
+      expect(assetConfig.enabled).to.be.true;
+      expect(assetConfig.numTimeframes).to.equal(ALL_TIMEFRAMES.length);
+    });
+
+    it("registers a disabled asset and an asset limited to 1h markets", async () => {
+      await setAssetConfig("AVAX", 2, false, ALL_TIMEFRAMES);
+      await setAssetConfig("LINK", 2, true, ["1h"]);
+
+      const assetConfig = await program.account.assetConfig.fetch(getAssetConfigPda("AVAX"));
+      expect(assetConfig.enabled).to.be.false;
+    });
+
+    it("registers an asset whose markets must be activated from the oracle", async () => {
+      await setAssetConfig("DOGE", 4, true, ["1h"], admin, true);
+
+      const assetConfig = await program.account.assetConfig.fetch(getAssetConfigPda("DOGE"));
+      expect(assetConfig.oracleActivation).to.be.true;
+    });
+
+    it("fails with more strike decimals than the price precision", async () => {
+      try {
+        await setAssetConfig("BTC", 9, true, ALL_TIMEFRAMES);
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("InvalidAssetConfig");
+      }
+    });
+
+    it("fails with an invalid timeframe label", async () => {
+      try {
+        await setAssetConfig("BTC", 8, true, ["timeframe-too-long"]);
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("InvalidTimeframe");
+      }
+    });
+
+    it("fails when non-admin tries to register an asset", async () => {
+      try {
+        await setAssetConfig("BTC", 8, true, ALL_TIMEFRAMES, user1);
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.satisfy(
+          (msg: string) => msg.includes("Unauthorized") || msg.includes("unauthorized") || msg.includes("constraint")
+        );
+      }
+    });
+  });
+
   // ============================================================================
   // INITIALIZE MARKET TESTS
   // ============================================================================
@@ -216,8 +514,8 @@ describe("degen-terminal", () => {
     let expiryTs: BN;
 
     beforeEach(() => {
-      // Set expiry to 5 minutes from now
-      expiryTs = new BN(Math.floor(Date.now() / 1000) + 300);
+      // Next 5m grid expiry at least 5 minutes from now
+      expiryTs = alignedExpiry(300, 300);
     });
 
     it("successfully creates a BTC 5m market", async () => {
@@ -228,6 +526,7 @@ describe("degen-terminal", () => {
           Buffer.from(ASSET),
           Buffer.from(TIMEFRAME),
           expiryTs.toArrayLike(Buffer, "le", 8),
+          Buffer.from([0]),
         ],
         program.programId
       );
@@ -239,10 +538,14 @@ describe("degen-terminal", () => {
       });
 
       await program.methods
-        .initializeMarket(ASSET, TIMEFRAME, STRIKE_PRICE, expiryTs)
+        .initializeMarket(ASSET, TIMEFRAME, STRIKE_PRICE, expiryTs, 0)
         .accounts({
           globalState: globalStatePda,
+          assetConfig: getAssetConfigPda(ASSET),
+          timeframeConfig: getTimeframeConfigPda(TIMEFRAME),
           market: marketPda,
+          marketGroup: getMarketGroupPda(ASSET, TIMEFRAME, expiryTs),
+          marketSeries: getMarketSeriesPda(ASSET, TIMEFRAME),
           vault: vaultPda,
           usdcMint: usdcMint,
           authority: relayer.publicKey,
@@ -260,17 +563,20 @@ describe("degen-terminal", () => {
       expect(market.authority.toBase58()).to.equal(relayer.publicKey.toBase58());
       expect(market.strikePrice.toString()).to.equal(STRIKE_PRICE.toString());
       expect(market.expiryAt.toNumber()).to.equal(expiryTs.toNumber());
+      expect(market.startAt.toNumber()).to.equal(expiryTs.toNumber() - 300);
       expect(market.status).to.deep.equal({ open: {} });
       expect(market.outcome).to.deep.equal({ pending: {} });
       expect(market.totalVolume.toNumber()).to.equal(0);
       expect(market.openInterest.toNumber()).to.equal(0);
+      expect(market.tradingParams.tickSize.toNumber()).to.equal(10_000);
+      expect(market.tradingParams.maxOrderSize.toString()).to.equal("100000000000");
 
       // Verify global state updated
       const globalState = await program.account.globalState.fetch(globalStatePda);
       expect(globalState.totalMarkets.toNumber()).to.equal(1);
     });
 
-    it("fails with invalid asset (not BTC/ETH/SOL)", async () => {
+    it("fails with an unregistered asset", async () => {
       const invalidAsset = "DOGE";
       const newExpiry = new BN(Math.floor(Date.now() / 1000) + 600);
       
@@ -280,6 +586,7 @@ describe("degen-terminal", () => {
           Buffer.from(invalidAsset),
           Buffer.from(TIMEFRAME),
           newExpiry.toArrayLike(Buffer, "le", 8),
+          Buffer.from([0]),
         ],
         program.programId
       );
@@ -291,10 +598,14 @@ describe("degen-terminal", () => {
 
       try {
         await program.methods
-          .initializeMarket(invalidAsset, TIMEFRAME, STRIKE_PRICE, newExpiry)
+          .initializeMarket(invalidAsset, TIMEFRAME, STRIKE_PRICE, newExpiry, 0)
           .accounts({
             globalState: globalStatePda,
+            assetConfig: getAssetConfigPda(invalidAsset),
+            timeframeConfig: getTimeframeConfigPda(TIMEFRAME),
             market: invalidMarketPda,
+            marketGroup: getMarketGroupPda(invalidAsset, TIMEFRAME, newExpiry),
+            marketSeries: getMarketSeriesPda(invalidAsset, TIMEFRAME),
             vault: invalidVault,
             usdcMint: usdcMint,
             authority: relayer.publicKey,
@@ -307,12 +618,12 @@ describe("degen-terminal", () => {
         expect.fail("Should have thrown an error");
       } catch (err: any) {
         expect(err.error?.errorCode?.code || err.message).to.satisfy(
-          (msg: string) => msg.includes("InvalidAsset") || msg.includes("invalid")
+          (msg: string) => msg.includes("AccountNotInitialized") || msg.includes("not initialized")
         );
       }
     });
 
-    it("fails with invalid timeframe (not 5m/15m/1h/4h)", async () => {
+    it("fails with an unregistered timeframe", async () => {
       const invalidTimeframe = "30m";
       const newExpiry = new BN(Math.floor(Date.now() / 1000) + 600);
       
@@ -322,6 +633,7 @@ describe("degen-terminal", () => {
           Buffer.from(ASSET),
           Buffer.from(invalidTimeframe),
           newExpiry.toArrayLike(Buffer, "le", 8),
+          Buffer.from([0]),
         ],
         program.programId
       );
@@ -333,10 +645,14 @@ describe("degen-terminal", () => {
 
       try {
         await program.methods
-          .initializeMarket(ASSET, invalidTimeframe, STRIKE_PRICE, newExpiry)
+          .initializeMarket(ASSET, invalidTimeframe, STRIKE_PRICE, newExpiry, 0)
           .accounts({
             globalState: globalStatePda,
+            assetConfig: getAssetConfigPda(ASSET),
+            timeframeConfig: getTimeframeConfigPda(invalidTimeframe),
             market: invalidMarketPda,
+            marketGroup: getMarketGroupPda(ASSET, invalidTimeframe, newExpiry),
+            marketSeries: getMarketSeriesPda(ASSET, invalidTimeframe),
             vault: invalidVault,
             usdcMint: usdcMint,
             authority: relayer.publicKey,
@@ -349,36 +665,39 @@ describe("degen-terminal", () => {
         expect.fail("Should have thrown an error");
       } catch (err: any) {
         expect(err.error?.errorCode?.code || err.message).to.satisfy(
-          (msg: string) => msg.includes("InvalidTimeframe") || msg.includes("invalid")
+          (msg: string) => msg.includes("AccountNotInitialized") || msg.includes("not initialized")
         );
       }
     });
 
-    it("fails with expiry in the past", async () => {
-      const pastExpiry = new BN(Math.floor(Date.now() / 1000) - 60); // 1 minute ago
-      
-      const [invalidMarketPda] = PublicKey.findProgramAddressSync(
+    it("fails for a disabled asset", async () => {
+      const newExpiry = new BN(Math.floor(Date.now() / 1000) + 600);
+      const [blockedMarketPda] = PublicKey.findProgramAddressSync(
         [
           Buffer.from("market"),
-          Buffer.from("ETH"),
-          Buffer.from(TIMEFRAME),
-          pastExpiry.toArrayLike(Buffer, "le", 8),
+          Buffer.from("AVAX"),
+          Buffer.from("5m"),
+          newExpiry.toArrayLike(Buffer, "le", 8),
+          Buffer.from([0]),
         ],
         program.programId
       );
-
-      const invalidVault = await anchor.utils.token.associatedAddress({
+      const blockedVault = await anchor.utils.token.associatedAddress({
         mint: usdcMint,
-        owner: invalidMarketPda,
+        owner: blockedMarketPda,
       });
 
       try {
         await program.methods
-          .initializeMarket("ETH", TIMEFRAME, STRIKE_PRICE, pastExpiry)
+          .initializeMarket("AVAX", "5m", new BN(20_00000000), newExpiry, 0)
           .accounts({
             globalState: globalStatePda,
-            market: invalidMarketPda,
-            vault: invalidVault,
+            assetConfig: getAssetConfigPda("AVAX"),
+            timeframeConfig: getTimeframeConfigPda("5m"),
+            market: blockedMarketPda,
+            marketGroup: getMarketGroupPda("AVAX", "5m", newExpiry),
+            marketSeries: getMarketSeriesPda("AVAX", "5m"),
+            vault: blockedVault,
             usdcMint: usdcMint,
             authority: relayer.publicKey,
             tokenProgram: TOKEN_PROGRAM_ID,
@@ -389,137 +708,585 @@ describe("degen-terminal", () => {
           .rpc();
         expect.fail("Should have thrown an error");
       } catch (err: any) {
-        expect(err.error?.errorCode?.code || err.message).to.satisfy(
-          (msg: string) => msg.includes("InvalidExpiry") || msg.includes("invalid")
-        );
+        expect(err.error?.errorCode?.code || err.message).to.include("AssetDisabled");
       }
     });
-  });
 
-  // ============================================================================
-  // PLACE ORDER TESTS
-  // ============================================================================
+    it("fails for a timeframe the asset is not enabled for", async () => {
+      const newExpiry = new BN(Math.floor(Date.now() / 1000) + 600);
+      const [blockedMarketPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("market"),
+          Buffer.from("LINK"),
+          Buffer.from("5m"),
+          newExpiry.toArrayLike(Buffer, "le", 8),
+          Buffer.from([0]),
+        ],
+        program.programId
+      );
+      const blockedVault = await anchor.utils.token.associatedAddress({
+        mint: usdcMint,
+        owner: blockedMarketPda,
+      });
 
-  describe("place_order", () => {
-    it("successfully validates a valid limit order", async () => {
-      const orderArgs = {
-        side: { bid: {} },
-        outcome: { yes: {} },
-        orderType: { limit: {} },
-        price: new BN(400_000), // $0.40
-        size: new BN(100),
-        expiryTs: new BN(Math.floor(Date.now() / 1000) + 3600), // 1 hour
-        clientOrderId: new BN(Date.now()),
-      };
+      try {
+        await program.methods
+          .initializeMarket("LINK", "5m", new BN(20_00000000), newExpiry, 0)
+          .accounts({
+            globalState: globalStatePda,
+            assetConfig: getAssetConfigPda("LINK"),
+            timeframeConfig: getTimeframeConfigPda("5m"),
+            market: blockedMarketPda,
+            marketGroup: getMarketGroupPda("LINK", "5m", newExpiry),
+            marketSeries: getMarketSeriesPda("LINK", "5m"),
+            vault: blockedVault,
+            usdcMint: usdcMint,
+            authority: relayer.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([relayer])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("TimeframeNotAllowed");
+      }
+    });
+
+    it("creates oracle-activated markets pending and rejects manual activation", async () => {
+      const expiry = alignedExpiry(3600, 3600);
+      const [oracleMarketPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("market"),
+          Buffer.from("DOGE"),
+          Buffer.from("1h"),
+          expiry.toArrayLike(Buffer, "le", 8),
+          Buffer.from([0]),
+        ],
+        program.programId
+      );
+      const oracleVault = await anchor.utils.token.associatedAddress({
+        mint: usdcMint,
+        owner: oracleMarketPda,
+      });
 
       await program.methods
-        .placeOrder(orderArgs)
+        .initializeMarket("DOGE", "1h", new BN(15000000), expiry, 0)
         .accounts({
           globalState: globalStatePda,
-          market: marketPda,
-          user: user1.publicKey,
+          assetConfig: getAssetConfigPda("DOGE"),
+          timeframeConfig: getTimeframeConfigPda("1h"),
+          market: oracleMarketPda,
+          marketGroup: getMarketGroupPda("DOGE", "1h", expiry),
+          marketSeries: getMarketSeriesPda("DOGE", "1h"),
+          vault: oracleVault,
+          usdcMint: usdcMint,
+          authority: relayer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
         })
-        .signers([user1])
+        .signers([relayer])
         .rpc();
 
-      // If no error, validation passed
-      console.log("  Order validated successfully");
-    });
-
-    it("fails with invalid price (< $0.01)", async () => {
-      const orderArgs = {
-        side: { bid: {} },
-        outcome: { yes: {} },
-        orderType: { limit: {} },
-        price: new BN(5_000), // $0.005 - below minimum
-        size: new BN(100),
-        expiryTs: new BN(Math.floor(Date.now() / 1000) + 3600),
-        clientOrderId: new BN(Date.now()),
-      };
+      const market = await program.account.market.fetch(oracleMarketPda);
+      expect(market.status).to.deep.equal({ pending: {} });
+      expect(market.strikePrice.toNumber()).to.equal(0);
+      expect(market.oracleActivation).to.be.true;
 
       try {
         await program.methods
-          .placeOrder(orderArgs)
+          .activateMarket(new BN(15000000))
           .accounts({
-            globalState: globalStatePda,
-            market: marketPda,
-            user: user1.publicKey,
+            market: oracleMarketPda,
+            assetConfig: getAssetConfigPda("DOGE"),
+            marketSeries: getMarketSeriesPda("DOGE", "1h"),
+            authority: relayer.publicKey,
           })
-          .signers([user1])
+          .signers([relayer])
           .rpc();
         expect.fail("Should have thrown an error");
       } catch (err: any) {
-        expect(err.error?.errorCode?.code || err.message).to.satisfy(
-          (msg: string) => msg.includes("InvalidPrice") || msg.includes("price")
-        );
+        expect(err.error?.errorCode?.code || err.message).to.include("OracleActivationRequired");
       }
     });
 
-    it("fails with invalid price (> $0.99)", async () => {
-      const orderArgs = {
-        side: { bid: {} },
-        outcome: { yes: {} },
-        orderType: { limit: {} },
-        price: new BN(995_000), // $0.995 - above maximum
-        size: new BN(100),
-        expiryTs: new BN(Math.floor(Date.now() / 1000) + 3600),
-        clientOrderId: new BN(Date.now()),
-      };
+    it("fails with expiry in the past", async () => {
+      const pastExpiry = new BN(Math.floor(Date.now() / 1000) - 60); // 1 minute ago
+      
+      const [invalidMarketPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("market"),
+          Buffer.from("ETH"),
+          Buffer.from(TIMEFRAME),
+          pastExpiry.toArrayLike(Buffer, "le", 8),
+          Buffer.from([0]),
+        ],
+        program.programId
+      );
+
+      const invalidVault = await anchor.utils.token.associatedAddress({
+        mint: usdcMint,
+        owner: invalidMarketPda,
+      });
 
       try {
         await program.methods
-          .placeOrder(orderArgs)
+          .initializeMarket("ETH", TIMEFRAME, STRIKE_PRICE, pastExpiry, 0)
           .accounts({
             globalState: globalStatePda,
-            market: marketPda,
-            user: user1.publicKey,
+            assetConfig: getAssetConfigPda("ETH"),
+            timeframeConfig: getTimeframeConfigPda(TIMEFRAME),
+            market: invalidMarketPda,
+            marketGroup: getMarketGroupPda("ETH", TIMEFRAME, pastExpiry),
+            marketSeries: getMarketSeriesPda("ETH", TIMEFRAME),
+            vault: invalidVault,
+            usdcMint: usdcMint,
+            authority: relayer.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
           })
-          .signers([user1])
+          .signers([relayer])
           .rpc();
         expect.fail("Should have thrown an error");
       } catch (err: any) {
         expect(err.error?.errorCode?.code || err.message).to.satisfy(
-          (msg: string) => msg.includes("InvalidPrice") || msg.includes("price")
+          (msg: string) => msg.includes("InvalidExpiry") || msg.includes("invalid")
         );
       }
     });
 
-    it("fails with invalid tick size (not $0.01 increment)", async () => {
-      const orderArgs = {
-        side: { bid: {} },
-        outcome: { yes: {} },
-        orderType: { limit: {} },
-        price: new BN(405_555), // $0.405555 - not on tick grid
-        size: new BN(100),
-        expiryTs: new BN(Math.floor(Date.now() / 1000) + 3600),
-        clientOrderId: new BN(Date.now()),
-      };
+    describe("strike ladders", () => {
+      const ladderExpiry = alignedExpiry(3600, 2 * 3600);
+
+      const createLadderMarket = async (strikeIndex: number, strike: BN, signer = relayer) => {
+        const [pda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("market"),
+            Buffer.from("BTC"),
+            Buffer.from("1h"),
+            ladderExpiry.toArrayLike(Buffer, "le", 8),
+            Buffer.from([strikeIndex]),
+          ],
+          program.programId
+        );
+        const vault = await anchor.utils.token.associatedAddress({ mint: usdcMint, owner: pda });
 
-      try {
         await program.methods
-          .placeOrder(orderArgs)
+          .initializeMarket("BTC", "1h", strike, ladderExpiry, strikeIndex)
           .accounts({
             globalState: globalStatePda,
-            market: marketPda,
-            user: user1.publicKey,
-          })
-          .signers([user1])
+            assetConfig: getAssetConfigPda("BTC"),
+            timeframeConfig: getTimeframeConfigPda("1h"),
+            market: pda,
+            marketGroup: getMarketGroupPda("BTC", "1h", ladderExpiry),
+            marketSeries: getMarketSeriesPda("BTC", "1h"),
+            vault,
+            usdcMint: usdcMint,
+            authority: signer.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([signer])
+          .rpc();
+
+        return pda;
+      };
+
+      it("lists several strikes on the same expiry", async () => {
+        const markets = [
+          await createLadderMarket(0, new BN(94_000_00000000)),
+          await createLadderMarket(1, new BN(95_000_00000000)),
+          await createLadderMarket(2, new BN(96_000_00000000)),
+        ];
+
+        const group = await program.account.marketGroup.fetch(getMarketGroupPda("BTC", "1h", ladderExpiry));
+        expect(group.numMarkets).to.equal(3);
+        expect(group.expiryAt.toString()).to.equal(ladderExpiry.toString());
+        expect(group.creator.toBase58()).to.equal(relayer.publicKey.toBase58());
+        markets.forEach((market, i) => {
+          expect(group.markets[i].toBase58()).to.equal(market.toBase58());
+        });
+
+        const top = await program.account.market.fetch(markets[2]);
+        expect(top.strikeIndex).to.equal(2);
+        expect(top.strikePrice.toString()).to.equal(new BN(96_000_00000000).toString());
+      });
+
+      it("fails with a strike index that skips a slot", async () => {
+        try {
+          await createLadderMarket(5, new BN(99_000_00000000));
+          expect.fail("Should have thrown an error");
+        } catch (err: any) {
+          expect(err.error?.errorCode?.code || err.message).to.include("InvalidStrikeIndex");
+        }
+      });
+
+      it("fails when someone other than the creator adds a strike", async () => {
+        try {
+          await createLadderMarket(3, new BN(97_000_00000000), user1);
+          expect.fail("Should have thrown an error");
+        } catch (err: any) {
+          expect(err.error?.errorCode?.code || err.message).to.include("Unauthorized");
+        }
+      });
+    });
+  });
+
+  // ============================================================================
+  // ACTIVATE MARKET WITH ATTESTATION TESTS
+  // ============================================================================
+
+  describe("activate_market_with_attestation", () => {
+    const publisher = Keypair.generate();
+    let pendingMarketPda: PublicKey;
+
+    const currentTime = async () => {
+      const slot = await provider.connection.getSlot();
+      return new BN((await provider.connection.getBlockTime(slot))!);
+    };
+
+    before(async () => {
+      await program.methods
+        .setPricePublishers([publisher.publicKey])
+        .accounts({
+          globalState: globalStatePda,
+          resolverConfig: resolverConfigPda,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+
+      // Pending market (strike = 0) to be activated by attestation
+      const expiry = alignedExpiry(24 * 3600, 3600);
+      [pendingMarketPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("market"),
+          Buffer.from("ETH"),
+          Buffer.from("24h"),
+          expiry.toArrayLike(Buffer, "le", 8),
+          Buffer.from([0]),
+        ],
+        program.programId
+      );
+      const vault = await anchor.utils.token.associatedAddress({ mint: usdcMint, owner: pendingMarketPda });
+
+      await program.methods
+        .initializeMarket("ETH", "24h", new BN(0), expiry, 0)
+        .accounts({
+          globalState: globalStatePda,
+          assetConfig: getAssetConfigPda("ETH"),
+          timeframeConfig: getTimeframeConfigPda("24h"),
+          market: pendingMarketPda,
+          marketGroup: getMarketGroupPda("ETH", "24h", expiry),
+          marketSeries: getMarketSeriesPda("ETH", "24h"),
+          vault,
+          usdcMint: usdcMint,
+          authority: relayer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([relayer])
+        .rpc();
+    });
+
+    const activateAccounts = () => ({
+      market: pendingMarketPda,
+      assetConfig: getAssetConfigPda("ETH"),
+      marketSeries: getMarketSeriesPda("ETH", "24h"),
+      resolverConfig: resolverConfigPda,
+      instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      authority: keeper.publicKey,
+    });
+
+    it("fails without an Ed25519 verification instruction", async () => {
+      const publishTime = await currentTime();
+      try {
+        await program.methods
+          .activateMarketWithAttestation(new BN(3000_00000000), publishTime)
+          .accounts(activateAccounts())
+          .signers([keeper])
           .rpc();
         expect.fail("Should have thrown an error");
       } catch (err: any) {
-        expect(err.error?.errorCode?.code || err.message).to.satisfy(
-          (msg: string) => msg.includes("InvalidTickSize") || msg.includes("tick")
-        );
+        expect(err.error?.errorCode?.code || err.message).to.include("MissingSignatureVerification");
       }
     });
 
-    it("fails with invalid size (0)", async () => {
+    it("fails to whitelist the same price publisher twice", async () => {
+      try {
+        await program.methods
+          .setPricePublishers([publisher.publicKey, publisher.publicKey])
+          .accounts({
+            globalState: globalStatePda,
+            resolverConfig: resolverConfigPda,
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([admin])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("InvalidPricePublisher");
+      }
+    });
+
+    it("fails to activate an oracle-activated market before its start", async () => {
+      // DOGE requires oracle activation; this market starts in at least an hour
+      const expiry = alignedExpiry(3600, 2 * 3600);
+      const [dogeMarketPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("market"),
+          Buffer.from("DOGE"),
+          Buffer.from("1h"),
+          expiry.toArrayLike(Buffer, "le", 8),
+          Buffer.from([0]),
+        ],
+        program.programId
+      );
+      const vault = await anchor.utils.token.associatedAddress({ mint: usdcMint, owner: dogeMarketPda });
+
+      await program.methods
+        .initializeMarket("DOGE", "1h", new BN(0), expiry, 0)
+        .accounts({
+          globalState: globalStatePda,
+          assetConfig: getAssetConfigPda("DOGE"),
+          timeframeConfig: getTimeframeConfigPda("1h"),
+          market: dogeMarketPda,
+          marketGroup: getMarketGroupPda("DOGE", "1h", expiry),
+          marketSeries: getMarketSeriesPda("DOGE", "1h"),
+          vault,
+          usdcMint: usdcMint,
+          authority: relayer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([relayer])
+        .rpc();
+
+      const strike = new BN(15000000);
+      const publishTime = await currentTime();
+
+      try {
+        await program.methods
+          .activateMarketWithAttestation(strike, publishTime)
+          .accounts({
+            ...activateAccounts(),
+            market: dogeMarketPda,
+            assetConfig: getAssetConfigPda("DOGE"),
+            marketSeries: getMarketSeriesPda("DOGE", "1h"),
+          })
+          .preInstructions([
+            Ed25519Program.createInstructionWithPrivateKey({
+              privateKey: publisher.secretKey,
+              message: attestationMessage(dogeMarketPda, 0, strike, publishTime),
+            }),
+          ])
+          .signers([keeper])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("MarketNotStarted");
+      }
+    });
+
+    it("fails when signed by a non-whitelisted key", async () => {
+      const impostor = Keypair.generate();
+      const strike = new BN(3000_00000000);
+      const publishTime = await currentTime();
+
+      try {
+        await program.methods
+          .activateMarketWithAttestation(strike, publishTime)
+          .accounts(activateAccounts())
+          .preInstructions([
+            Ed25519Program.createInstructionWithPrivateKey({
+              privateKey: impostor.secretKey,
+              message: attestationMessage(pendingMarketPda, 0, strike, publishTime),
+            }),
+          ])
+          .signers([keeper])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("SignerMismatch");
+      }
+    });
+
+    it("fails when the attested price does not match the argument", async () => {
+      const publishTime = await currentTime();
+
+      try {
+        await program.methods
+          .activateMarketWithAttestation(new BN(3000_00000000), publishTime)
+          .accounts(activateAccounts())
+          .preInstructions([
+            Ed25519Program.createInstructionWithPrivateKey({
+              privateKey: publisher.secretKey,
+              message: attestationMessage(pendingMarketPda, 0, new BN(2000_00000000), publishTime),
+            }),
+          ])
+          .signers([keeper])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("InvalidSignature");
+      }
+    });
+
+    it("successfully activates with a publisher-signed strike price", async () => {
+      const strike = new BN(3000_00000000);
+      const publishTime = await currentTime();
+
+      await program.methods
+        .activateMarketWithAttestation(strike, publishTime)
+        .accounts(activateAccounts())
+        .preInstructions([
+          Ed25519Program.createInstructionWithPrivateKey({
+            privateKey: publisher.secretKey,
+            message: attestationMessage(pendingMarketPda, 0, strike, publishTime),
+          }),
+        ])
+        .signers([keeper])
+        .rpc();
+
+      const market = await program.account.market.fetch(pendingMarketPda);
+      expect(market.status).to.deep.equal({ open: {} });
+      expect(market.strikePrice.toString()).to.equal(strike.toString());
+    });
+  });
+
+  // ============================================================================
+  // ACTIVATE MARKET WITH ORACLE TESTS
+  // ============================================================================
+
+  describe("activate_market_with_oracle", () => {
+    const createPendingMarket = async (timeframe: string, expiry: BN): Promise<PublicKey> => {
+      const [pda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("market"),
+          Buffer.from("BTC"),
+          Buffer.from(timeframe),
+          expiry.toArrayLike(Buffer, "le", 8),
+          Buffer.from([0]),
+        ],
+        program.programId
+      );
+      const vault = await anchor.utils.token.associatedAddress({ mint: usdcMint, owner: pda });
+
+      await program.methods
+        .initializeMarket("BTC", timeframe, new BN(0), expiry, 0)
+        .accounts({
+          globalState: globalStatePda,
+          assetConfig: getAssetConfigPda("BTC"),
+          timeframeConfig: getTimeframeConfigPda(timeframe),
+          market: pda,
+          marketGroup: getMarketGroupPda("BTC", timeframe, expiry),
+          marketSeries: getMarketSeriesPda("BTC", timeframe),
+          vault,
+          usdcMint: usdcMint,
+          authority: relayer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([relayer])
+        .rpc();
+
+      return pda;
+    };
+
+    it("fails before the market's start time", async () => {
+      // 24h market expiring in 25h starts in 1h
+      const expiry = alignedExpiry(24 * 3600, 25 * 3600);
+      const futureMarketPda = await createPendingMarket("24h", expiry);
+
+      try {
+        await program.methods
+          .activateMarketWithOracle()
+          .accounts({
+            market: futureMarketPda,
+            assetConfig: getAssetConfigPda("BTC"),
+            marketSeries: getMarketSeriesPda("BTC", "24h"),
+            priceUpdate: Keypair.generate().publicKey,
+            authority: keeper.publicKey,
+          })
+          .signers([keeper])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("MarketNotStarted");
+      }
+    });
+
+    it("fails with a price account not owned by the Pyth receiver", async () => {
+      // 1m market on the next grid expiry - wait for its window to start
+      const expiry = alignedExpiry(60, 10);
+      const startedMarketPda = await createPendingMarket("1m", expiry);
+      await new Promise(resolve => setTimeout(resolve, Math.max(0, expiry.toNumber() - 60 - Math.floor(Date.now() / 1000) + 1) * 1000));
+
+      try {
+        await program.methods
+          .activateMarketWithOracle()
+          .accounts({
+            market: startedMarketPda,
+            assetConfig: getAssetConfigPda("BTC"),
+            marketSeries: getMarketSeriesPda("BTC", "1m"),
+            priceUpdate: Keypair.generate().publicKey,
+            authority: keeper.publicKey,
+          })
+          .signers([keeper])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("InvalidOracle");
+      }
+    });
+  });
+
+  // ============================================================================
+  // PLACE ORDER TESTS
+  // ============================================================================
+
+  describe("place_order", () => {
+    it("successfully validates a valid limit order", async () => {
       const orderArgs = {
         side: { bid: {} },
         outcome: { yes: {} },
         orderType: { limit: {} },
-        price: new BN(500_000), // $0.50
-        size: new BN(0), // Invalid
+        price: new BN(400_000), // $0.40
+        size: new BN(100),
+        expiryTs: new BN(Math.floor(Date.now() / 1000) + 3600), // 1 hour
+        clientOrderId: new BN(Date.now()),
+      };
+
+      await program.methods
+        .placeOrder(orderArgs)
+        .accounts({
+          globalState: globalStatePda,
+          market: marketPda,
+          user: user1.publicKey,
+        })
+        .signers([user1])
+        .rpc();
+
+      // If no error, validation passed
+      console.log("  Order validated successfully");
+    });
+
+    it("fails with invalid price (< $0.01)", async () => {
+      const orderArgs = {
+        side: { bid: {} },
+        outcome: { yes: {} },
+        orderType: { limit: {} },
+        price: new BN(5_000), // $0.005 - below minimum
+        size: new BN(100),
         expiryTs: new BN(Math.floor(Date.now() / 1000) + 3600),
         clientOrderId: new BN(Date.now()),
       };
@@ -537,18 +1304,18 @@ describe("degen-terminal", () => {
         expect.fail("Should have thrown an error");
       } catch (err: any) {
         expect(err.error?.errorCode?.code || err.message).to.satisfy(
-          (msg: string) => msg.includes("InvalidSize") || msg.includes("size")
+          (msg: string) => msg.includes("InvalidPrice") || msg.includes("price")
         );
       }
     });
 
-    it("fails with size exceeding maximum (> 100,000)", async () => {
+    it("fails with invalid price (> $0.99)", async () => {
       const orderArgs = {
         side: { bid: {} },
         outcome: { yes: {} },
         orderType: { limit: {} },
-        price: new BN(500_000), // $0.50
-        size: new BN(100_001), // Exceeds max
+        price: new BN(995_000), // $0.995 - above maximum
+        size: new BN(100),
         expiryTs: new BN(Math.floor(Date.now() / 1000) + 3600),
         clientOrderId: new BN(Date.now()),
       };
@@ -566,19 +1333,19 @@ describe("degen-terminal", () => {
         expect.fail("Should have thrown an error");
       } catch (err: any) {
         expect(err.error?.errorCode?.code || err.message).to.satisfy(
-          (msg: string) => msg.includes("InvalidSize") || msg.includes("size")
+          (msg: string) => msg.includes("InvalidPrice") || msg.includes("price")
         );
       }
     });
 
-    it("fails with expired order", async () => {
+    it("fails with invalid tick size (not $0.01 increment)", async () => {
       const orderArgs = {
         side: { bid: {} },
         outcome: { yes: {} },
         orderType: { limit: {} },
-        price: new BN(500_000),
+        price: new BN(405_555), // $0.405555 - not on tick grid
         size: new BN(100),
-        expiryTs: new BN(Math.floor(Date.now() / 1000) - 60), // Already expired
+        expiryTs: new BN(Math.floor(Date.now() / 1000) + 3600),
         clientOrderId: new BN(Date.now()),
       };
 
@@ -595,19 +1362,106 @@ describe("degen-terminal", () => {
         expect.fail("Should have thrown an error");
       } catch (err: any) {
         expect(err.error?.errorCode?.code || err.message).to.satisfy(
-          (msg: string) => msg.includes("OrderExpired") || msg.includes("expired")
+          (msg: string) => msg.includes("InvalidTickSize") || msg.includes("tick")
         );
       }
     });
-  });
-
-  // ============================================================================
-  // EXECUTE MATCH TESTS
-  // ============================================================================
 
-  describe("execute_match", () => {
-    let user1PositionPda: PublicKey;
-    let user2PositionPda: PublicKey;
+    it("fails with invalid size (0)", async () => {
+      const orderArgs = {
+        side: { bid: {} },
+        outcome: { yes: {} },
+        orderType: { limit: {} },
+        price: new BN(500_000), // $0.50
+        size: new BN(0), // Invalid
+        expiryTs: new BN(Math.floor(Date.now() / 1000) + 3600),
+        clientOrderId: new BN(Date.now()),
+      };
+
+      try {
+        await program.methods
+          .placeOrder(orderArgs)
+          .accounts({
+            globalState: globalStatePda,
+            market: marketPda,
+            user: user1.publicKey,
+          })
+          .signers([user1])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.satisfy(
+          (msg: string) => msg.includes("InvalidSize") || msg.includes("size")
+        );
+      }
+    });
+
+    it("fails with size exceeding maximum (> 100,000)", async () => {
+      const orderArgs = {
+        side: { bid: {} },
+        outcome: { yes: {} },
+        orderType: { limit: {} },
+        price: new BN(500_000), // $0.50
+        size: new BN(100_001), // Exceeds max
+        expiryTs: new BN(Math.floor(Date.now() / 1000) + 3600),
+        clientOrderId: new BN(Date.now()),
+      };
+
+      try {
+        await program.methods
+          .placeOrder(orderArgs)
+          .accounts({
+            globalState: globalStatePda,
+            market: marketPda,
+            user: user1.publicKey,
+          })
+          .signers([user1])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.satisfy(
+          (msg: string) => msg.includes("InvalidSize") || msg.includes("size")
+        );
+      }
+    });
+
+    it("fails with expired order", async () => {
+      const orderArgs = {
+        side: { bid: {} },
+        outcome: { yes: {} },
+        orderType: { limit: {} },
+        price: new BN(500_000),
+        size: new BN(100),
+        expiryTs: new BN(Math.floor(Date.now() / 1000) - 60), // Already expired
+        clientOrderId: new BN(Date.now()),
+      };
+
+      try {
+        await program.methods
+          .placeOrder(orderArgs)
+          .accounts({
+            globalState: globalStatePda,
+            market: marketPda,
+            user: user1.publicKey,
+          })
+          .signers([user1])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.satisfy(
+          (msg: string) => msg.includes("OrderExpired") || msg.includes("expired")
+        );
+      }
+    });
+  });
+
+  // ============================================================================
+  // EXECUTE MATCH TESTS
+  // ============================================================================
+
+  describe("execute_match", () => {
+    let user1PositionPda: PublicKey;
+    let user2PositionPda: PublicKey;
 
     beforeEach(async () => {
       // Derive position PDAs
@@ -910,116 +1764,1000 @@ describe("degen-terminal", () => {
   });
 
   // ============================================================================
-  // ADMIN FUNCTIONS TESTS
+  // BATCH MATCH TESTS
   // ============================================================================
 
-  describe("admin functions", () => {
-    describe("pause_protocol", () => {
-      it("successfully pauses the protocol", async () => {
-        await program.methods
-          .pauseProtocol(true, "Scheduled maintenance")
-          .accounts({
-            globalState: globalStatePda,
-            admin: admin.publicKey,
-          })
-          .signers([admin])
-          .rpc();
+  describe("execute_batch_match", () => {
+    let user1PositionPda: PublicKey;
+    let user2PositionPda: PublicKey;
 
-        const globalState = await program.account.globalState.fetch(globalStatePda);
-        expect(globalState.paused).to.be.true;
-        expect(globalState.pausedAt.toNumber()).to.be.greaterThan(0);
-      });
+    const placeOrderIx = async (user: Keypair, userUsdc: PublicKey, side: any, orderType: any, price: BN, size: BN) => {
+      const clientOrderId = new BN(Date.now() + Math.floor(Math.random() * 1000));
+      const [orderPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("order"), marketPda.toBuffer(), user.publicKey.toBuffer(), clientOrderId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
 
-      it("fails to place orders when paused", async () => {
-        const orderArgs = {
-          side: { bid: {} },
+      const ix = await program.methods
+        .placeOrder({
+          side,
           outcome: { yes: {} },
-          orderType: { limit: {} },
-          price: new BN(500_000),
-          size: new BN(100),
+          orderType,
+          price,
+          size,
           expiryTs: new BN(Math.floor(Date.now() / 1000) + 3600),
-          clientOrderId: new BN(Date.now()),
-        };
+          clientOrderId,
+        })
+        .accounts({
+          globalState: globalStatePda,
+          market: marketPda,
+          order: orderPda,
+          vault: vaultPda,
+          userUsdc,
+          user: user.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction();
+
+      return { orderPda, ix };
+    };
+
+    // Resting limit ask from user2
+    const placeAsk = async (price: BN, size: BN) => {
+      const { orderPda, ix } = await placeOrderIx(user2, user2Usdc, { ask: {} }, { limit: {} }, price, size);
+      await provider.sendAndConfirm(new Transaction().add(ix), [user2]);
+      return orderPda;
+    };
+
+    // IOC bid from user1 swept against the given asks in one transaction
+    const sweep = async (asks: PublicKey[], matchSizes: BN[], remainingAccounts?: any[]) => {
+      const { orderPda: takerOrder, ix: placeIx } = await placeOrderIx(
+        user1, user1Usdc, { bid: {} }, { ioc: {} }, new BN(450_000), new BN(10_000_000)
+      );
+      const takerArgs = {
+        side: { bid: {} },
+        outcome: { yes: {} },
+        orderType: { limit: {} },
+        price: new BN(0),
+        size: new BN(0),
+        expiryTs: new BN(0),
+        clientOrderId: new BN(0),
+      };
 
-        try {
-          await program.methods
-            .placeOrder(orderArgs)
-            .accounts({
-              globalState: globalStatePda,
-              market: marketPda,
-              user: user1.publicKey,
-            })
-            .signers([user1])
-            .rpc();
-          expect.fail("Should have thrown an error");
-        } catch (err: any) {
-          expect(err.error?.errorCode?.code || err.message).to.satisfy(
-            (msg: string) => msg.includes("ProtocolPaused") || msg.includes("paused")
-          );
-        }
-      });
+      await program.methods
+        .executeBatchMatch(takerArgs, matchSizes)
+        .accounts({
+          globalState: globalStatePda,
+          market: marketPda,
+          vault: vaultPda,
+          feeRecipient: feeRecipientUsdc,
+          taker: user1.publicKey,
+          takerPosition: user1PositionPda,
+          takerUsdc: user1Usdc,
+          takerOrder,
+          relayer: relayer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+          remainingAccounts ??
+            asks.flatMap((ask) => [
+              { pubkey: ask, isWritable: true, isSigner: false },
+              { pubkey: user2PositionPda, isWritable: true, isSigner: false },
+              { pubkey: user2Usdc, isWritable: true, isSigner: false },
+            ])
+        )
+        .preInstructions([placeIx])
+        .signers([user1, relayer])
+        .rpc();
 
-      it("successfully unpauses the protocol", async () => {
-        await program.methods
-          .pauseProtocol(false, null)
-          .accounts({
-            globalState: globalStatePda,
-            admin: admin.publicKey,
-          })
-          .signers([admin])
-          .rpc();
+      return takerOrder;
+    };
 
-        const globalState = await program.account.globalState.fetch(globalStatePda);
-        expect(globalState.paused).to.be.false;
-        expect(globalState.pausedAt.toNumber()).to.equal(0);
-      });
+    before(async () => {
+      [user1PositionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position"), marketPda.toBuffer(), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      [user2PositionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position"), marketPda.toBuffer(), user2.publicKey.toBuffer()],
+        program.programId
+      );
+    });
 
-      it("fails when non-admin tries to pause", async () => {
-        try {
-          await program.methods
-            .pauseProtocol(true, "Unauthorized pause")
-            .accounts({
-              globalState: globalStatePda,
-              admin: user1.publicKey, // Not admin
-            })
-            .signers([user1])
-            .rpc();
-          expect.fail("Should have thrown an error");
-        } catch (err: any) {
-          expect(err.error?.errorCode?.code || err.message).to.satisfy(
-            (msg: string) => msg.includes("Unauthorized") || msg.includes("unauthorized") || msg.includes("constraint")
-          );
-        }
-      });
+    it("sweeps two price levels with one taker", async () => {
+      const firstAsk = await placeAsk(new BN(400_000), new BN(3_000_000));
+      const secondAsk = await placeAsk(new BN(420_000), new BN(3_000_000));
+
+      const takerBefore = await program.account.userPosition.fetch(user1PositionPda);
+      const makerBefore = await program.account.userPosition.fetch(user2PositionPda);
+
+      const takerOrder = await sweep([firstAsk, secondAsk], [new BN(3_000_000), new BN(3_000_000)]);
+
+      const takerAfter = await program.account.userPosition.fetch(user1PositionPda);
+      const makerAfter = await program.account.userPosition.fetch(user2PositionPda);
+
+      // Both legs land on the same maker position, each at its own price
+      expect(takerAfter.yesShares.sub(takerBefore.yesShares).toNumber()).to.equal(6_000_000);
+      expect(makerAfter.noShares.sub(makerBefore.noShares).toNumber()).to.equal(6_000_000);
+      expect(makerAfter.noCostBasis.sub(makerBefore.noCostBasis).toNumber()).to.equal(1_800_000 + 1_740_000);
+
+      expect((await program.account.order.fetch(firstAsk)).status).to.deep.equal({ filled: {} });
+      expect((await program.account.order.fetch(secondAsk)).status).to.deep.equal({ filled: {} });
+
+      // The IOC taker's unfilled 4 contracts are cancelled
+      const taker = await program.account.order.fetch(takerOrder);
+      expect(taker.filledSize.toNumber()).to.equal(6_000_000);
+      expect(taker.status).to.deep.equal({ cancelled: {} });
     });
 
-    describe("update_fees", () => {
-      it("successfully updates fees", async () => {
-        const newMakerFee = 5;   // 0.05%
-        const newTakerFee = 15;  // 0.15%
+    it("fails when a maker does not cross the taker", async () => {
+      const ask = await placeAsk(new BN(480_000), new BN(3_000_000));
 
-        await program.methods
-          .updateFees(newMakerFee, newTakerFee)
-          .accounts({
-            globalState: globalStatePda,
-            admin: admin.publicKey,
+      try {
+        await sweep([ask], [new BN(3_000_000)]);
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("PriceMismatch");
+      }
+    });
+
+    it("fails without a full account triple per maker", async () => {
+      const ask = await placeAsk(new BN(400_000), new BN(3_000_000));
+
+      try {
+        await sweep([ask], [new BN(3_000_000)], [{ pubkey: ask, isWritable: true, isSigner: false }]);
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("InvalidBatchAccounts");
+      }
+    });
+
+    it("creates a maker position whose address was pre-funded", async () => {
+      const maker = Keypair.generate();
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(maker.publicKey, 2 * LAMPORTS_PER_SOL)
+      );
+      const makerUsdc = await createAssociatedTokenAccount(provider.connection, admin, usdcMint, maker.publicKey);
+      await mintTo(provider.connection, admin, usdcMint, makerUsdc, admin, 100 * USDC_MULTIPLIER);
+
+      const [makerPosition] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position"), marketPda.toBuffer(), maker.publicKey.toBuffer()],
+        program.programId
+      );
+
+      // A plain transfer to the PDA would make create_account fail
+      await provider.sendAndConfirm(
+        new Transaction().add(
+          SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            toPubkey: makerPosition,
+            lamports: 1_000_000,
           })
-          .signers([admin])
-          .rpc();
+        )
+      );
 
-        const globalState = await program.account.globalState.fetch(globalStatePda);
-        expect(globalState.makerFeeBps).to.equal(newMakerFee);
-        expect(globalState.takerFeeBps).to.equal(newTakerFee);
-      });
+      const { orderPda: ask, ix } = await placeOrderIx(maker, makerUsdc, { ask: {} }, { limit: {} }, new BN(400_000), new BN(3_000_000));
+      await provider.sendAndConfirm(new Transaction().add(ix), [maker]);
 
-      it("fails with fees exceeding maximum (5%)", async () => {
-        try {
-          await program.methods
-            .updateFees(0, 501) // 5.01% - too high
-            .accounts({
-              globalState: globalStatePda,
-              admin: admin.publicKey,
-            })
+      await sweep([ask], [new BN(3_000_000)], [
+        { pubkey: ask, isWritable: true, isSigner: false },
+        { pubkey: makerPosition, isWritable: true, isSigner: false },
+        { pubkey: makerUsdc, isWritable: true, isSigner: false },
+      ]);
+
+      const position = await program.account.userPosition.fetch(makerPosition);
+      expect(position.owner.toBase58()).to.equal(maker.publicKey.toBase58());
+      expect(position.noShares.toNumber()).to.equal(3_000_000);
+    });
+  });
+
+  // ============================================================================
+  // ORDER TYPE TESTS
+  // ============================================================================
+
+  describe("order types", () => {
+    const price = new BN(400_000); // $0.40 for YES
+    let user1PositionPda: PublicKey;
+    let user2PositionPda: PublicKey;
+
+    const placeOrderIx = async (user: Keypair, userUsdc: PublicKey, side: any, orderType: any, size: BN) => {
+      const clientOrderId = new BN(Date.now() + Math.floor(Math.random() * 1000));
+      const [orderPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("order"), marketPda.toBuffer(), user.publicKey.toBuffer(), clientOrderId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      const ix = await program.methods
+        .placeOrder({
+          side,
+          outcome: { yes: {} },
+          orderType,
+          price,
+          size,
+          expiryTs: new BN(Math.floor(Date.now() / 1000) + 3600),
+          clientOrderId,
+        })
+        .accounts({
+          globalState: globalStatePda,
+          market: marketPda,
+          order: orderPda,
+          vault: vaultPda,
+          userUsdc,
+          user: user.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction();
+
+      return { orderPda, ix };
+    };
+
+    // Resting limit ask from user2 (the maker)
+    const placeRestingAsk = async (size: BN) => {
+      const { orderPda, ix } = await placeOrderIx(user2, user2Usdc, { ask: {} }, { limit: {} }, size);
+      await provider.sendAndConfirm(new Transaction().add(ix), [user2]);
+      return orderPda;
+    };
+
+    // Relayer match of the resting ask against user1's order
+    const executeMatch = (makerOrder: PublicKey, takerOrder: PublicKey, matchSize: BN) => {
+      const orderArgs = {
+        side: { bid: {} },
+        outcome: { yes: {} },
+        orderType: { limit: {} },
+        price,
+        size: matchSize,
+        expiryTs: new BN(0),
+        clientOrderId: new BN(0),
+      };
+
+      return program.methods
+        .executeMatch(orderArgs, orderArgs, matchSize)
+        .accounts({
+          globalState: globalStatePda,
+          market: marketPda,
+          vault: vaultPda,
+          feeRecipient: feeRecipientUsdc,
+          maker: user2.publicKey,
+          makerPosition: user2PositionPda,
+          makerUsdc: user2Usdc,
+          makerOrder,
+          taker: user1.publicKey,
+          takerPosition: user1PositionPda,
+          takerUsdc: user1Usdc,
+          takerOrder,
+          sellerUsdcReceive: null,
+          relayer: relayer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        });
+    };
+
+    before(async () => {
+      [user1PositionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position"), marketPda.toBuffer(), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      [user2PositionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position"), marketPda.toBuffer(), user2.publicKey.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("refunds the unfilled remainder of an IOC order matched in its placement transaction", async () => {
+      const makerOrder = await placeRestingAsk(new BN(4_000_000)); // 4 contracts resting
+
+      // IOC bid for 10 contracts locks $4.00, only 4 contracts ($1.60) can fill
+      const user1BalanceBefore = (await getAccount(provider.connection, user1Usdc)).amount;
+      const { orderPda: takerOrder, ix: placeIx } = await placeOrderIx(user1, user1Usdc, { bid: {} }, { ioc: {} }, new BN(10_000_000));
+      await executeMatch(makerOrder, takerOrder, new BN(4_000_000))
+        .preInstructions([placeIx])
+        .signers([user1, relayer])
+        .rpc();
+
+      const order = await program.account.order.fetch(takerOrder);
+      expect(order.filledSize.toNumber()).to.equal(4_000_000);
+      expect(order.status).to.deep.equal({ cancelled: {} });
+
+      // Only the filled part (plus the taker fee) stays spent
+      const user1BalanceAfter = (await getAccount(provider.connection, user1Usdc)).amount;
+      const spent = Number(user1BalanceBefore - user1BalanceAfter);
+      expect(spent).to.be.at.least(1_600_000);
+      expect(spent).to.be.below(2_000_000);
+    });
+
+    it("fails to fill an order beyond its remaining size", async () => {
+      const makerOrder = await placeRestingAsk(new BN(40_000_000));
+      const { orderPda: takerOrder, ix: placeIx } = await placeOrderIx(user1, user1Usdc, { bid: {} }, { limit: {} }, new BN(4_000_000));
+      await provider.sendAndConfirm(new Transaction().add(placeIx), [user1]);
+
+      try {
+        // 10x the taker's size would draw on other users' escrow in the vault
+        await executeMatch(makerOrder, takerOrder, new BN(40_000_000)).signers([relayer]).rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("InvalidSize");
+      }
+    });
+
+    it("fails to match an IOC order after its placement slot", async () => {
+      const makerOrder = await placeRestingAsk(new BN(4_000_000));
+      const { orderPda: takerOrder, ix: placeIx } = await placeOrderIx(user1, user1Usdc, { bid: {} }, { ioc: {} }, new BN(4_000_000));
+      await provider.sendAndConfirm(new Transaction().add(placeIx), [user1]);
+
+      try {
+        await executeMatch(makerOrder, takerOrder, new BN(4_000_000)).signers([relayer]).rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("OrderExpired");
+      }
+
+      // The relayer can reclaim the expired order right away
+      await program.methods
+        .cancelOrderByRelayer()
+        .accounts({
+          market: marketPda,
+          order: takerOrder,
+          owner: user1.publicKey,
+          vault: vaultPda,
+          userUsdc: user1Usdc,
+          authority: relayer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([relayer])
+        .rpc();
+
+      const order = await program.account.order.fetch(takerOrder);
+      expect(order.status).to.deep.equal({ cancelled: {} });
+    });
+
+    it("fails to partially fill a FOK order", async () => {
+      const makerOrder = await placeRestingAsk(new BN(4_000_000));
+      const { orderPda: takerOrder, ix: placeIx } = await placeOrderIx(user1, user1Usdc, { bid: {} }, { fok: {} }, new BN(10_000_000));
+
+      try {
+        await executeMatch(makerOrder, takerOrder, new BN(4_000_000))
+          .preInstructions([placeIx])
+          .signers([user1, relayer])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("FillOrKillNotFilled");
+      }
+    });
+  });
+
+  // ============================================================================
+  // COMPLEMENTARY ORDER TESTS
+  // ============================================================================
+
+  describe("complementary orders", () => {
+    let user1PositionPda: PublicKey;
+    let user2PositionPda: PublicKey;
+
+    const placeBidIx = async (user: Keypair, userUsdc: PublicKey, outcome: any, price: BN, size: BN) => {
+      const clientOrderId = new BN(Date.now() + Math.floor(Math.random() * 1000));
+      const [orderPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("order"), marketPda.toBuffer(), user.publicKey.toBuffer(), clientOrderId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      const ix = await program.methods
+        .placeOrder({
+          side: { bid: {} },
+          outcome,
+          orderType: { limit: {} },
+          price,
+          size,
+          expiryTs: new BN(Math.floor(Date.now() / 1000) + 3600),
+          clientOrderId,
+        })
+        .accounts({
+          globalState: globalStatePda,
+          market: marketPda,
+          order: orderPda,
+          vault: vaultPda,
+          userUsdc,
+          user: user.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction();
+
+      return { orderPda, ix };
+    };
+
+    // Relayer match of user2's NO bid (maker) against user1's YES bid (taker)
+    const executeMatch = (makerOrder: PublicKey, takerOrder: PublicKey, matchSize: BN) => {
+      // Ignored - both sides have Order PDAs
+      const orderArgs = {
+        side: { bid: {} },
+        outcome: { yes: {} },
+        orderType: { limit: {} },
+        price: new BN(500_000),
+        size: matchSize,
+        expiryTs: new BN(0),
+        clientOrderId: new BN(0),
+      };
+
+      return program.methods
+        .executeMatch(orderArgs, orderArgs, matchSize)
+        .accounts({
+          globalState: globalStatePda,
+          market: marketPda,
+          vault: vaultPda,
+          feeRecipient: feeRecipientUsdc,
+          maker: user2.publicKey,
+          makerPosition: user2PositionPda,
+          makerUsdc: user2Usdc,
+          makerOrder,
+          taker: user1.publicKey,
+          takerPosition: user1PositionPda,
+          takerUsdc: user1Usdc,
+          takerOrder,
+          sellerUsdcReceive: null,
+          relayer: relayer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        });
+    };
+
+    before(async () => {
+      [user1PositionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position"), marketPda.toBuffer(), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      [user2PositionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position"), marketPda.toBuffer(), user2.publicKey.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("matches a YES bid against a NO bid into a new pair", async () => {
+      const size = new BN(5_000_000);
+      const maker = await placeBidIx(user2, user2Usdc, { no: {} }, new BN(450_000), size);
+      const taker = await placeBidIx(user1, user1Usdc, { yes: {} }, new BN(600_000), size);
+
+      const user1BalanceBefore = (await getAccount(provider.connection, user1Usdc)).amount;
+      const yesBefore = await program.account.userPosition.fetch(user1PositionPda);
+      const noBefore = await program.account.userPosition.fetch(user2PositionPda);
+
+      await executeMatch(maker.orderPda, taker.orderPda, size)
+        .preInstructions([maker.ix, taker.ix])
+        .signers([user1, user2, relayer])
+        .rpc();
+
+      const yesAfter = await program.account.userPosition.fetch(user1PositionPda);
+      const noAfter = await program.account.userPosition.fetch(user2PositionPda);
+      expect(yesAfter.yesShares.sub(yesBefore.yesShares).toNumber()).to.equal(5_000_000);
+      expect(noAfter.noShares.sub(noBefore.noShares).toNumber()).to.equal(5_000_000);
+      expect(noAfter.noCostBasis.sub(noBefore.noCostBasis).toNumber()).to.equal(2_250_000); // 5 x $0.45
+
+      // The taker pays $0.55 per pair (plus the taker fee), not its $0.60 limit
+      const user1BalanceAfter = (await getAccount(provider.connection, user1Usdc)).amount;
+      const spent = Number(user1BalanceBefore - user1BalanceAfter);
+      expect(spent).to.be.at.least(2_750_000);
+      expect(spent).to.be.below(3_000_000);
+    });
+
+    it("fails to match a YES bid and a NO bid summing to less than $1", async () => {
+      const size = new BN(5_000_000);
+      const maker = await placeBidIx(user2, user2Usdc, { no: {} }, new BN(450_000), size);
+      const taker = await placeBidIx(user1, user1Usdc, { yes: {} }, new BN(500_000), size);
+
+      try {
+        await executeMatch(maker.orderPda, taker.orderPda, size)
+          .preInstructions([maker.ix, taker.ix])
+          .signers([user1, user2, relayer])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("PriceMismatch");
+      }
+    });
+  });
+
+  // ============================================================================
+  // ORDER BOOK TESTS
+  // ============================================================================
+
+  describe("order book", () => {
+    let orderBookPda: PublicKey;
+    let user1PositionPda: PublicKey;
+    let user2PositionPda: PublicKey;
+
+    // Place a limit order and rest it on the book in one transaction
+    const postOrder = async (
+      user: Keypair, userUsdc: PublicKey, side: any, price: BN, size: BN, orderType: any = { limit: {} }, outcome: any = { yes: {} }
+    ) => {
+      const clientOrderId = new BN(Date.now() + Math.floor(Math.random() * 1000));
+      const [orderPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("order"), marketPda.toBuffer(), user.publicKey.toBuffer(), clientOrderId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      const placeIx = await program.methods
+        .placeOrder({
+          side,
+          outcome,
+          orderType,
+          price,
+          size,
+          expiryTs: new BN(Math.floor(Date.now() / 1000) + 3600),
+          clientOrderId,
+        })
+        .accounts({
+          globalState: globalStatePda,
+          market: marketPda,
+          order: orderPda,
+          vault: vaultPda,
+          userUsdc,
+          user: user.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction();
+
+      await program.methods
+        .postOrder()
+        .accounts({
+          market: marketPda,
+          orderBook: orderBookPda,
+          order: orderPda,
+          owner: user.publicKey,
+        })
+        .preInstructions([placeIx])
+        .signers([user])
+        .rpc();
+
+      return orderPda;
+    };
+
+    const crankMatch = (bidOrder: PublicKey, askOrder: PublicKey) =>
+      program.methods
+        .crankMatch({ yes: {} })
+        .accounts({
+          globalState: globalStatePda,
+          market: marketPda,
+          orderBook: orderBookPda,
+          vault: vaultPda,
+          feeRecipient: feeRecipientUsdc,
+          bidOrder,
+          askOrder,
+          bidOwnerUsdc: user1Usdc,
+          askOwnerUsdc: user2Usdc,
+          bidPosition: user1PositionPda,
+          askPosition: user2PositionPda,
+          cranker: provider.wallet.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    let cheapAsk: PublicKey;
+    let expensiveAsk: PublicKey;
+
+    before(async () => {
+      [orderBookPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("order_book"), marketPda.toBuffer()],
+        program.programId
+      );
+      [user1PositionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position"), marketPda.toBuffer(), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      [user2PositionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position"), marketPda.toBuffer(), user2.publicKey.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("successfully creates the market's order book", async () => {
+      await program.methods
+        .initializeOrderBook()
+        .accounts({
+          globalState: globalStatePda,
+          market: marketPda,
+          orderBook: orderBookPda,
+          authority: relayer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([relayer])
+        .rpc();
+
+      const orderBook = await program.account.orderBook.fetch(orderBookPda);
+      expect(orderBook.market.toBase58()).to.equal(marketPda.toBase58());
+      expect(orderBook.numOrders).to.equal(0);
+    });
+
+    it("rests limit orders on the book", async () => {
+      expensiveAsk = await postOrder(user2, user2Usdc, { ask: {} }, new BN(450_000), new BN(5_000_000));
+      cheapAsk = await postOrder(user2, user2Usdc, { ask: {} }, new BN(420_000), new BN(5_000_000));
+
+      const order = await program.account.order.fetch(cheapAsk);
+      expect(order.onBook).to.equal(true);
+      const orderBook = await program.account.orderBook.fetch(orderBookPda);
+      expect(orderBook.numOrders).to.equal(2);
+    });
+
+    it("fails to post an order crossing the owner's own resting order", async () => {
+      try {
+        await postOrder(user2, user2Usdc, { bid: {} }, new BN(500_000), new BN(5_000_000));
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("SelfTrade");
+      }
+    });
+
+    it("fails to post an IOC order", async () => {
+      try {
+        await postOrder(user1, user1Usdc, { bid: {} }, new BN(500_000), new BN(5_000_000), { ioc: {} });
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("OrderNotRestable");
+      }
+    });
+
+    it("fails to post an order holding less than the minimum resting escrow", async () => {
+      try {
+        // 1 contract at $0.50 escrows ~$0.53, below the $1 minimum
+        await postOrder(user1, user1Usdc, { bid: {} }, new BN(500_000), new BN(1_000_000));
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("RestingOrderTooSmall");
+      }
+    });
+
+    it("matches the top of the book in price-time priority", async () => {
+      const bid = await postOrder(user1, user1Usdc, { bid: {} }, new BN(500_000), new BN(5_000_000));
+
+      try {
+        await crankMatch(bid, expensiveAsk);
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("NotTopOfBook");
+      }
+
+      // The resting ask was posted first, so the bid fills at its $0.42
+      const makerBefore = await program.account.userPosition.fetch(user2PositionPda);
+      await crankMatch(bid, cheapAsk);
+      const makerAfter = await program.account.userPosition.fetch(user2PositionPda);
+
+      expect(makerAfter.noCostBasis.sub(makerBefore.noCostBasis).toNumber()).to.equal(2_900_000); // 5 x $0.58
+      expect((await program.account.order.fetch(bid)).status).to.deep.equal({ filled: {} });
+      expect((await program.account.order.fetch(cheapAsk)).onBook).to.equal(false);
+      expect((await program.account.orderBook.fetch(orderBookPda)).numOrders).to.equal(1);
+    });
+
+    it("fails to cancel a resting order without the order book", async () => {
+      try {
+        await program.methods
+          .cancelOrder()
+          .accounts({
+            market: marketPda,
+            vault: vaultPda,
+            userUsdc: user2Usdc,
+            order: expensiveAsk,
+            owner: user2.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user2])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("OrderOnBook");
+      }
+
+      await program.methods
+        .cancelOrder()
+        .accounts({
+          market: marketPda,
+          vault: vaultPda,
+          userUsdc: user2Usdc,
+          orderBook: orderBookPda,
+          order: expensiveAsk,
+          owner: user2.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user2])
+        .rpc();
+
+      expect((await program.account.orderBook.fetch(orderBookPda)).numOrders).to.equal(0);
+    });
+
+    it("crosses a YES bid with a resting NO bid", async () => {
+      // The NO bid at $0.45 rests as a YES ask at $0.55
+      const noBid = await postOrder(user2, user2Usdc, { bid: {} }, new BN(450_000), new BN(5_000_000), { limit: {} }, { no: {} });
+      const yesBid = await postOrder(user1, user1Usdc, { bid: {} }, new BN(600_000), new BN(5_000_000));
+
+      const user1BalanceBefore = (await getAccount(provider.connection, user1Usdc)).amount;
+      const makerBefore = await program.account.userPosition.fetch(user2PositionPda);
+      await crankMatch(yesBid, noBid);
+      const makerAfter = await program.account.userPosition.fetch(user2PositionPda);
+
+      expect(makerAfter.noCostBasis.sub(makerBefore.noCostBasis).toNumber()).to.equal(2_250_000); // 5 x $0.45
+      expect((await program.account.order.fetch(noBid)).status).to.deep.equal({ filled: {} });
+      expect((await program.account.orderBook.fetch(orderBookPda)).numOrders).to.equal(0);
+
+      // The YES bid fills at $0.55 and gets back the rest of its $0.60 escrow and fee reserve, less the taker fee
+      const refund = Number((await getAccount(provider.connection, user1Usdc)).amount - user1BalanceBefore);
+      expect(refund).to.be.above(250_000);
+      expect(refund).to.be.at.most(400_000);
+    });
+
+    it("keeps the vault backing open interest and escrow after a crank at equal prices", async () => {
+      // Vault balance left over after $1 per open pair and every active order's escrow
+      const vaultSurplus = async () => {
+        const market = await program.account.market.fetch(marketPda);
+        const vault = (await getAccount(provider.connection, vaultPda)).amount;
+        const orders = await program.account.order.all([{ memcmp: { offset: 8 + 32, bytes: marketPda.toBase58() } }]);
+        const escrow = orders
+          .filter(({ account }) => account.status.open || account.status.partialFill)
+          .reduce((total, { account }) => total + Number(account.lockedAmount.mul(account.size.sub(account.filledSize)).div(account.size)), 0);
+        return Number(vault) - market.openInterest.toNumber() - escrow;
+      };
+
+      const ask = await postOrder(user2, user2Usdc, { ask: {} }, new BN(600_000), new BN(5_000_000));
+      const bid = await postOrder(user1, user1Usdc, { bid: {} }, new BN(600_000), new BN(5_000_000));
+
+      const surplusBefore = await vaultSurplus();
+      await crankMatch(bid, ask);
+      const surplusAfter = await vaultSurplus();
+
+      // The taker fee comes out of the taker's fee reserve, not other users' collateral
+      expect((await program.account.order.fetch(bid)).status).to.deep.equal({ filled: {} });
+      expect(surplusAfter).to.equal(surplusBefore);
+      expect(surplusAfter).to.be.at.least(0);
+    });
+  });
+
+  // ============================================================================
+  // EXECUTE MERGE TESTS
+  // ============================================================================
+
+  describe("execute_merge", () => {
+    let user1PositionPda: PublicKey;
+    let user2PositionPda: PublicKey;
+
+    // Ask order the merge is checked against (escrowed, not posted to the book)
+    const placeAsk = async (user: Keypair, userUsdc: PublicKey, outcome: any, price: BN, size: BN) => {
+      const clientOrderId = new BN(Date.now() + Math.floor(Math.random() * 1000));
+      const [orderPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("order"), marketPda.toBuffer(), user.publicKey.toBuffer(), clientOrderId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      await program.methods
+        .placeOrder({
+          side: { ask: {} },
+          outcome,
+          orderType: { limit: {} },
+          price,
+          size,
+          expiryTs: new BN(Math.floor(Date.now() / 1000) + 3600),
+          clientOrderId,
+        })
+        .accounts({
+          globalState: globalStatePda,
+          market: marketPda,
+          order: orderPda,
+          vault: vaultPda,
+          userUsdc,
+          user: user.publicKey,
+        })
+        .signers([user])
+        .rpc();
+
+      return orderPda;
+    };
+
+    // Relayer merge of user2 selling NO (maker) with user1 selling YES (taker)
+    const executeMerge = async (makerPrice: BN, takerPrice: BN, size: BN, makerOrderPrice = makerPrice) => {
+      const makerOrder = await placeAsk(user2, user2Usdc, { no: {} }, makerOrderPrice, size);
+      const takerOrder = await placeAsk(user1, user1Usdc, { yes: {} }, takerPrice, size);
+
+      await program.methods
+        .executeMerge({ makerOutcome: { no: {} }, makerPrice, takerPrice, size })
+        .accounts({
+          globalState: globalStatePda,
+          market: marketPda,
+          vault: vaultPda,
+          feeRecipient: feeRecipientUsdc,
+          maker: user2.publicKey,
+          makerPosition: user2PositionPda,
+          makerUsdc: user2Usdc,
+          makerOrder,
+          taker: user1.publicKey,
+          takerPosition: user1PositionPda,
+          takerUsdc: user1Usdc,
+          takerOrder,
+          relayer: relayer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([relayer])
+        .rpc();
+
+      return [makerOrder, takerOrder];
+    };
+
+    before(async () => {
+      [user1PositionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position"), marketPda.toBuffer(), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      [user2PositionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position"), marketPda.toBuffer(), user2.publicKey.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("burns a YES/NO pair and pays both sellers from the vault", async () => {
+      const size = new BN(1_000_000);
+      const marketBefore = await program.account.market.fetch(marketPda);
+      const yesBefore = await program.account.userPosition.fetch(user1PositionPda);
+      const noBefore = await program.account.userPosition.fetch(user2PositionPda);
+      const user1BalanceBefore = (await getAccount(provider.connection, user1Usdc)).amount;
+      const user2BalanceBefore = (await getAccount(provider.connection, user2Usdc)).amount;
+
+      // NO ask at $0.40 and YES ask at $0.55 - the YES seller gets the $0.05 improvement
+      const [makerOrder, takerOrder] = await executeMerge(new BN(400_000), new BN(550_000), size);
+
+      const marketAfter = await program.account.market.fetch(marketPda);
+      const yesAfter = await program.account.userPosition.fetch(user1PositionPda);
+      const noAfter = await program.account.userPosition.fetch(user2PositionPda);
+      expect(marketBefore.openInterest.sub(marketAfter.openInterest).toNumber()).to.equal(1_000_000);
+      expect(yesBefore.yesShares.sub(yesAfter.yesShares).toNumber()).to.equal(1_000_000);
+      expect(noBefore.noShares.sub(noAfter.noShares).toNumber()).to.equal(1_000_000);
+
+      // Both asks are filled and their escrow was refunded (the net balance change is the proceeds)
+      const makerOrderAfter = await program.account.order.fetch(makerOrder);
+      const takerOrderAfter = await program.account.order.fetch(takerOrder);
+      expect(makerOrderAfter.status).to.deep.equal({ filled: {} });
+      expect(takerOrderAfter.status).to.deep.equal({ filled: {} });
+
+      const user1Received = Number((await getAccount(provider.connection, user1Usdc)).amount - user1BalanceBefore);
+      const user2Received = Number((await getAccount(provider.connection, user2Usdc)).amount - user2BalanceBefore);
+      expect(user2Received).to.equal(400_000);
+      expect(user1Received).to.be.above(550_000);
+      expect(user1Received).to.be.at.most(600_000);
+    });
+
+    it("fails when the merge price differs from the seller's ask", async () => {
+      try {
+        await executeMerge(new BN(450_000), new BN(550_000), new BN(1_000_000), new BN(400_000));
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("MergeOrderMismatch");
+      }
+    });
+
+    it("fails to merge asks summing to more than $1", async () => {
+      try {
+        await executeMerge(new BN(500_000), new BN(550_000), new BN(1_000_000));
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("PriceMismatch");
+      }
+    });
+  });
+
+  // ============================================================================
+  // ADMIN FUNCTIONS TESTS
+  // ============================================================================
+
+  describe("admin functions", () => {
+    describe("pause_protocol", () => {
+      it("successfully pauses the protocol", async () => {
+        await program.methods
+          .pauseProtocol(true, "Scheduled maintenance")
+          .accounts({
+            globalState: globalStatePda,
+            admin: admin.publicKey,
+          })
+          .signers([admin])
+          .rpc();
+
+        const globalState = await program.account.globalState.fetch(globalStatePda);
+        expect(globalState.paused).to.be.true;
+        expect(globalState.pausedAt.toNumber()).to.be.greaterThan(0);
+      });
+
+      it("fails to place orders when paused", async () => {
+        const orderArgs = {
+          side: { bid: {} },
+          outcome: { yes: {} },
+          orderType: { limit: {} },
+          price: new BN(500_000),
+          size: new BN(100),
+          expiryTs: new BN(Math.floor(Date.now() / 1000) + 3600),
+          clientOrderId: new BN(Date.now()),
+        };
+
+        try {
+          await program.methods
+            .placeOrder(orderArgs)
+            .accounts({
+              globalState: globalStatePda,
+              market: marketPda,
+              user: user1.publicKey,
+            })
+            .signers([user1])
+            .rpc();
+          expect.fail("Should have thrown an error");
+        } catch (err: any) {
+          expect(err.error?.errorCode?.code || err.message).to.satisfy(
+            (msg: string) => msg.includes("ProtocolPaused") || msg.includes("paused")
+          );
+        }
+      });
+
+      it("successfully unpauses the protocol", async () => {
+        await program.methods
+          .pauseProtocol(false, null)
+          .accounts({
+            globalState: globalStatePda,
+            admin: admin.publicKey,
+          })
+          .signers([admin])
+          .rpc();
+
+        const globalState = await program.account.globalState.fetch(globalStatePda);
+        expect(globalState.paused).to.be.false;
+        expect(globalState.pausedAt.toNumber()).to.equal(0);
+      });
+
+      it("fails when non-admin tries to pause", async () => {
+        try {
+          await program.methods
+            .pauseProtocol(true, "Unauthorized pause")
+            .accounts({
+              globalState: globalStatePda,
+              admin: user1.publicKey, // Not admin
+            })
+            .signers([user1])
+            .rpc();
+          expect.fail("Should have thrown an error");
+        } catch (err: any) {
+          expect(err.error?.errorCode?.code || err.message).to.satisfy(
+            (msg: string) => msg.includes("Unauthorized") || msg.includes("unauthorized") || msg.includes("constraint")
+          );
+        }
+      });
+    });
+
+    describe("update_fees", () => {
+      it("successfully updates fees", async () => {
+        const newMakerFee = 5;   // 0.05%
+        const newTakerFee = 15;  // 0.15%
+
+        await program.methods
+          .updateFees(newMakerFee, newTakerFee)
+          .accounts({
+            globalState: globalStatePda,
+            admin: admin.publicKey,
+          })
+          .signers([admin])
+          .rpc();
+
+        const globalState = await program.account.globalState.fetch(globalStatePda);
+        expect(globalState.makerFeeBps).to.equal(newMakerFee);
+        expect(globalState.takerFeeBps).to.equal(newTakerFee);
+      });
+
+      it("fails with fees exceeding maximum (5%)", async () => {
+        try {
+          await program.methods
+            .updateFees(0, 501) // 5.01% - too high
+            .accounts({
+              globalState: globalStatePda,
+              admin: admin.publicKey,
+            })
             .signers([admin])
             .rpc();
           expect.fail("Should have thrown an error");
@@ -1030,134 +2768,1755 @@ describe("degen-terminal", () => {
         }
       });
 
-      it("fails when non-admin tries to update fees", async () => {
-        try {
-          await program.methods
-            .updateFees(0, 20)
-            .accounts({
-              globalState: globalStatePda,
-              admin: user1.publicKey, // Not admin
-            })
-            .signers([user1])
-            .rpc();
-          expect.fail("Should have thrown an error");
-        } catch (err: any) {
-          expect(err.error?.errorCode?.code || err.message).to.satisfy(
-            (msg: string) => msg.includes("Unauthorized") || msg.includes("unauthorized") || msg.includes("constraint")
-          );
-        }
-      });
+      it("fails when non-admin tries to update fees", async () => {
+        try {
+          await program.methods
+            .updateFees(0, 20)
+            .accounts({
+              globalState: globalStatePda,
+              admin: user1.publicKey, // Not admin
+            })
+            .signers([user1])
+            .rpc();
+          expect.fail("Should have thrown an error");
+        } catch (err: any) {
+          expect(err.error?.errorCode?.code || err.message).to.satisfy(
+            (msg: string) => msg.includes("Unauthorized") || msg.includes("unauthorized") || msg.includes("constraint")
+          );
+        }
+      });
+
+      // Reset fees for other tests
+      after(async () => {
+        await program.methods
+          .updateFees(0, 10)
+          .accounts({
+            globalState: globalStatePda,
+            admin: admin.publicKey,
+          })
+          .signers([admin])
+          .rpc();
+      });
+    });
+
+    describe("set_market_fees", () => {
+      // Free trading until the last 10 minutes, then 0.20% and 0.50% in the final minute
+      const TIERS = [
+        { secondsBeforeExpiry: new BN(600), takerFeeBps: 20 },
+        { secondsBeforeExpiry: new BN(60), takerFeeBps: 50 },
+      ];
+
+      const setMarketFees = (takerFeeBps: number | null, tiers: typeof TIERS, signer = admin) =>
+        program.methods
+          .setMarketFees(takerFeeBps, tiers)
+          .accounts({
+            market: marketPda,
+            globalState: globalStatePda,
+            admin: signer.publicKey,
+          })
+          .signers([signer])
+          .rpc();
+
+      it("successfully sets a market fee schedule", async () => {
+        await setMarketFees(0, TIERS);
+
+        const market = await program.account.market.fetch(marketPda);
+        expect(market.feeSchedule.enabled).to.be.true;
+        expect(market.feeSchedule.takerFeeBps).to.equal(0);
+        expect(market.feeSchedule.numTiers).to.equal(2);
+        expect(market.feeSchedule.tiers[1].secondsBeforeExpiry.toNumber()).to.equal(60);
+        expect(market.feeSchedule.tiers[1].takerFeeBps).to.equal(50);
+      });
+
+      it("fails with tiers not ordered towards expiry", async () => {
+        try {
+          await setMarketFees(0, [...TIERS].reverse());
+          expect.fail("Should have thrown an error");
+        } catch (err: any) {
+          expect(err.error?.errorCode?.code || err.message).to.include("InvalidFeeConfig");
+        }
+      });
+
+      it("fails with a fee above the maximum", async () => {
+        try {
+          await setMarketFees(501, []);
+          expect.fail("Should have thrown an error");
+        } catch (err: any) {
+          expect(err.error?.errorCode?.code || err.message).to.include("InvalidFeeConfig");
+        }
+      });
+
+      it("fails when non-admin tries to set market fees", async () => {
+        try {
+          await setMarketFees(0, [], relayer);
+          expect.fail("Should have thrown an error");
+        } catch (err: any) {
+          expect(err.error?.errorCode?.code || err.message).to.include("Unauthorized");
+        }
+      });
+
+      it("successfully reverts a market to the global fee", async () => {
+        await setMarketFees(null, []);
+
+        const market = await program.account.market.fetch(marketPda);
+        expect(market.feeSchedule.enabled).to.be.false;
+        expect(market.feeSchedule.numTiers).to.equal(0);
+      });
+    });
+
+    describe("transfer_admin", () => {
+      let newAdmin: Keypair;
+
+      before(async () => {
+        newAdmin = Keypair.generate();
+        await provider.connection.requestAirdrop(newAdmin.publicKey, 2 * LAMPORTS_PER_SOL);
+        await new Promise(resolve => setTimeout(resolve, 1000)); // Wait for airdrop
+      });
+
+      it("successfully transfers admin authority", async () => {
+        await program.methods
+          .transferAdmin()
+          .accounts({
+            globalState: globalStatePda,
+            admin: admin.publicKey,
+            newAdmin: newAdmin.publicKey,
+          })
+          .signers([admin])
+          .rpc();
+
+        const globalState = await program.account.globalState.fetch(globalStatePda);
+        expect(globalState.admin.toBase58()).to.equal(newAdmin.publicKey.toBase58());
+      });
+
+      it("fails when old admin tries to act", async () => {
+        try {
+          await program.methods
+            .pauseProtocol(true, "Should fail")
+            .accounts({
+              globalState: globalStatePda,
+              admin: admin.publicKey, // Old admin
+            })
+            .signers([admin])
+            .rpc();
+          expect.fail("Should have thrown an error");
+        } catch (err: any) {
+          expect(err.error?.errorCode?.code || err.message).to.satisfy(
+            (msg: string) => msg.includes("Unauthorized") || msg.includes("unauthorized") || msg.includes("constraint")
+          );
+        }
+      });
+
+      // Transfer back for other tests
+      after(async () => {
+        await program.methods
+          .transferAdmin()
+          .accounts({
+            globalState: globalStatePda,
+            admin: newAdmin.publicKey,
+            newAdmin: admin.publicKey,
+          })
+          .signers([newAdmin])
+          .rpc();
+      });
+    });
+
+    describe("set_resolvers", () => {
+      it("successfully registers the keeper as a resolver", async () => {
+        await program.methods
+          .setResolvers([keeper.publicKey], 1)
+          .accounts({
+            globalState: globalStatePda,
+            resolverConfig: resolverConfigPda,
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([admin])
+          .rpc();
+
+        const resolverConfig = await program.account.resolverConfig.fetch(resolverConfigPda);
+        expect(resolverConfig.numResolvers).to.equal(1);
+        expect(resolverConfig.threshold).to.equal(1);
+        expect(resolverConfig.resolvers[0].toBase58()).to.equal(keeper.publicKey.toBase58());
+      });
+
+      it("fails with threshold above the number of resolvers", async () => {
+        try {
+          await program.methods
+            .setResolvers([keeper.publicKey], 2)
+            .accounts({
+              globalState: globalStatePda,
+              resolverConfig: resolverConfigPda,
+              admin: admin.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .signers([admin])
+            .rpc();
+          expect.fail("Should have thrown an error");
+        } catch (err: any) {
+          expect(err.error?.errorCode?.code || err.message).to.include("InvalidThreshold");
+        }
+      });
+    });
+
+    describe("set_dispute_config", () => {
+      it("successfully sets the proposal bond and dispute window", async () => {
+        await program.methods
+          .setDisputeConfig(new BN(10_000_000), new BN(3))
+          .accounts({
+            globalState: globalStatePda,
+            resolverConfig: resolverConfigPda,
+            admin: admin.publicKey,
+          })
+          .signers([admin])
+          .rpc();
+
+        const resolverConfig = await program.account.resolverConfig.fetch(resolverConfigPda);
+        expect(resolverConfig.proposalBond.toNumber()).to.equal(10_000_000);
+        expect(resolverConfig.disputeWindow.toNumber()).to.equal(3);
+      });
+
+      it("fails with a bond but no dispute window", async () => {
+        try {
+          await program.methods
+            .setDisputeConfig(new BN(10_000_000), new BN(0))
+            .accounts({
+              globalState: globalStatePda,
+              resolverConfig: resolverConfigPda,
+              admin: admin.publicKey,
+            })
+            .signers([admin])
+            .rpc();
+          expect.fail("Should have thrown an error");
+        } catch (err: any) {
+          expect(err.error?.errorCode?.code || err.message).to.include("InvalidDisputeConfig");
+        }
+      });
+
+      it("fails with duplicate resolvers", async () => {
+        try {
+          await program.methods
+            .setResolvers([keeper.publicKey, keeper.publicKey], 1)
+            .accounts({
+              globalState: globalStatePda,
+              resolverConfig: resolverConfigPda,
+              admin: admin.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .signers([admin])
+            .rpc();
+          expect.fail("Should have thrown an error");
+        } catch (err: any) {
+          expect(err.error?.errorCode?.code || err.message).to.include("InvalidResolver");
+        }
+      });
+
+      it("fails when non-admin tries to set resolvers", async () => {
+        try {
+          await program.methods
+            .setResolvers([user1.publicKey], 1)
+            .accounts({
+              globalState: globalStatePda,
+              resolverConfig: resolverConfigPda,
+              admin: user1.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .signers([user1])
+            .rpc();
+          expect.fail("Should have thrown an error");
+        } catch (err: any) {
+          expect(err.error?.errorCode?.code || err.message).to.satisfy(
+            (msg: string) => msg.includes("Unauthorized") || msg.includes("unauthorized") || msg.includes("constraint")
+          );
+        }
+      });
+    });
+
+    describe("set_oracle_config", () => {
+      const [oracleConfigPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("oracle_config"), Buffer.from("BTC")],
+        program.programId
+      );
+      const pythFeed = Keypair.generate().publicKey;
+      const switchboardAggregator = Keypair.generate().publicKey;
+      const publisherKey = Keypair.generate().publicKey;
+
+      const setOracleConfig = (sources: any[], minSources: number, maxDeviationBps: number, signer = admin) =>
+        program.methods
+          .setOracleConfig("BTC", sources, minSources, maxDeviationBps)
+          .accounts({
+            globalState: globalStatePda,
+            assetConfig: getAssetConfigPda("BTC"),
+            oracleConfig: oracleConfigPda,
+            admin: signer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([signer])
+          .rpc();
+
+      it("successfully configures three price sources", async () => {
+        await setOracleConfig(
+          [
+            { kind: { pyth: {} }, key: pythFeed },
+            { kind: { switchboard: {} }, key: switchboardAggregator },
+            { kind: { signedPublisher: {} }, key: publisherKey },
+          ],
+          2,
+          50
+        );
+
+        const oracleConfig = await program.account.oracleConfig.fetch(oracleConfigPda);
+        expect(oracleConfig.numSources).to.equal(3);
+        expect(oracleConfig.minSources).to.equal(2);
+        expect(oracleConfig.maxDeviationBps).to.equal(50);
+        expect(oracleConfig.sources[1].key.toString()).to.equal(switchboardAggregator.toString());
+      });
+
+      it("fails with two sources of the same kind", async () => {
+        try {
+          await setOracleConfig(
+            [
+              { kind: { pyth: {} }, key: pythFeed },
+              { kind: { pyth: {} }, key: Keypair.generate().publicKey },
+            ],
+            1,
+            50
+          );
+          expect.fail("Should have thrown an error");
+        } catch (err: any) {
+          expect(err.error?.errorCode?.code || err.message).to.include("InvalidOracleConfig");
+        }
+      });
+
+      it("fails when min_sources exceeds the number of sources", async () => {
+        try {
+          await setOracleConfig([{ kind: { pyth: {} }, key: pythFeed }], 2, 50);
+          expect.fail("Should have thrown an error");
+        } catch (err: any) {
+          expect(err.error?.errorCode?.code || err.message).to.include("InvalidOracleConfig");
+        }
+      });
+
+      it("fails when non-admin tries to configure oracles", async () => {
+        try {
+          await setOracleConfig([{ kind: { pyth: {} }, key: pythFeed }], 1, 50, user1);
+          expect.fail("Should have thrown an error");
+        } catch (err: any) {
+          expect(err.error?.errorCode?.code || err.message).to.satisfy(
+            (msg: string) => msg.includes("Unauthorized") || msg.includes("unauthorized") || msg.includes("constraint")
+          );
+        }
+      });
+    });
+  });
+
+  // ============================================================================
+  // RESOLVE MARKET TESTS
+  // ============================================================================
+
+  describeSlow("resolve_market", () => {
+    let resolveMarketPda: PublicKey;
+
+    before(async () => {
+      const { market, expiry } = await createExpiringMarket("ETH", new BN(3000_00000000));
+      resolveMarketPda = market;
+
+      await waitForExpiry(expiry);
+    });
+
+    it("fails to resolve market before expiry", async () => {
+      // Create another market that hasn't expired
+      const futureExpiry = alignedExpiry(3600, 600);
+      
+      const [futureMarketPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("market"),
+          Buffer.from("SOL"),
+          Buffer.from("1h"),
+          futureExpiry.toArrayLike(Buffer, "le", 8),
+          Buffer.from([0]),
+        ],
+        program.programId
+      );
+
+      const futureVaultPda = await anchor.utils.token.associatedAddress({
+        mint: usdcMint,
+        owner: futureMarketPda,
+      });
+
+      await program.methods
+        .initializeMarket("SOL", "1h", new BN(150_00000000), futureExpiry, 0)
+        .accounts({
+          globalState: globalStatePda,
+          assetConfig: getAssetConfigPda("SOL"),
+          timeframeConfig: getTimeframeConfigPda("1h"),
+          market: futureMarketPda,
+          marketGroup: getMarketGroupPda("SOL", "1h", futureExpiry),
+          marketSeries: getMarketSeriesPda("SOL", "1h"),
+          vault: futureVaultPda,
+          usdcMint: usdcMint,
+          authority: relayer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([relayer])
+        .rpc();
+
+      try {
+        await program.methods
+          .resolveMarket({ finalPrice: new BN(151_00000000) })
+          .accounts({
+            market: futureMarketPda,
+            assetConfig: getAssetConfigPda("SOL"),
+            globalState: globalStatePda,
+            resolverConfig: resolverConfigPda,
+            priceSamples: null,
+            authority: keeper.publicKey,
+          })
+          .signers([keeper])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.satisfy(
+          (msg: string) => msg.includes("MarketNotExpired") || msg.includes("expired")
+        );
+      }
+    });
+
+    it("fails when an unregistered wallet resolves", async () => {
+      try {
+        await program.methods
+          .resolveMarket({ finalPrice: new BN(2900_00000000) })
+          .accounts({
+            market: resolveMarketPda,
+            assetConfig: getAssetConfigPda("ETH"),
+            globalState: globalStatePda,
+            resolverConfig: resolverConfigPda,
+            priceSamples: null,
+            authority: user2.publicKey,
+          })
+          .signers([user2])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("Unauthorized");
+      }
+    });
+
+    it("fails when the market authority resolves without being a resolver", async () => {
+      try {
+        await program.methods
+          .resolveMarket({ finalPrice: new BN(2900_00000000) })
+          .accounts({
+            market: resolveMarketPda,
+            assetConfig: getAssetConfigPda("ETH"),
+            globalState: globalStatePda,
+            resolverConfig: resolverConfigPda,
+            priceSamples: null,
+            authority: relayer.publicKey,
+          })
+          .signers([relayer])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("Unauthorized");
+      }
+    });
+
+    it("successfully resolves an expired market", async () => {
+      // Final price above the $3,000 strike - outcome is derived on-chain
+      await program.methods
+        .resolveMarket({ finalPrice: new BN(3010_00000000) })
+        .accounts({
+          market: resolveMarketPda,
+          assetConfig: getAssetConfigPda("ETH"),
+          globalState: globalStatePda,
+          resolverConfig: resolverConfigPda,
+          priceSamples: null,
+          authority: keeper.publicKey,
+        })
+        .signers([keeper])
+        .rpc();
+
+      const market = await program.account.market.fetch(resolveMarketPda);
+      expect(market.status).to.deep.equal({ resolved: {} });
+      expect(market.outcome).to.deep.equal({ yes: {} });
+      expect(market.finalPrice.toString()).to.equal("301000000000");
+      expect(market.resolvedAt.toNumber()).to.be.greaterThan(0);
+
+      console.log(`  Market resolved with outcome: ${JSON.stringify(market.outcome)}`);
+      console.log(`  Final price: ${market.finalPrice.toNumber()}`);
+    });
+
+    it("fails to resolve an already resolved market", async () => {
+      try {
+        await program.methods
+          .resolveMarket({ finalPrice: new BN(3010_00000000) })
+          .accounts({
+            market: resolveMarketPda,
+            assetConfig: getAssetConfigPda("ETH"),
+            globalState: globalStatePda,
+            resolverConfig: resolverConfigPda,
+            priceSamples: null,
+            authority: keeper.publicKey,
+          })
+          .signers([keeper])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.satisfy(
+          (msg: string) => msg.includes("MarketAlreadyResolved") || msg.includes("resolved")
+        );
+      }
+    });
+  });
+
+  // ============================================================================
+  // SUBMIT RESOLUTION (M-OF-N QUORUM) TESTS
+  // ============================================================================
+
+  describeSlow("submit_resolution", () => {
+    let agreedMarketPda: PublicKey;
+    let disputedMarketPda: PublicKey;
+    let staleVoteMarketPda: PublicKey;
+
+    const setResolvers = async (resolvers: PublicKey[], threshold: number) => {
+      await program.methods
+        .setResolvers(resolvers, threshold)
+        .accounts({
+          globalState: globalStatePda,
+          resolverConfig: resolverConfigPda,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+    };
+
+    const submitResolution = async (marketPda: PublicKey, resolver: Keypair, finalPrice: BN) => {
+      const [votesPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("resolution_votes"), marketPda.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .submitResolution(finalPrice)
+        .accounts({
+          market: marketPda,
+          resolverConfig: resolverConfigPda,
+          resolutionVotes: votesPda,
+          priceSamples: null,
+          resolver: resolver.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([resolver])
+        .rpc();
+    };
+
+    before(async () => {
+      // Require 2-of-2 resolver agreement
+      await setResolvers([keeper.publicKey, user1.publicKey], 2);
+
+      const agreed = await createExpiringMarket("BTC", new BN(95000_00000000));
+      const disputed = await createExpiringMarket("SOL", new BN(150_00000000));
+      const staleVote = await createExpiringMarket("ETH", new BN(3000_00000000));
+      agreedMarketPda = agreed.market;
+      disputedMarketPda = disputed.market;
+      staleVoteMarketPda = staleVote.market;
+
+      await waitForExpiry(BN.max(BN.max(agreed.expiry, disputed.expiry), staleVote.expiry));
+    });
+
+    after(async () => {
+      await setResolvers([keeper.publicKey], 1);
+    });
+
+    it("fails to resolve directly when a quorum is required", async () => {
+      try {
+        await program.methods
+          .resolveMarket({ finalPrice: new BN(96000_00000000) })
+          .accounts({
+            market: agreedMarketPda,
+            assetConfig: getAssetConfigPda("BTC"),
+            globalState: globalStatePda,
+            resolverConfig: resolverConfigPda,
+            priceSamples: null,
+            authority: keeper.publicKey,
+          })
+          .signers([keeper])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("QuorumRequired");
+      }
+    });
+
+    it("resolves once the threshold of matching votes is reached", async () => {
+      await submitResolution(agreedMarketPda, keeper, new BN(96000_00000000));
+
+      let market = await program.account.market.fetch(agreedMarketPda);
+      expect(market.status).to.not.deep.equal({ resolved: {} });
+
+      await submitResolution(agreedMarketPda, user1, new BN(96000_00000000));
+
+      market = await program.account.market.fetch(agreedMarketPda);
+      expect(market.status).to.deep.equal({ resolved: {} });
+      expect(market.outcome).to.deep.equal({ yes: {} });
+      expect(market.finalPrice.toString()).to.equal("9600000000000");
+    });
+
+    it("fails when a resolver votes twice", async () => {
+      try {
+        await submitResolution(disputedMarketPda, keeper, new BN(149_00000000));
+        await submitResolution(disputedMarketPda, keeper, new BN(149_00000000));
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("AlreadyVoted");
+      }
+    });
+
+    it("moves the market to disputed on conflicting votes", async () => {
+      await submitResolution(disputedMarketPda, user1, new BN(151_00000000));
+
+      const market = await program.account.market.fetch(disputedMarketPda);
+      expect(market.status).to.deep.equal({ disputed: {} });
+    });
+
+    it("lets the admin resolve a disputed market", async () => {
+      await program.methods
+        .resolveMarket({ finalPrice: new BN(149_00000000) })
+        .accounts({
+          market: disputedMarketPda,
+          assetConfig: getAssetConfigPda("SOL"),
+          globalState: globalStatePda,
+          resolverConfig: resolverConfigPda,
+          priceSamples: null,
+          authority: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+
+      const market = await program.account.market.fetch(disputedMarketPda);
+      expect(market.status).to.deep.equal({ resolved: {} });
+      expect(market.outcome).to.deep.equal({ no: {} });
+    });
+
+    it("ignores votes from resolvers removed since voting", async () => {
+      await submitResolution(staleVoteMarketPda, keeper, new BN(2990_00000000));
+
+      // Keeper is rotated out; its conflicting vote no longer disputes the market
+      await setResolvers([user1.publicKey, user2.publicKey], 2);
+      await submitResolution(staleVoteMarketPda, user1, new BN(3010_00000000));
+
+      let market = await program.account.market.fetch(staleVoteMarketPda);
+      expect(market.status).to.not.deep.equal({ disputed: {} });
+      expect(market.status).to.not.deep.equal({ resolved: {} });
+
+      await submitResolution(staleVoteMarketPda, user2, new BN(3010_00000000));
+
+      market = await program.account.market.fetch(staleVoteMarketPda);
+      expect(market.status).to.deep.equal({ resolved: {} });
+      expect(market.outcome).to.deep.equal({ yes: {} });
+    });
+  });
+
+  // ============================================================================
+  // OPTIMISTIC RESOLUTION (PROPOSE / CHALLENGE / DISPUTE) TESTS
+  // ============================================================================
+
+  describeSlow("optimistic resolution", () => {
+    let unchallengedMarketPda: PublicKey;
+    let challengedMarketPda: PublicKey;
+
+    const proposalAccounts = async (market: PublicKey) => {
+      const [proposal] = PublicKey.findProgramAddressSync(
+        [Buffer.from("proposal"), market.toBuffer()],
+        program.programId
+      );
+      const bondVault = await anchor.utils.token.associatedAddress({ mint: usdcMint, owner: proposal });
+      const vault = await anchor.utils.token.associatedAddress({ mint: usdcMint, owner: market });
+      return { proposal, bondVault, vault };
+    };
+
+    const setResolvers = (resolvers: PublicKey[]) =>
+      program.methods
+        .setResolvers(resolvers, 1)
+        .accounts({
+          globalState: globalStatePda,
+          resolverConfig: resolverConfigPda,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+
+    const propose = async (market: PublicKey, finalPrice: BN) => {
+      const { proposal, bondVault, vault } = await proposalAccounts(market);
+      await program.methods
+        .proposeResolution(finalPrice)
+        .accounts({
+          market,
+          vault,
+          resolverConfig: resolverConfigPda,
+          proposal,
+          bondVault,
+          priceSamples: null,
+          usdcMint: usdcMint,
+          proposerUsdc: user1Usdc,
+          proposer: user1.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user1])
+        .rpc();
+    };
+
+    before(async () => {
+      const unchallenged = await createExpiringMarket("BTC", new BN(95000_00000000));
+      const challenged = await createExpiringMarket("ETH", new BN(3000_00000000));
+      unchallengedMarketPda = unchallenged.market;
+      challengedMarketPda = challenged.market;
+
+      await waitForExpiry(BN.max(unchallenged.expiry, challenged.expiry));
+    });
+
+    it("moves the market to proposed and escrows the bond", async () => {
+      await propose(unchallengedMarketPda, new BN(96000_00000000));
+
+      const { proposal, bondVault } = await proposalAccounts(unchallengedMarketPda);
+      const market = await program.account.market.fetch(unchallengedMarketPda);
+      const proposalAccount = await program.account.resolutionProposal.fetch(proposal);
+      const bond = await getAccount(provider.connection, bondVault);
+
+      expect(market.status).to.deep.equal({ proposed: {} });
+      expect(proposalAccount.outcome).to.deep.equal({ yes: {} });
+      expect(Number(bond.amount)).to.equal(10_000_000);
+    });
+
+    it("fails to finalize before the dispute window has passed", async () => {
+      const { proposal, bondVault } = await proposalAccounts(unchallengedMarketPda);
+      try {
+        await program.methods
+          .finalizeResolution()
+          .accounts({
+            market: unchallengedMarketPda,
+            proposal,
+            bondVault,
+            proposer: user1.publicKey,
+            proposerUsdc: user1Usdc,
+            authority: keeper.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([keeper])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("DisputeWindowOpen");
+      }
+    });
+
+    it("finalizes an unchallenged proposal after the dispute window", async () => {
+      await new Promise(resolve => setTimeout(resolve, 4000));
+
+      const { proposal, bondVault } = await proposalAccounts(unchallengedMarketPda);
+      await program.methods
+        .finalizeResolution()
+        .accounts({
+          market: unchallengedMarketPda,
+          proposal,
+          bondVault,
+          proposer: user1.publicKey,
+          proposerUsdc: user1Usdc,
+          authority: keeper.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([keeper])
+        .rpc();
+
+      const market = await program.account.market.fetch(unchallengedMarketPda);
+      expect(market.status).to.deep.equal({ resolved: {} });
+      expect(market.outcome).to.deep.equal({ yes: {} });
+    });
+
+    it("slashes the proposer's bond to a successful challenger", async () => {
+      // Wrong proposal: final price above the $3,000 strike
+      await propose(challengedMarketPda, new BN(3010_00000000));
+
+      const { proposal, bondVault } = await proposalAccounts(challengedMarketPda);
+      await program.methods
+        .challengeResolution()
+        .accounts({
+          market: challengedMarketPda,
+          proposal,
+          bondVault,
+          challengerUsdc: user2Usdc,
+          challenger: user2.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user2])
+        .rpc();
+
+      // The proposer cannot settle its own dispute, even as a registered resolver
+      await setResolvers([keeper.publicKey, user1.publicKey]);
+      try {
+        await program.methods
+          .settleDispute(new BN(3010_00000000))
+          .accounts({
+            market: challengedMarketPda,
+            globalState: globalStatePda,
+            resolverConfig: resolverConfigPda,
+            proposal,
+            bondVault,
+            proposer: user1.publicKey,
+            proposerUsdc: user1Usdc,
+            challengerUsdc: user2Usdc,
+            priceSamples: null,
+            authority: user1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user1])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("DisputeParticipant");
+      } finally {
+        await setResolvers([keeper.publicKey]);
+      }
+
+      const before = await getAccount(provider.connection, user2Usdc);
+
+      await program.methods
+        .settleDispute(new BN(2990_00000000))
+        .accounts({
+          market: challengedMarketPda,
+          globalState: globalStatePda,
+          resolverConfig: resolverConfigPda,
+          proposal,
+          bondVault,
+          proposer: user1.publicKey,
+          proposerUsdc: user1Usdc,
+          challengerUsdc: user2Usdc,
+          priceSamples: null,
+          authority: admin.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc();
+
+      const after = await getAccount(provider.connection, user2Usdc);
+      const market = await program.account.market.fetch(challengedMarketPda);
+      expect(market.status).to.deep.equal({ resolved: {} });
+      expect(market.outcome).to.deep.equal({ no: {} });
+      expect(Number(after.amount) - Number(before.amount)).to.equal(20_000_000);
+    });
+  });
+
+  // ============================================================================
+  // RESOLVE MARKET WITH ORACLE TESTS
+  // ============================================================================
+
+  describeSlow("resolve_market_with_oracle", () => {
+    const PYTH_RECEIVER_PROGRAM_ID = new PublicKey("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+    const PRICE_UPDATE_V2_SIZE = 134;
+
+    let oracleMarketPda: PublicKey;
+
+    before(async () => {
+      const { market, expiry } = await createExpiringMarket("SOL", new BN(150_00000000));
+      oracleMarketPda = market;
+
+      await waitForExpiry(expiry);
+    });
+
+    it("fails with a price account not owned by the Pyth receiver", async () => {
+      const mockPriceUpdate = Keypair.generate();
+
+      try {
+        await program.methods
+          .resolveMarketWithOracle()
+          .accounts({
+            market: oracleMarketPda,
+            assetConfig: getAssetConfigPda("SOL"),
+            priceUpdate: mockPriceUpdate.publicKey,
+            authority: keeper.publicKey,
+          })
+          .signers([keeper])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.satisfy(
+          (msg: string) => msg.includes("InvalidOracle") || msg.includes("oracle")
+        );
+      }
+    });
+
+    it("fails with a Pyth-owned account that is not a PriceUpdateV2", async () => {
+      // Mock account assigned to the receiver program but never written by it
+      const mockPriceUpdate = Keypair.generate();
+      const lamports = await provider.connection.getMinimumBalanceForRentExemption(PRICE_UPDATE_V2_SIZE);
+      const createIx = SystemProgram.createAccount({
+        fromPubkey: keeper.publicKey,
+        newAccountPubkey: mockPriceUpdate.publicKey,
+        lamports,
+        space: PRICE_UPDATE_V2_SIZE,
+        programId: PYTH_RECEIVER_PROGRAM_ID,
+      });
+
+      try {
+        await program.methods
+          .resolveMarketWithOracle()
+          .accounts({
+            market: oracleMarketPda,
+            assetConfig: getAssetConfigPda("SOL"),
+            priceUpdate: mockPriceUpdate.publicKey,
+            authority: keeper.publicKey,
+          })
+          .preInstructions([createIx])
+          .signers([keeper, mockPriceUpdate])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.satisfy(
+          (msg: string) => msg.includes("InvalidOracle") || msg.includes("oracle")
+        );
+      }
+
+      const market = await program.account.market.fetch(oracleMarketPda);
+      expect(market.status).to.deep.equal({ open: {} });
+    });
+  });
+
+  // ============================================================================
+  // RESOLVE MARKET WITH ORACLES (MEDIAN) TESTS
+  // ============================================================================
+
+  describeSlow("resolve_market_with_oracles", () => {
+    // BTC sources (Pyth, Switchboard and a signed publisher) are configured in set_oracle_config
+    const [oracleConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("oracle_config"), Buffer.from("BTC")],
+      program.programId
+    );
+
+    let medianMarketPda: PublicKey;
+
+    const resolveWithOracles = (pythPriceUpdate: PublicKey | null) =>
+      program.methods
+        .resolveMarketWithOracles(new BN(96000_00000000), new BN(0))
+        .accounts({
+          market: medianMarketPda,
+          oracleConfig: oracleConfigPda,
+          pythPriceUpdate,
+          switchboardAggregator: Keypair.generate().publicKey,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          authority: keeper.publicKey,
+        })
+        .signers([keeper])
+        .rpc();
+
+    before(async () => {
+      const { market, expiry } = await createExpiringMarket("BTC", new BN(95000_00000000));
+      medianMarketPda = market;
+
+      await waitForExpiry(expiry);
+    });
+
+    it("fails when a configured source is omitted", async () => {
+      try {
+        await resolveWithOracles(null);
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("MissingOracleSource");
+      }
+    });
+
+    it("fails instead of skipping an invalid source account", async () => {
+      try {
+        // Not owned by the Pyth receiver
+        await resolveWithOracles(Keypair.generate().publicKey);
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("InvalidOracle");
+      }
+
+      const market = await program.account.market.fetch(medianMarketPda);
+      expect(market.status).to.not.deep.equal({ resolved: {} });
+    });
+  });
+
+  // ============================================================================
+  // RESOLVE MARKET WITH ATTESTATION TESTS
+  // ============================================================================
+
+  describeSlow("resolve_market_with_attestation", () => {
+    const FINAL = 1; // AttestationKind::Final
+    const publisher = Keypair.generate();
+    const finalPrice = new BN(3100_00000000);
+
+    let attestedMarketPda: PublicKey;
+    let publishTime: BN;
+
+    const resolveWithAttestation = (price: BN, signer: Keypair | null) =>
+      program.methods
+        .resolveMarketWithAttestation(price, publishTime)
+        .accounts({
+          market: attestedMarketPda,
+          resolverConfig: resolverConfigPda,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          authority: keeper.publicKey,
+        })
+        .preInstructions(
+          signer
+            ? [
+                Ed25519Program.createInstructionWithPrivateKey({
+                  privateKey: signer.secretKey,
+                  message: attestationMessage(attestedMarketPda, FINAL, price, publishTime),
+                }),
+              ]
+            : []
+        )
+        .signers([keeper])
+        .rpc();
+
+    before(async () => {
+      await program.methods
+        .setPricePublishers([publisher.publicKey])
+        .accounts({
+          globalState: globalStatePda,
+          resolverConfig: resolverConfigPda,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+
+      const { market, expiry } = await createExpiringMarket("ETH", new BN(3000_00000000));
+      attestedMarketPda = market;
+      publishTime = expiry;
+
+      await waitForExpiry(expiry);
+    });
+
+    it("fails without an Ed25519 verification instruction", async () => {
+      try {
+        await resolveWithAttestation(finalPrice, null);
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("MissingSignatureVerification");
+      }
+    });
+
+    it("fails when signed by a non-whitelisted key", async () => {
+      try {
+        await resolveWithAttestation(finalPrice, Keypair.generate());
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("SignerMismatch");
+      }
+    });
+
+    it("successfully resolves with a publisher-signed final price", async () => {
+      await resolveWithAttestation(finalPrice, publisher);
+
+      const market = await program.account.market.fetch(attestedMarketPda);
+      expect(market.status).to.deep.equal({ resolved: {} });
+      expect(market.finalPrice.toString()).to.equal(finalPrice.toString());
+      expect(market.outcome).to.deep.equal({ yes: {} });
+    });
+  });
+
+  // ============================================================================
+  // SETTLE POSITIONS TESTS
+  // ============================================================================
+
+  describeSlow("settle_positions", () => {
+    let settleMarketPda: PublicKey;
+    let settleVaultPda: PublicKey;
+    let settlerPositionPda: PublicKey;
+    let settler: Keypair;
+    let settlerUsdc: PublicKey;
+
+    before(async () => {
+      settler = Keypair.generate();
+      
+      // Airdrop SOL
+      await provider.connection.requestAirdrop(settler.publicKey, 5 * LAMPORTS_PER_SOL);
+      await new Promise(resolve => setTimeout(resolve, 1000));
+
+      // Create USDC account for settler
+      settlerUsdc = await createAssociatedTokenAccount(
+        provider.connection,
+        admin,
+        usdcMint,
+        settler.publicKey
+      );
+
+      // Mint USDC to settler
+      await mintTo(
+        provider.connection,
+        admin,
+        usdcMint,
+        settlerUsdc,
+        admin,
+        5000 * USDC_MULTIPLIER
+      );
+
+      const { market, vault, expiry: settleExpiry } = await createExpiringMarket("BTC", new BN(94_000_00000000));
+      settleMarketPda = market;
+      settleVaultPda = vault;
+
+      [settlerPositionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position"), settleMarketPda.toBuffer(), settler.publicKey.toBuffer()],
+        program.programId
+      );
+
+      // Execute a trade so settler has a position
+      const [counterpartyPositionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position"), settleMarketPda.toBuffer(), user1.publicKey.toBuffer()],
+        program.programId
+      );
+
+      const matchArgs = {
+        side: { bid: {} },
+        outcome: { yes: {} },
+        orderType: { limit: {} },
+        price: new BN(400_000),
+        size: new BN(50),
+        expiryTs: new BN(Math.floor(Date.now() / 1000) + 10),
+        clientOrderId: new BN(Date.now()),
+      };
+
+      const counterArgs = {
+        side: { ask: {} },
+        outcome: { yes: {} },
+        orderType: { limit: {} },
+        price: new BN(400_000),
+        size: new BN(50),
+        expiryTs: new BN(Math.floor(Date.now() / 1000) + 10),
+        clientOrderId: new BN(Date.now() + 1),
+      };
+
+      await program.methods
+        .executeMatch(matchArgs, counterArgs, new BN(50))
+        .accounts({
+          globalState: globalStatePda,
+          market: settleMarketPda,
+          vault: settleVaultPda,
+          feeRecipient: feeRecipientUsdc,
+          maker: settler.publicKey,
+          makerPosition: settlerPositionPda,
+          makerUsdc: settlerUsdc,
+          taker: user1.publicKey,
+          takerPosition: counterpartyPositionPda,
+          takerUsdc: user1Usdc,
+          relayer: relayer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([settler, user1, relayer])
+        .rpc();
+
+      await waitForExpiry(settleExpiry);
+
+      // Resolve market at the strike - NO wins ties
+      await program.methods
+        .resolveMarket({ finalPrice: new BN(94_000_00000000) })
+        .accounts({
+          market: settleMarketPda,
+          assetConfig: getAssetConfigPda("BTC"),
+          globalState: globalStatePda,
+          resolverConfig: resolverConfigPda,
+          priceSamples: null,
+          authority: keeper.publicKey,
+        })
+        .signers([keeper])
+        .rpc();
+    });
+
+    it("fails to settle unresolved market", async () => {
+      // We'd need another market for this test since ours is already resolved
+      // Skipping detailed test - covered by resolve_market tests
+      console.log("  (Covered by resolve_market tests)");
+    });
+
+    it("successfully settles a winning position", async () => {
+      const positionBefore = await program.account.userPosition.fetch(settlerPositionPda);
+      const balanceBefore = (await getAccount(provider.connection, settlerUsdc)).amount;
+
+      await program.methods
+        .settlePositions()
+        .accounts({
+          market: settleMarketPda,
+          globalState: globalStatePda,
+          resolverConfig: resolverConfigPda,
+          vault: settleVaultPda,
+          position: settlerPositionPda,
+          userUsdc: settlerUsdc,
+          authority: keeper.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([keeper])
+        .rpc();
+
+      const positionAfter = await program.account.userPosition.fetch(settlerPositionPda);
+      expect(positionAfter.settled).to.be.true;
+      
+      const market = await program.account.market.fetch(settleMarketPda);
+      
+      // If they had winning shares, balance should increase
+      const balanceAfter = (await getAccount(provider.connection, settlerUsdc)).amount;
+      
+      console.log(`  Position settled:`);
+      console.log(`    Payout: ${positionAfter.payout.toNumber()}`);
+      console.log(`    Market outcome: ${JSON.stringify(market.outcome)}`);
+      
+      if (positionAfter.payout.toNumber() > 0) {
+        expect(Number(balanceAfter)).to.be.greaterThan(Number(balanceBefore));
+      }
+    });
+
+    it("fails to settle an already settled position", async () => {
+      try {
+        await program.methods
+          .settlePositions()
+          .accounts({
+            market: settleMarketPda,
+            globalState: globalStatePda,
+            resolverConfig: resolverConfigPda,
+            vault: settleVaultPda,
+            position: settlerPositionPda,
+            userUsdc: settlerUsdc,
+            authority: keeper.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([keeper])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.satisfy(
+          (msg: string) => msg.includes("PositionAlreadySettled") || msg.includes("settled")
+        );
+      }
+    });
+  });
 
-      // Reset fees for other tests
-      after(async () => {
+  // ============================================================================
+  // RESOLUTION METHOD (TWAP / MEDIAN) TESTS
+  // ============================================================================
+
+  describe("set_resolution_method", () => {
+    let twapMarketPda: PublicKey;
+
+    before(async () => {
+      const expiry = alignedExpiry(3600, 2 * 3600);
+
+      [twapMarketPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("market"),
+          Buffer.from("BTC"),
+          Buffer.from("1h"),
+          expiry.toArrayLike(Buffer, "le", 8),
+          Buffer.from([0]),
+        ],
+        program.programId
+      );
+      const vault = await anchor.utils.token.associatedAddress({ mint: usdcMint, owner: twapMarketPda });
+
+      await program.methods
+        .initializeMarket("BTC", "1h", new BN(95000_00000000), expiry, 0)
+        .accounts({
+          globalState: globalStatePda,
+          assetConfig: getAssetConfigPda("BTC"),
+          timeframeConfig: getTimeframeConfigPda("1h"),
+          market: twapMarketPda,
+          marketGroup: getMarketGroupPda("BTC", "1h", expiry),
+          marketSeries: getMarketSeriesPda("BTC", "1h"),
+          vault,
+          usdcMint: usdcMint,
+          authority: relayer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([relayer])
+        .rpc();
+    });
+
+    it("successfully sets a TWAP resolution window", async () => {
+      await program.methods
+        .setResolutionMethod({ twap: {} }, new BN(300), 3)
+        .accounts({
+          market: twapMarketPda,
+          globalState: globalStatePda,
+          authority: relayer.publicKey,
+        })
+        .signers([relayer])
+        .rpc();
+
+      const market = await program.account.market.fetch(twapMarketPda);
+      expect(market.resolutionMethod).to.deep.equal({ twap: {} });
+      expect(market.resolutionWindow.toNumber()).to.equal(300);
+      expect(market.resolutionSamples).to.equal(3);
+    });
+
+    it("fails with a window longer than the timeframe", async () => {
+      try {
         await program.methods
-          .updateFees(0, 10)
+          .setResolutionMethod({ median: {} }, new BN(7200), 5)
           .accounts({
+            market: twapMarketPda,
             globalState: globalStatePda,
-            admin: admin.publicKey,
+            authority: relayer.publicKey,
           })
-          .signers([admin])
+          .signers([relayer])
           .rpc();
-      });
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("InvalidResolutionMethod");
+      }
+    });
+
+    it("fails when called by someone other than the market authority or admin", async () => {
+      try {
+        await program.methods
+          .setResolutionMethod({ spot: {} }, new BN(0), 0)
+          .accounts({
+            market: twapMarketPda,
+            globalState: globalStatePda,
+            authority: user1.publicKey,
+          })
+          .signers([user1])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("Unauthorized");
+      }
+    });
+
+    it("fails to record samples for a spot market", async () => {
+      const [priceSamplesPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("price_samples"), marketPda.toBuffer()],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .recordPriceSample()
+          .accounts({
+            market: marketPda,
+            assetConfig: getAssetConfigPda("BTC"),
+            priceSamples: priceSamplesPda,
+            priceUpdate: Keypair.generate().publicKey,
+            payer: keeper.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([keeper])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("InvalidResolutionMethod");
+      }
+    });
+  });
+
+  // ============================================================================
+  // CATEGORICAL (BUCKET) MARKET TESTS
+  // ============================================================================
+
+  describe("categorical markets", () => {
+    // "Where will ETH close: <=3000, 3000-3100, 3100-3200, >3200"
+    const BUCKET_BOUNDS = [new BN(3000_00000000), new BN(3100_00000000), new BN(3200_00000000)];
+    let bucketMarketPda: PublicKey;
+    let bucketVaultPda: PublicKey;
+
+    const placeBucketOrder = async (user: Keypair, userUsdc: PublicKey, side: any, bucket: number, price: BN, size: BN) => {
+      const clientOrderId = new BN(Date.now() + Math.floor(Math.random() * 1000));
+      const [orderPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("order"), bucketMarketPda.toBuffer(), user.publicKey.toBuffer(), clientOrderId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      await program.methods
+        .placeOrder({
+          side,
+          outcome: { bucket: { "0": bucket } },
+          orderType: { limit: {} },
+          price,
+          size,
+          expiryTs: new BN(Math.floor(Date.now() / 1000) + 3600),
+          clientOrderId,
+        })
+        .accounts({
+          globalState: globalStatePda,
+          market: bucketMarketPda,
+          order: orderPda,
+          vault: bucketVaultPda,
+          userUsdc,
+          user: user.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      return orderPda;
+    };
+
+    before(async () => {
+      const expiry = alignedExpiry(4 * 3600, 4 * 3600);
+
+      [bucketMarketPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("market"),
+          Buffer.from("ETH"),
+          Buffer.from("4h"),
+          expiry.toArrayLike(Buffer, "le", 8),
+          Buffer.from([0]),
+        ],
+        program.programId
+      );
+      bucketVaultPda = await anchor.utils.token.associatedAddress({ mint: usdcMint, owner: bucketMarketPda });
+
+      // Created pending so the buckets can be set before trading starts
+      await program.methods
+        .initializeMarket("ETH", "4h", new BN(0), expiry, 0)
+        .accounts({
+          globalState: globalStatePda,
+          assetConfig: getAssetConfigPda("ETH"),
+          timeframeConfig: getTimeframeConfigPda("4h"),
+          market: bucketMarketPda,
+          marketGroup: getMarketGroupPda("ETH", "4h", expiry),
+          marketSeries: getMarketSeriesPda("ETH", "4h"),
+          vault: bucketVaultPda,
+          usdcMint: usdcMint,
+          authority: relayer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([relayer])
+        .rpc();
+    });
+
+    it("fails with bucket bounds that are not ascending", async () => {
+      try {
+        await program.methods
+          .setMarketBuckets([new BN(3100_00000000), new BN(3000_00000000)])
+          .accounts({
+            market: bucketMarketPda,
+            globalState: globalStatePda,
+            authority: relayer.publicKey,
+          })
+          .signers([relayer])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("InvalidBucketBounds");
+      }
+    });
+
+    it("fails when called by someone other than the market authority or admin", async () => {
+      try {
+        await program.methods
+          .setMarketBuckets(BUCKET_BOUNDS)
+          .accounts({
+            market: bucketMarketPda,
+            globalState: globalStatePda,
+            authority: user1.publicKey,
+          })
+          .signers([user1])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("Unauthorized");
+      }
+    });
+
+    it("successfully splits a pending market into four buckets", async () => {
+      await program.methods
+        .setMarketBuckets(BUCKET_BOUNDS)
+        .accounts({
+          market: bucketMarketPda,
+          globalState: globalStatePda,
+          authority: relayer.publicKey,
+        })
+        .signers([relayer])
+        .rpc();
+
+      const market = await program.account.market.fetch(bucketMarketPda);
+      expect(market.marketType).to.deep.equal({ categorical: {} });
+      expect(market.numBuckets).to.equal(4);
+      expect(market.bucketBounds.slice(0, 3).map((bound: BN) => bound.toString())).to.deep.equal(
+        BUCKET_BOUNDS.map((bound) => bound.toString())
+      );
+
+      // Open trading (the reference price does not affect a categorical outcome)
+      await program.methods
+        .activateMarket(new BN(3050_00000000))
+        .accounts({
+          market: bucketMarketPda,
+          assetConfig: getAssetConfigPda("ETH"),
+          marketSeries: getMarketSeriesPda("ETH", "4h"),
+          authority: relayer.publicKey,
+        })
+        .signers([relayer])
+        .rpc();
+    });
+
+    it("fails to change the buckets once the market is open", async () => {
+      try {
+        await program.methods
+          .setMarketBuckets([])
+          .accounts({
+            market: bucketMarketPda,
+            globalState: globalStatePda,
+            authority: relayer.publicKey,
+          })
+          .signers([relayer])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("MarketNotPending");
+      }
+    });
+
+    it("fails to place an order on a bucket that does not exist", async () => {
+      try {
+        await placeBucketOrder(user1, user1Usdc, { bid: {} }, 4, new BN(300_000), new BN(10_000_000));
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("InvalidOutcome");
+      }
+    });
+
+    it("mints a complete set when a bucket bid matches a bucket ask", async () => {
+      const size = new BN(10_000_000); // 10 contracts
+      const price = new BN(300_000); // $0.30 for "3000-3100"
+
+      // User1 buys bucket 1 (locks $3), user2 sells it (locks $7 for the other three buckets)
+      const makerOrder = await placeBucketOrder(user1, user1Usdc, { bid: {} }, 1, price, size);
+      const takerOrder = await placeBucketOrder(user2, user2Usdc, { ask: {} }, 1, price, size);
+
+      const [user1PositionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position"), bucketMarketPda.toBuffer(), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      const [user2PositionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position"), bucketMarketPda.toBuffer(), user2.publicKey.toBuffer()],
+        program.programId
+      );
+
+      const orderArgs = {
+        side: { bid: {} },
+        outcome: { bucket: { "0": 1 } },
+        orderType: { limit: {} },
+        price,
+        size,
+        expiryTs: new BN(0),
+        clientOrderId: new BN(0),
+      };
+
+      await program.methods
+        .executeMatch(orderArgs, orderArgs, size)
+        .accounts({
+          globalState: globalStatePda,
+          market: bucketMarketPda,
+          vault: bucketVaultPda,
+          feeRecipient: feeRecipientUsdc,
+          maker: user1.publicKey,
+          makerPosition: user1PositionPda,
+          makerUsdc: user1Usdc,
+          makerOrder,
+          taker: user2.publicKey,
+          takerPosition: user2PositionPda,
+          takerUsdc: user2Usdc,
+          takerOrder,
+          sellerUsdcReceive: null,
+          relayer: relayer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([relayer])
+        .rpc();
+
+      const user1Position = await program.account.userPosition.fetch(user1PositionPda);
+      const user2Position = await program.account.userPosition.fetch(user2PositionPda);
+
+      expect(user1Position.bucketShares.slice(0, 4).map((shares: BN) => shares.toNumber())).to.deep.equal([0, 10_000_000, 0, 0]);
+      expect(user2Position.bucketShares.slice(0, 4).map((shares: BN) => shares.toNumber())).to.deep.equal([10_000_000, 0, 10_000_000, 10_000_000]);
+      expect(user1Position.yesShares.toNumber()).to.equal(0);
+      expect(user2Position.noShares.toNumber()).to.equal(0);
+
+      // One $1 complete set per contract
+      const market = await program.account.market.fetch(bucketMarketPda);
+      expect(market.openInterest.toNumber()).to.equal(10_000_000);
     });
+  });
 
-    describe("transfer_admin", () => {
-      let newAdmin: Keypair;
+  // ============================================================================
+  // SCALAR MARKET TESTS
+  // ============================================================================
 
-      before(async () => {
-        newAdmin = Keypair.generate();
-        await provider.connection.requestAirdrop(newAdmin.publicKey, 2 * LAMPORTS_PER_SOL);
-        await new Promise(resolve => setTimeout(resolve, 1000)); // Wait for airdrop
-      });
+  describe("scalar markets", () => {
+    // LONG pays $0 at or below $100 and $1 at or above $200
+    const LOWER_BOUND = new BN(100_00000000);
+    const UPPER_BOUND = new BN(200_00000000);
+    let scalarMarketPda: PublicKey;
+    let scalarVaultPda: PublicKey;
 
-      it("successfully transfers admin authority", async () => {
+    before(async () => {
+      const expiry = alignedExpiry(24 * 3600, 24 * 3600);
+
+      [scalarMarketPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("market"),
+          Buffer.from("SOL"),
+          Buffer.from("24h"),
+          expiry.toArrayLike(Buffer, "le", 8),
+          Buffer.from([0]),
+        ],
+        program.programId
+      );
+      scalarVaultPda = await anchor.utils.token.associatedAddress({ mint: usdcMint, owner: scalarMarketPda });
+
+      await program.methods
+        .initializeMarket("SOL", "24h", new BN(0), expiry, 0)
+        .accounts({
+          globalState: globalStatePda,
+          assetConfig: getAssetConfigPda("SOL"),
+          timeframeConfig: getTimeframeConfigPda("24h"),
+          market: scalarMarketPda,
+          marketGroup: getMarketGroupPda("SOL", "24h", expiry),
+          marketSeries: getMarketSeriesPda("SOL", "24h"),
+          vault: scalarVaultPda,
+          usdcMint: usdcMint,
+          authority: relayer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([relayer])
+        .rpc();
+    });
+
+    it("fails with an upper bound that is not above the lower bound", async () => {
+      try {
         await program.methods
-          .transferAdmin()
+          .setScalarBounds(UPPER_BOUND, LOWER_BOUND)
           .accounts({
+            market: scalarMarketPda,
             globalState: globalStatePda,
-            admin: admin.publicKey,
-            newAdmin: newAdmin.publicKey,
+            authority: relayer.publicKey,
           })
-          .signers([admin])
+          .signers([relayer])
           .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("InvalidScalarBounds");
+      }
+    });
 
-        const globalState = await program.account.globalState.fetch(globalStatePda);
-        expect(globalState.admin.toBase58()).to.equal(newAdmin.publicKey.toBase58());
-      });
+    it("successfully sets the bounds of a pending market", async () => {
+      await program.methods
+        .setScalarBounds(LOWER_BOUND, UPPER_BOUND)
+        .accounts({
+          market: scalarMarketPda,
+          globalState: globalStatePda,
+          authority: relayer.publicKey,
+        })
+        .signers([relayer])
+        .rpc();
 
-      it("fails when old admin tries to act", async () => {
-        try {
-          await program.methods
-            .pauseProtocol(true, "Should fail")
-            .accounts({
-              globalState: globalStatePda,
-              admin: admin.publicKey, // Old admin
-            })
-            .signers([admin])
-            .rpc();
-          expect.fail("Should have thrown an error");
-        } catch (err: any) {
-          expect(err.error?.errorCode?.code || err.message).to.satisfy(
-            (msg: string) => msg.includes("Unauthorized") || msg.includes("unauthorized") || msg.includes("constraint")
-          );
-        }
-      });
+      const market = await program.account.market.fetch(scalarMarketPda);
+      expect(market.marketType).to.deep.equal({ scalar: {} });
+      expect(market.lowerBound.toString()).to.equal(LOWER_BOUND.toString());
+      expect(market.upperBound.toString()).to.equal(UPPER_BOUND.toString());
+      expect(market.numBuckets).to.equal(0);
 
-      // Transfer back for other tests
-      after(async () => {
+      await program.methods
+        .activateMarket(new BN(150_00000000))
+        .accounts({
+          market: scalarMarketPda,
+          assetConfig: getAssetConfigPda("SOL"),
+          marketSeries: getMarketSeriesPda("SOL", "24h"),
+          authority: relayer.publicKey,
+        })
+        .signers([relayer])
+        .rpc();
+    });
+
+    it("fails to place a bucket order on a scalar market", async () => {
+      const clientOrderId = new BN(Date.now());
+      const [orderPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("order"), scalarMarketPda.toBuffer(), user1.publicKey.toBuffer(), clientOrderId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      try {
         await program.methods
-          .transferAdmin()
+          .placeOrder({
+            side: { bid: {} },
+            outcome: { bucket: { "0": 0 } },
+            orderType: { limit: {} },
+            price: new BN(500_000),
+            size: new BN(1_000_000),
+            expiryTs: new BN(Math.floor(Date.now() / 1000) + 3600),
+            clientOrderId,
+          })
           .accounts({
             globalState: globalStatePda,
-            admin: newAdmin.publicKey,
-            newAdmin: admin.publicKey,
+            market: scalarMarketPda,
+            order: orderPda,
+            vault: scalarVaultPda,
+            userUsdc: user1Usdc,
+            user: user1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
           })
-          .signers([newAdmin])
+          .signers([user1])
           .rpc();
-      });
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("InvalidOutcome");
+      }
     });
   });
 
   // ============================================================================
-  // RESOLVE MARKET TESTS
+  // ONE-TOUCH (BARRIER) MARKET TESTS
   // ============================================================================
 
-  // NOTE: These tests are skipped because they require waiting 65+ seconds for market expiry
-  // The contract enforces expiry > now + 60s, making fast tests impossible without clock mocking
-  // Run with: SLOW_TESTS=1 anchor test (not implemented - would need custom test runner)
-  describe.skip("resolve_market", () => {
-    let resolveMarketPda: PublicKey;
-    let resolveVaultPda: PublicKey;
-    let mockOracle: Keypair;
+  describe("barrier markets", () => {
+    // "Will BTC touch $100,000 before expiry?"
+    const BARRIER_PRICE = new BN(100_000_00000000);
+    let barrierMarketPda: PublicKey;
 
     before(async () => {
-      // Create a market that will expire soon for testing
-      const shortExpiry = new BN(Math.floor(Date.now() / 1000) + 65); // 65 seconds from now (must be > 60s minimum)
-      
-      mockOracle = Keypair.generate();
-      await provider.connection.requestAirdrop(mockOracle.publicKey, LAMPORTS_PER_SOL);
+      const expiry = alignedExpiry(4 * 3600, 4 * 3600);
 
-      [resolveMarketPda] = PublicKey.findProgramAddressSync(
+      [barrierMarketPda] = PublicKey.findProgramAddressSync(
         [
           Buffer.from("market"),
-          Buffer.from("ETH"),
-          Buffer.from("15m"),
-          shortExpiry.toArrayLike(Buffer, "le", 8),
+          Buffer.from("BTC"),
+          Buffer.from("4h"),
+          expiry.toArrayLike(Buffer, "le", 8),
+          Buffer.from([0]),
         ],
         program.programId
       );
-
-      resolveVaultPda = await anchor.utils.token.associatedAddress({
-        mint: usdcMint,
-        owner: resolveMarketPda,
-      });
+      const vault = await anchor.utils.token.associatedAddress({ mint: usdcMint, owner: barrierMarketPda });
 
       await program.methods
-        .initializeMarket("ETH", "15m", new BN(3000_00000000), shortExpiry)
+        .initializeMarket("BTC", "4h", new BN(0), expiry, 0)
         .accounts({
           globalState: globalStatePda,
-          market: resolveMarketPda,
-          vault: resolveVaultPda,
+          assetConfig: getAssetConfigPda("BTC"),
+          timeframeConfig: getTimeframeConfigPda("4h"),
+          market: barrierMarketPda,
+          marketGroup: getMarketGroupPda("BTC", "4h", expiry),
+          marketSeries: getMarketSeriesPda("BTC", "4h"),
+          vault,
           usdcMint: usdcMint,
           authority: relayer.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -1166,36 +4525,237 @@ describe("degen-terminal", () => {
         })
         .signers([relayer])
         .rpc();
+    });
+
+    it("successfully sets an upward barrier on a pending market", async () => {
+      await program.methods
+        .setBarrier(BARRIER_PRICE, { up: {} })
+        .accounts({
+          market: barrierMarketPda,
+          globalState: globalStatePda,
+          authority: relayer.publicKey,
+        })
+        .signers([relayer])
+        .rpc();
 
-      // Wait for market to expire
-      await new Promise(resolve => setTimeout(resolve, 6000));
+      const market = await program.account.market.fetch(barrierMarketPda);
+      expect(market.marketType).to.deep.equal({ barrier: {} });
+      expect(market.barrierPrice.toString()).to.equal(BARRIER_PRICE.toString());
+      expect(market.barrierDirection).to.deep.equal({ up: {} });
+
+      await program.methods
+        .activateMarket(new BN(95_000_00000000))
+        .accounts({
+          market: barrierMarketPda,
+          assetConfig: getAssetConfigPda("BTC"),
+          marketSeries: getMarketSeriesPda("BTC", "4h"),
+          authority: relayer.publicKey,
+        })
+        .signers([relayer])
+        .rpc();
     });
 
-    it("fails to resolve market before expiry", async () => {
-      // Create another market that hasn't expired
-      const futureExpiry = new BN(Math.floor(Date.now() / 1000) + 600);
-      
-      const [futureMarketPda] = PublicKey.findProgramAddressSync(
+    it("fails to resolve a binary market on a touch", async () => {
+      try {
+        await program.methods
+          .resolveMarketOnTouch()
+          .accounts({
+            market: marketPda,
+            assetConfig: getAssetConfigPda(ASSET),
+            priceUpdate: Keypair.generate().publicKey,
+            authority: keeper.publicKey,
+          })
+          .signers([keeper])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("InvalidResolutionMethod");
+      }
+    });
+
+    it("fails with a price account not owned by the Pyth receiver", async () => {
+      try {
+        await program.methods
+          .resolveMarketOnTouch()
+          .accounts({
+            market: barrierMarketPda,
+            assetConfig: getAssetConfigPda("BTC"),
+            priceUpdate: Keypair.generate().publicKey,
+            authority: keeper.publicKey,
+          })
+          .signers([keeper])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("InvalidOracle");
+      }
+
+      // Still open - a failed proof does not stop trading
+      const market = await program.account.market.fetch(barrierMarketPda);
+      expect(market.status).to.deep.equal({ open: {} });
+    });
+  });
+
+  // ============================================================================
+  // MARKET SERIES TESTS
+  // ============================================================================
+
+  describe("market series", () => {
+    const SERIES_MARKET_COUNT = 2;
+    const seriesMarkets: PublicKey[] = [];
+    let firstExpiry: BN;
+
+    before(async () => {
+      // Two consecutive SOL 15m slots: the second starts when the first expires
+      firstExpiry = alignedExpiry(15 * 60, 15 * 60);
+
+      for (let i = 0; i < SERIES_MARKET_COUNT; i++) {
+        const expiry = firstExpiry.add(new BN(i * 15 * 60));
+        const [market] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("market"),
+            Buffer.from("SOL"),
+            Buffer.from("15m"),
+            expiry.toArrayLike(Buffer, "le", 8),
+            Buffer.from([0]),
+          ],
+          program.programId
+        );
+        const vault = await anchor.utils.token.associatedAddress({ mint: usdcMint, owner: market });
+
+        await program.methods
+          .initializeMarket("SOL", "15m", new BN(0), expiry, 0)
+          .accounts({
+            globalState: globalStatePda,
+            assetConfig: getAssetConfigPda("SOL"),
+            timeframeConfig: getTimeframeConfigPda("15m"),
+            market,
+            marketGroup: getMarketGroupPda("SOL", "15m", expiry),
+            marketSeries: getMarketSeriesPda("SOL", "15m"),
+            vault,
+            usdcMint: usdcMint,
+            authority: relayer.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([relayer])
+          .rpc();
+
+        seriesMarkets.push(market);
+      }
+    });
+
+    it("fails to start a series from a pending market", async () => {
+      try {
+        await program.methods
+          .setMarketSeries(true)
+          .accounts({
+            globalState: globalStatePda,
+            headMarket: seriesMarkets[0],
+            marketSeries: getMarketSeriesPda("SOL", "15m"),
+            admin: admin.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([admin])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("InvalidSeriesMarket");
+      }
+    });
+
+    it("successfully starts a series from an activated market", async () => {
+      await program.methods
+        .activateMarket(new BN(150_00000000))
+        .accounts({
+          market: seriesMarkets[0],
+          assetConfig: getAssetConfigPda("SOL"),
+          marketSeries: getMarketSeriesPda("SOL", "15m"),
+          authority: relayer.publicKey,
+        })
+        .signers([relayer])
+        .rpc();
+
+      await program.methods
+        .setMarketSeries(true)
+        .accounts({
+          globalState: globalStatePda,
+          headMarket: seriesMarkets[0],
+          marketSeries: getMarketSeriesPda("SOL", "15m"),
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+
+      const series = await program.account.marketSeries.fetch(getMarketSeriesPda("SOL", "15m"));
+      expect(series.enabled).to.be.true;
+      expect(series.lastMarket.toBase58()).to.equal(seriesMarkets[0].toBase58());
+      expect(series.numRolls.toNumber()).to.equal(0);
+    });
+
+    it("fails when non-admin tries to start a series", async () => {
+      try {
+        await program.methods
+          .setMarketSeries(false)
+          .accounts({
+            globalState: globalStatePda,
+            headMarket: seriesMarkets[0],
+            marketSeries: getMarketSeriesPda("SOL", "15m"),
+            admin: relayer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([relayer])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("Unauthorized");
+      }
+    });
+
+    it("fails to activate the next market with a relayer-chosen strike", async () => {
+      try {
+        await program.methods
+          .activateMarket(new BN(155_00000000))
+          .accounts({
+            market: seriesMarkets[1],
+            assetConfig: getAssetConfigPda("SOL"),
+            marketSeries: getMarketSeriesPda("SOL", "15m"),
+            authority: relayer.publicKey,
+          })
+          .signers([relayer])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("SeriesStrikeRequired");
+      }
+    });
+
+    it("creates later series slots as pending even with a strike", async () => {
+      const expiry = firstExpiry.add(new BN(SERIES_MARKET_COUNT * 15 * 60));
+      const [market] = PublicKey.findProgramAddressSync(
         [
           Buffer.from("market"),
           Buffer.from("SOL"),
-          Buffer.from("1h"),
-          futureExpiry.toArrayLike(Buffer, "le", 8),
+          Buffer.from("15m"),
+          expiry.toArrayLike(Buffer, "le", 8),
+          Buffer.from([0]),
         ],
-        program.programId
-      );
-
-      const futureVaultPda = await anchor.utils.token.associatedAddress({
-        mint: usdcMint,
-        owner: futureMarketPda,
-      });
+        program.programId
+      );
+      const vault = await anchor.utils.token.associatedAddress({ mint: usdcMint, owner: market });
 
       await program.methods
-        .initializeMarket("SOL", "1h", new BN(150_00000000), futureExpiry)
+        .initializeMarket("SOL", "15m", new BN(160_00000000), expiry, 0)
         .accounts({
           globalState: globalStatePda,
-          market: futureMarketPda,
-          vault: futureVaultPda,
+          assetConfig: getAssetConfigPda("SOL"),
+          timeframeConfig: getTimeframeConfigPda("15m"),
+          market,
+          marketGroup: getMarketGroupPda("SOL", "15m", expiry),
+          marketSeries: getMarketSeriesPda("SOL", "15m"),
+          vault,
           usdcMint: usdcMint,
           authority: relayer.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -1205,136 +4765,263 @@ describe("degen-terminal", () => {
         .signers([relayer])
         .rpc();
 
+      const created = await program.account.market.fetch(market);
+      expect(created.status).to.deep.equal({ pending: {} });
+      expect(created.strikePrice.toNumber()).to.equal(0);
+    });
+
+    it("fails to roll before the previous market is resolved", async () => {
       try {
         await program.methods
-          .resolveMarket()
+          .rollSeries()
           .accounts({
-            market: futureMarketPda,
-            oracle: mockOracle.publicKey,
+            marketSeries: getMarketSeriesPda("SOL", "15m"),
+            previousMarket: seriesMarkets[0],
+            nextMarket: seriesMarkets[1],
             authority: keeper.publicKey,
           })
           .signers([keeper])
           .rpc();
         expect.fail("Should have thrown an error");
       } catch (err: any) {
-        expect(err.error?.errorCode?.code || err.message).to.satisfy(
-          (msg: string) => msg.includes("MarketNotExpired") || msg.includes("expired")
-        );
+        expect(err.error?.errorCode?.code || err.message).to.include("MarketNotResolved");
       }
+
+      const market = await program.account.market.fetch(seriesMarkets[1]);
+      expect(market.status).to.deep.equal({ pending: {} });
     });
+  });
+
+  // ============================================================================
+  // MARKET SCHEDULE TESTS
+  // ============================================================================
+
+  describe("market schedules", () => {
+    // Hourly ETH 15m markets, created up to two hours ahead
+    const CADENCE = 3600;
+    const LEAD_TIME = 2 * 3600;
+    const TIP = 1_000_000;
+    const schedulePda = () => getMarketSchedulePda("ETH", "15m");
+
+    const setMarketSchedule = (cadence: number, signer = admin) =>
+      program.methods
+        .setMarketSchedule("ETH", "15m", new BN(cadence), new BN(LEAD_TIME), new BN(TIP), true)
+        .accounts({
+          globalState: globalStatePda,
+          timeframeConfig: getTimeframeConfigPda("15m"),
+          marketSchedule: schedulePda(),
+          marketAuthority: relayer.publicKey,
+          usdcMint: usdcMint,
+          admin: signer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([signer])
+        .rpc();
+
+    // Mirrors MarketSchedule::due_expiry with the 15m min lead time
+    const dueExpiry = async () => {
+      const schedule = await program.account.marketSchedule.fetch(schedulePda());
+      const earliest = Math.floor(Date.now() / 1000) + 60;
+      return BN.max(new BN(Math.ceil(earliest / CADENCE) * CADENCE), schedule.nextExpiry);
+    };
+
+    // Scheduled markets are appended at the group's next strike index
+    const crank = async (expiry: BN) => {
+      const group = await program.account.marketGroup.fetchNullable(getMarketGroupPda("ETH", "15m", expiry));
+      const [market] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("market"),
+          Buffer.from("ETH"),
+          Buffer.from("15m"),
+          expiry.toArrayLike(Buffer, "le", 8),
+          Buffer.from([group ? group.numMarkets : 0]),
+        ],
+        program.programId
+      );
+      const vault = await anchor.utils.token.associatedAddress({ mint: usdcMint, owner: market });
 
-    it("successfully resolves an expired market", async () => {
-      // Note: This test uses a mock oracle. In production, use actual Switchboard/Pyth
       await program.methods
-        .resolveMarket()
+        .crankCreateMarket(expiry)
         .accounts({
-          market: resolveMarketPda,
-          oracle: mockOracle.publicKey,
-          authority: keeper.publicKey,
+          globalState: globalStatePda,
+          marketSchedule: schedulePda(),
+          assetConfig: getAssetConfigPda("ETH"),
+          timeframeConfig: getTimeframeConfigPda("15m"),
+          market,
+          marketGroup: getMarketGroupPda("ETH", "15m", expiry),
+          vault,
+          usdcMint: usdcMint,
+          cranker: keeper.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
         })
         .signers([keeper])
         .rpc();
 
-      const market = await program.account.market.fetch(resolveMarketPda);
-      expect(market.status).to.deep.equal({ resolved: {} });
-      expect(market.outcome).to.not.deep.equal({ pending: {} });
-      expect(market.finalPrice.toNumber()).to.be.greaterThan(0);
-      expect(market.resolvedAt.toNumber()).to.be.greaterThan(0);
+      return market;
+    };
 
-      console.log(`  Market resolved with outcome: ${JSON.stringify(market.outcome)}`);
-      console.log(`  Final price: ${market.finalPrice.toNumber()}`);
+    it("fails with a cadence off the timeframe grid", async () => {
+      try {
+        await setMarketSchedule(1000);
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("InvalidScheduleConfig");
+      }
     });
 
-    it("fails to resolve an already resolved market", async () => {
+    it("fails when non-admin tries to set a schedule", async () => {
       try {
-        await program.methods
-          .resolveMarket()
-          .accounts({
-            market: resolveMarketPda,
-            oracle: mockOracle.publicKey,
-            authority: keeper.publicKey,
-          })
-          .signers([keeper])
-          .rpc();
+        await setMarketSchedule(CADENCE, relayer);
         expect.fail("Should have thrown an error");
       } catch (err: any) {
-        expect(err.error?.errorCode?.code || err.message).to.satisfy(
-          (msg: string) => msg.includes("MarketAlreadyResolved") || msg.includes("resolved")
-        );
+        expect(err.error?.errorCode?.code || err.message).to.include("Unauthorized");
       }
     });
-  });
 
-  // ============================================================================
-  // SETTLE POSITIONS TESTS
-  // ============================================================================
+    it("successfully creates a schedule", async () => {
+      await setMarketSchedule(CADENCE);
 
-  // NOTE: Skipped - requires market to be resolved first (see resolve_market skip note above)
-  describe.skip("settle_positions", () => {
-    let settleMarketPda: PublicKey;
-    let settleVaultPda: PublicKey;
-    let settlerPositionPda: PublicKey;
-    let settler: Keypair;
-    let settlerUsdc: PublicKey;
-    let mockOracle: Keypair;
+      const schedule = await program.account.marketSchedule.fetch(schedulePda());
+      expect(schedule.enabled).to.be.true;
+      expect(schedule.cadence.toNumber()).to.equal(CADENCE);
+      expect(schedule.marketAuthority.toBase58()).to.equal(relayer.publicKey.toBase58());
+      expect(schedule.numCreated.toNumber()).to.equal(0);
+    });
 
-    before(async () => {
-      settler = Keypair.generate();
-      mockOracle = Keypair.generate();
-      
-      // Airdrop SOL
-      await provider.connection.requestAirdrop(settler.publicKey, 5 * LAMPORTS_PER_SOL);
-      await provider.connection.requestAirdrop(mockOracle.publicKey, LAMPORTS_PER_SOL);
-      await new Promise(resolve => setTimeout(resolve, 1000));
+    it("fails to crank an unfunded schedule", async () => {
+      try {
+        await crank(await dueExpiry());
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("ScheduleUnderfunded");
+      }
+    });
 
-      // Create USDC account for settler
-      settlerUsdc = await createAssociatedTokenAccount(
-        provider.connection,
-        admin,
-        usdcMint,
-        settler.publicKey
+    it("fails to crank a slot other than the due one", async () => {
+      await provider.sendAndConfirm(
+        new Transaction().add(
+          SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            toPubkey: schedulePda(),
+            lamports: LAMPORTS_PER_SOL,
+          })
+        )
       );
 
-      // Mint USDC to settler
-      await mintTo(
-        provider.connection,
-        admin,
-        usdcMint,
-        settlerUsdc,
-        admin,
-        5000 * USDC_MULTIPLIER
-      );
+      try {
+        await crank((await dueExpiry()).add(new BN(CADENCE)));
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("InvalidExpiry");
+      }
+    });
 
-      // Create a market that expires quickly
-      const settleExpiry = new BN(Math.floor(Date.now() / 1000) + 65); // must be > 60s minimum
-      
-      [settleMarketPda] = PublicKey.findProgramAddressSync(
+    it("successfully creates the due market and pays the cranker", async () => {
+      const expiry = await dueExpiry();
+      const keeperBefore = await provider.connection.getBalance(keeper.publicKey);
+      const scheduleBefore = await provider.connection.getBalance(schedulePda());
+
+      const market = await crank(expiry);
+
+      const marketAccount = await program.account.market.fetch(market);
+      expect(marketAccount.status).to.deep.equal({ pending: {} });
+      expect(marketAccount.expiryAt.toString()).to.equal(expiry.toString());
+      expect(marketAccount.authority.toBase58()).to.equal(relayer.publicKey.toBase58());
+
+      const schedule = await program.account.marketSchedule.fetch(schedulePda());
+      expect(schedule.nextExpiry.toString()).to.equal(expiry.add(new BN(CADENCE)).toString());
+      expect(schedule.lastMarket.toBase58()).to.equal(market.toBase58());
+      expect(schedule.numCreated.toNumber()).to.equal(1);
+
+      // Rent is paid back, so the cranker nets the tip minus the transaction fee
+      const keeperGain = (await provider.connection.getBalance(keeper.publicKey)) - keeperBefore;
+      expect(keeperGain).to.be.above(0).and.at.most(TIP);
+      expect(scheduleBefore - (await provider.connection.getBalance(schedulePda()))).to.be.above(TIP);
+    });
+
+    it("still cranks a slot whose group was created by initialize_market", async () => {
+      const expiry = await dueExpiry();
+      const [squatted] = PublicKey.findProgramAddressSync(
         [
           Buffer.from("market"),
-          Buffer.from("BTC"),
+          Buffer.from("ETH"),
           Buffer.from("15m"),
-          settleExpiry.toArrayLike(Buffer, "le", 8),
+          expiry.toArrayLike(Buffer, "le", 8),
+          Buffer.from([0]),
         ],
         program.programId
       );
 
-      settleVaultPda = await anchor.utils.token.associatedAddress({
-        mint: usdcMint,
-        owner: settleMarketPda,
-      });
+      await program.methods
+        .initializeMarket("ETH", "15m", new BN(3000_00000000), expiry, 0)
+        .accounts({
+          globalState: globalStatePda,
+          assetConfig: getAssetConfigPda("ETH"),
+          timeframeConfig: getTimeframeConfigPda("15m"),
+          market: squatted,
+          marketGroup: getMarketGroupPda("ETH", "15m", expiry),
+          marketSeries: getMarketSeriesPda("ETH", "15m"),
+          vault: await anchor.utils.token.associatedAddress({ mint: usdcMint, owner: squatted }),
+          usdcMint: usdcMint,
+          authority: user1.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user1])
+        .rpc();
 
-      [settlerPositionPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("position"), settleMarketPda.toBuffer(), settler.publicKey.toBuffer()],
+      const market = await crank(expiry);
+
+      const marketAccount = await program.account.market.fetch(market);
+      expect(marketAccount.strikeIndex).to.equal(1);
+      expect(marketAccount.authority.toBase58()).to.equal(relayer.publicKey.toBase58());
+
+      const group = await program.account.marketGroup.fetch(getMarketGroupPda("ETH", "15m", expiry));
+      expect(group.numMarkets).to.equal(2);
+      expect(group.markets[1].toBase58()).to.equal(market.toBase58());
+    });
+  });
+
+  // ============================================================================
+  // VOID MARKET TESTS
+  // ============================================================================
+
+  describe("void_market", () => {
+    let voidMarketPda: PublicKey;
+    let voidVaultPda: PublicKey;
+
+    before(async () => {
+      const expiry = alignedExpiry(4 * 3600, 4 * 3600);
+
+      [voidMarketPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("market"),
+          Buffer.from("SOL"),
+          Buffer.from("4h"),
+          expiry.toArrayLike(Buffer, "le", 8),
+          Buffer.from([0]),
+        ],
         program.programId
       );
 
-      // Initialize market
+      voidVaultPda = await anchor.utils.token.associatedAddress({
+        mint: usdcMint,
+        owner: voidMarketPda,
+      });
+
       await program.methods
-        .initializeMarket("BTC", "15m", new BN(94_000_00000000), settleExpiry)
+        .initializeMarket("SOL", "4h", new BN(150_00000000), expiry, 0)
         .accounts({
           globalState: globalStatePda,
-          market: settleMarketPda,
-          vault: settleVaultPda,
+          assetConfig: getAssetConfigPda("SOL"),
+          timeframeConfig: getTimeframeConfigPda("4h"),
+          market: voidMarketPda,
+          marketGroup: getMarketGroupPda("SOL", "4h", expiry),
+          marketSeries: getMarketSeriesPda("SOL", "4h"),
+          vault: voidVaultPda,
           usdcMint: usdcMint,
           authority: relayer.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -1343,127 +5030,118 @@ describe("degen-terminal", () => {
         })
         .signers([relayer])
         .rpc();
+    });
 
-      // Execute a trade so settler has a position
-      const [counterpartyPositionPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("position"), settleMarketPda.toBuffer(), user1.publicKey.toBuffer()],
-        program.programId
-      );
+    it("fails when non-admin tries to void a market", async () => {
+      try {
+        await program.methods
+          .voidMarket()
+          .accounts({
+            market: voidMarketPda,
+            globalState: globalStatePda,
+            vault: voidVaultPda,
+            admin: relayer.publicKey,
+          })
+          .signers([relayer])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code || err.message).to.include("Unauthorized");
+      }
+    });
 
-      const matchArgs = {
-        side: { bid: {} },
-        outcome: { yes: {} },
-        orderType: { limit: {} },
-        price: new BN(400_000),
-        size: new BN(50),
-        expiryTs: new BN(Math.floor(Date.now() / 1000) + 10),
-        clientOrderId: new BN(Date.now()),
-      };
+    let voidOrderPda: PublicKey;
 
-      const counterArgs = {
-        side: { ask: {} },
-        outcome: { yes: {} },
-        orderType: { limit: {} },
-        price: new BN(400_000),
-        size: new BN(50),
-        expiryTs: new BN(Math.floor(Date.now() / 1000) + 10),
-        clientOrderId: new BN(Date.now() + 1),
-      };
+    it("tracks escrow of orders resting on the market", async () => {
+      const clientOrderId = new BN(Date.now());
+      [voidOrderPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("order"), voidMarketPda.toBuffer(), user1.publicKey.toBuffer(), clientOrderId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
 
       await program.methods
-        .executeMatch(matchArgs, counterArgs, new BN(50))
+        .placeOrder({
+          side: { bid: {} },
+          outcome: { yes: {} },
+          orderType: { limit: {} },
+          price: new BN(400_000),
+          size: new BN(1_000_000),
+          expiryTs: new BN(Math.floor(Date.now() / 1000) + 3600),
+          clientOrderId,
+        })
         .accounts({
           globalState: globalStatePda,
-          market: settleMarketPda,
-          vault: settleVaultPda,
-          feeRecipient: feeRecipientUsdc,
-          maker: settler.publicKey,
-          makerPosition: settlerPositionPda,
-          makerUsdc: settlerUsdc,
-          taker: user1.publicKey,
-          takerPosition: counterpartyPositionPda,
-          takerUsdc: user1Usdc,
-          relayer: relayer.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
-          systemProgram: SystemProgram.programId,
+          market: voidMarketPda,
+          order: voidOrderPda,
+          vault: voidVaultPda,
+          userUsdc: user1Usdc,
+          user: user1.publicKey,
         })
-        .signers([settler, user1, relayer])
+        .signers([user1])
         .rpc();
 
-      // Wait for market to expire
-      await new Promise(resolve => setTimeout(resolve, 6000));
+      const order = await program.account.order.fetch(voidOrderPda);
+      const market = await program.account.market.fetch(voidMarketPda);
+      expect(market.openOrderEscrow.toNumber()).to.equal(order.lockedAmount.toNumber());
+    });
 
-      // Resolve market
+    it("successfully voids a market as invalid", async () => {
       await program.methods
-        .resolveMarket()
+        .voidMarket()
         .accounts({
-          market: settleMarketPda,
-          oracle: mockOracle.publicKey,
-          authority: keeper.publicKey,
+          market: voidMarketPda,
+          globalState: globalStatePda,
+          vault: voidVaultPda,
+          admin: admin.publicKey,
         })
-        .signers([keeper])
+        .signers([admin])
         .rpc();
-    });
 
-    it("fails to settle unresolved market", async () => {
-      // We'd need another market for this test since ours is already resolved
-      // Skipping detailed test - covered by resolve_market tests
-      console.log("  (Covered by resolve_market tests)");
+      const market = await program.account.market.fetch(voidMarketPda);
+      expect(market.status).to.deep.equal({ resolved: {} });
+      expect(market.outcome).to.deep.equal({ invalid: {} });
+      // The vault only holds the open order's escrow, which is not position collateral
+      expect(market.refundVaultBalance.toNumber()).to.equal(0);
     });
 
-    it("successfully settles a winning position", async () => {
-      const positionBefore = await program.account.userPosition.fetch(settlerPositionPda);
-      const balanceBefore = (await getAccount(provider.connection, settlerUsdc)).amount;
+    it("refunds open-order escrow after the market is voided", async () => {
+      const order = await program.account.order.fetch(voidOrderPda);
+      const balanceBefore = (await getAccount(provider.connection, user1Usdc)).amount;
 
       await program.methods
-        .settlePositions()
+        .cancelOrder()
         .accounts({
-          market: settleMarketPda,
-          vault: settleVaultPda,
-          position: settlerPositionPda,
-          userUsdc: settlerUsdc,
-          authority: keeper.publicKey,
+          market: voidMarketPda,
+          vault: voidVaultPda,
+          userUsdc: user1Usdc,
+          order: voidOrderPda,
+          owner: user1.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
-        .signers([keeper])
+        .signers([user1])
         .rpc();
 
-      const positionAfter = await program.account.userPosition.fetch(settlerPositionPda);
-      expect(positionAfter.settled).to.be.true;
-      
-      const market = await program.account.market.fetch(settleMarketPda);
-      
-      // If they had winning shares, balance should increase
-      const balanceAfter = (await getAccount(provider.connection, settlerUsdc)).amount;
-      
-      console.log(`  Position settled:`);
-      console.log(`    Payout: ${positionAfter.payout.toNumber()}`);
-      console.log(`    Market outcome: ${JSON.stringify(market.outcome)}`);
-      
-      if (positionAfter.payout.toNumber() > 0) {
-        expect(Number(balanceAfter)).to.be.greaterThan(Number(balanceBefore));
-      }
+      const balanceAfter = (await getAccount(provider.connection, user1Usdc)).amount;
+      expect(Number(balanceAfter - balanceBefore)).to.equal(order.lockedAmount.toNumber());
+      const market = await program.account.market.fetch(voidMarketPda);
+      expect(market.openOrderEscrow.toNumber()).to.equal(0);
     });
 
-    it("fails to settle an already settled position", async () => {
+    it("fails to void an already resolved market", async () => {
       try {
         await program.methods
-          .settlePositions()
+          .voidMarket()
           .accounts({
-            market: settleMarketPda,
-            vault: settleVaultPda,
-            position: settlerPositionPda,
-            userUsdc: settlerUsdc,
-            authority: keeper.publicKey,
-            tokenProgram: TOKEN_PROGRAM_ID,
+            market: voidMarketPda,
+            globalState: globalStatePda,
+            vault: voidVaultPda,
+            admin: admin.publicKey,
           })
-          .signers([keeper])
+          .signers([admin])
           .rpc();
         expect.fail("Should have thrown an error");
       } catch (err: any) {
-        expect(err.error?.errorCode?.code || err.message).to.satisfy(
-          (msg: string) => msg.includes("PositionAlreadySettled") || msg.includes("settled")
-        );
+        expect(err.error?.errorCode?.code || err.message).to.include("MarketAlreadyResolved");
       }
     });
   });
@@ -1475,7 +5153,7 @@ describe("degen-terminal", () => {
   describe("edge cases", () => {
     it("correctly calculates fees on trades", async () => {
       // Create fresh market
-      const feeTestExpiry = new BN(Math.floor(Date.now() / 1000) + 300);
+      const feeTestExpiry = alignedExpiry(300, 300);
       
       const [feeMarketPda] = PublicKey.findProgramAddressSync(
         [
@@ -1483,6 +5161,7 @@ describe("degen-terminal", () => {
           Buffer.from("SOL"),
           Buffer.from("5m"),
           feeTestExpiry.toArrayLike(Buffer, "le", 8),
+          Buffer.from([0]),
         ],
         program.programId
       );
@@ -1493,10 +5172,14 @@ describe("degen-terminal", () => {
       });
 
       await program.methods
-        .initializeMarket("SOL", "5m", new BN(150_00000000), feeTestExpiry)
+        .initializeMarket("SOL", "5m", new BN(150_00000000), feeTestExpiry, 0)
         .accounts({
           globalState: globalStatePda,
+          assetConfig: getAssetConfigPda("SOL"),
+          timeframeConfig: getTimeframeConfigPda("5m"),
           market: feeMarketPda,
+          marketGroup: getMarketGroupPda("SOL", "5m", feeTestExpiry),
+          marketSeries: getMarketSeriesPda("SOL", "5m"),
           vault: feeVaultPda,
           usdcMint: usdcMint,
           authority: relayer.publicKey,
@@ -170,7 +170,8 @@ export function deriveMarketPda(
   programId: PublicKey,
   asset: string,
   timeframe: string,
-  expiryTs: BN
+  expiryTs: BN,
+  strikeIndex = 0
 ): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
     [
@@ -178,6 +179,7 @@ export function deriveMarketPda(
       Buffer.from(asset),
       Buffer.from(timeframe),
       expiryTs.toArrayLike(Buffer, "le", 8),
+      Buffer.from([strikeIndex]),
     ],
     programId
   );